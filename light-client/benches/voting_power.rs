@@ -0,0 +1,123 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use signatory::ed25519;
+use signatory::public_key::PublicKeyed;
+use signatory::signature::Signer;
+use signatory_dalek::Ed25519Signer;
+
+use tendermint::block::{
+    header::Version, parts, Commit, CommitSig, CommitSigs, Header, Height, Id,
+};
+use tendermint::{validator, vote, Hash, PublicKey, Signature, Time};
+
+use tendermint_light_client::operations::{
+    BatchVotingPowerCalculator, ProdVotingPowerCalculator, VotingPowerCalculator,
+};
+use tendermint_light_client::types::{SignedHeader, TrustThreshold, ValidatorSet};
+
+const VALIDATORS_COUNT: u8 = 150;
+
+/// Make a header at height 1 along with a commit signed by all the given validators
+fn make_signed_header(signers: &[Ed25519Signer], validators: &[validator::Info]) -> SignedHeader {
+    let time = Time::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap();
+
+    let header = Header {
+        version: Version { block: 10, app: 0 },
+        chain_id: "bench-chain".parse().unwrap(),
+        height: Height::from(1_u64),
+        time,
+        last_block_id: None,
+        last_commit_hash: None,
+        data_hash: None,
+        validators_hash: Hash::Sha256([1; 32]),
+        next_validators_hash: Hash::Sha256([1; 32]),
+        consensus_hash: Hash::Sha256([2; 32]),
+        app_hash: vec![],
+        last_results_hash: None,
+        evidence_hash: None,
+        proposer_address: validators[0].address,
+    };
+
+    let commit_sig =
+        |validator: &validator::Info, signature: Signature| CommitSig::BlockIDFlagCommit {
+            validator_address: validator.address,
+            timestamp: time,
+            signature,
+        };
+
+    let unsigned = Signature::Ed25519(ed25519::Signature::new([0; 64]));
+
+    let mut signed_header = SignedHeader {
+        header,
+        commit: Commit {
+            height: Height::from(1_u64),
+            round: 0,
+            block_id: Id::new(
+                Hash::Sha256([3; 32]),
+                Some(parts::Header::new(1, Hash::Sha256([4; 32]))),
+            ),
+            signatures: CommitSigs::new(
+                validators
+                    .iter()
+                    .map(|validator| commit_sig(validator, unsigned.clone()))
+                    .collect::<Vec<_>>(),
+            ),
+        },
+    };
+
+    // All the votes share the same sign bytes, as they have the same timestamp
+    let sign_bytes = signed_header.signed_votes()[0].sign_bytes();
+
+    signed_header.commit.signatures = CommitSigs::new(
+        signers
+            .iter()
+            .zip(validators)
+            .map(|(signer, validator)| {
+                commit_sig(validator, Signature::Ed25519(signer.sign(&sign_bytes)))
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    signed_header
+}
+
+fn bench_voting_power_in(c: &mut Criterion) {
+    let signers: Vec<_> = (0..VALIDATORS_COUNT)
+        .map(|i| Ed25519Signer::from(&ed25519::Seed::new([i; 32])))
+        .collect();
+
+    let validators: Vec<_> = signers
+        .iter()
+        .map(|signer| {
+            let public_key = PublicKey::from(signer.public_key().unwrap());
+            validator::Info::new(public_key, vote::Power::new(10))
+        })
+        .collect();
+
+    let signed_header = make_signed_header(&signers, &validators);
+    let validator_set = ValidatorSet::new(validators);
+    let trust_threshold = TrustThreshold::default();
+
+    let mut group = c.benchmark_group("voting_power_in");
+
+    group.bench_function("prod", |b| {
+        b.iter(|| {
+            ProdVotingPowerCalculator
+                .voting_power_in(&signed_header, &validator_set, trust_threshold)
+                .unwrap()
+        })
+    });
+
+    group.bench_function("batch", |b| {
+        b.iter(|| {
+            BatchVotingPowerCalculator
+                .voting_power_in(&signed_header, &validator_set, trust_threshold)
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_voting_power_in);
+criterion_main!(benches);
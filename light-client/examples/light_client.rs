@@ -19,7 +19,11 @@ use tendermint_light_client::{
     light_client::{self, LightClient},
     peer_list::PeerList,
     state::State,
-    store::{sled::SledStore, LightStore},
+    store::{
+        memory::{MemoryEvidenceStore, MemoryFaultStore},
+        sled::SledStore,
+        LightStore,
+    },
     types::{Height, PeerId, Status, TrustThreshold},
 };
 
@@ -50,6 +54,12 @@ struct SyncOpts {
         default = "tcp://127.0.0.1:26657"
     )]
     address: tendermint::net::Address,
+    #[options(
+        help = "identifier of the chain to follow",
+        meta = "CHAIN_ID",
+        default = "dockerchain"
+    )]
+    chain_id: tendermint::chain::Id,
     #[options(
         help = "height of the initial trusted state (optional if store already initialized)",
         meta = "HEIGHT"
@@ -121,6 +131,7 @@ fn make_instance(
         },
         trusting_period: Duration::from_secs(36000),
         clock_drift: Duration::from_secs(1),
+        chain_id: opts.chain_id,
     };
 
     let verifier = ProdVerifier::default();
@@ -156,7 +167,9 @@ fn sync_cmd(opts: SyncOpts) {
     let mut supervisor = Supervisor::new(
         peer_list,
         ProdForkDetector::default(),
-        ProdEvidenceReporter::new(peer_addr),
+        ProdEvidenceReporter::new(peer_addr, Some(Duration::from_secs(5))),
+        MemoryEvidenceStore::new(),
+        MemoryFaultStore::new(),
     );
 
     let handle = supervisor.handle();
@@ -10,6 +10,7 @@ use tendermint::{
 };
 
 use tendermint_rpc as rpc;
+use tendermint_rpc::endpoint::{commit, validators};
 
 use crate::{
     bail,
@@ -85,6 +86,10 @@ pub struct ProdIo {
 #[contract_trait]
 impl Io for ProdIo {
     fn fetch_light_block(&self, peer: PeerId, height: AtHeight) -> Result<LightBlock, IoError> {
+        if let AtHeight::At(height) = height {
+            return self.fetch_light_block_at(peer, height);
+        }
+
         let signed_header = self.fetch_signed_header(peer, height)?;
         let height: Height = signed_header.header.height.into();
 
@@ -108,6 +113,34 @@ impl ProdIo {
         Self { peer_map, timeout }
     }
 
+    /// Fetch the commit and both validator sets making up the light block at
+    /// the given height in a single batch request.
+    #[pre(self.peer_map.contains_key(&peer))]
+    fn fetch_light_block_at(&self, peer: PeerId, height: Height) -> Result<LightBlock, IoError> {
+        let mut batch = rpc::Batch::new();
+        let commit_req = batch.add(commit::Request::new(height.into()));
+        let validators_req = batch.add(validators::Request::new(height.into()));
+        let next_validators_req = batch.add(validators::Request::new((height + 1).into()));
+
+        let mut responses = block_on_with_timeout(
+            self.rpc_client_for(peer).perform_batch(batch),
+            peer,
+            self.timeout,
+        )??;
+
+        let signed_header = responses.take(&commit_req)?.signed_header;
+        let validator_set = TMValidatorSet::new(responses.take(&validators_req)?.validators);
+        let next_validator_set =
+            TMValidatorSet::new(responses.take(&next_validators_req)?.validators);
+
+        Ok(LightBlock::new(
+            signed_header,
+            validator_set,
+            next_validator_set,
+            peer,
+        ))
+    }
+
     #[pre(self.peer_map.contains_key(&peer))]
     fn fetch_signed_header(
         &self,
@@ -116,7 +149,7 @@ impl ProdIo {
     ) -> Result<TMSignedHeader, IoError> {
         let rpc_client = self.rpc_client_for(peer);
 
-        let res = block_on(
+        let res = block_on_with_timeout(
             async {
                 match height {
                     AtHeight::Highest => rpc_client.latest_commit().await,
@@ -146,7 +179,7 @@ impl ProdIo {
             AtHeight::At(height) => height,
         };
 
-        let res = block_on(
+        let res = block_on_with_timeout(
             self.rpc_client_for(peer).validators(height),
             peer,
             self.timeout,
@@ -166,21 +199,26 @@ impl ProdIo {
     }
 }
 
-fn block_on<F: std::future::Future>(
-    f: F,
-    peer: PeerId,
-    timeout: Option<Duration>,
-) -> Result<F::Output, IoError> {
-    let mut rt = tokio::runtime::Builder::new()
+/// Run the given future to completion on a new single-threaded runtime.
+pub(crate) fn block_on<F: std::future::Future>(f: F) -> F::Output {
+    tokio::runtime::Builder::new()
         .basic_scheduler()
         .enable_all()
         .build()
-        .unwrap();
+        .unwrap()
+        .block_on(f)
+}
 
-    if let Some(timeout) = timeout {
-        rt.block_on(async { tokio::time::timeout(timeout, f).await })
-            .map_err(|_| IoError::Timeout(peer))
-    } else {
-        Ok(rt.block_on(f))
+/// Run the given future to completion, failing with a timeout of the given peer
+/// if it does not complete within the given `timeout`, if any.
+fn block_on_with_timeout<F: std::future::Future>(
+    f: F,
+    peer: PeerId,
+    timeout: Option<Duration>,
+) -> Result<F::Output, IoError> {
+    match timeout {
+        Some(timeout) => block_on(async { tokio::time::timeout(timeout, f).await })
+            .map_err(|_| IoError::Timeout(peer)),
+        None => Ok(block_on(f)),
     }
 }
@@ -0,0 +1,315 @@
+//! Discovery of full nodes among the peers of a known full node.
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use tendermint::{chain, net};
+use tendermint_rpc as rpc;
+use tendermint_rpc::endpoint::net_info::PeerInfo;
+
+use crate::{
+    components::io::{block_on, IoError},
+    types::PeerId,
+};
+
+/// Options for peer discovery.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Options {
+    /// Maximum number of peers to discover.
+    pub max_peers: usize,
+
+    /// If not empty, only the peers in this list are discovered.
+    #[serde(default)]
+    pub allow: Vec<PeerId>,
+
+    /// The peers in this list are never discovered.
+    #[serde(default)]
+    pub deny: Vec<PeerId>,
+}
+
+/// A full node discovered among the peers of another full node,
+/// along with the address of its RPC endpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscoveredPeer {
+    /// Id of the discovered peer
+    pub peer_id: PeerId,
+    /// Address of the RPC endpoint of the discovered peer
+    pub address: net::Address,
+}
+
+/// Discovers full nodes by querying the `/net_info` endpoint of a known full node,
+/// and probing the RPC endpoints of its peers with `/health` and `/status`.
+///
+/// Only the peers on the given chain, which are reachable, report the expected
+/// peer id and are not catching up are retained.
+#[derive(Clone, Debug)]
+pub struct Discovery {
+    chain_id: chain::Id,
+    options: Options,
+    timeout: Option<Duration>,
+}
+
+impl Discovery {
+    /// Constructs a new discovery for the given chain, with the given options.
+    /// Each request times out after the given `timeout`, if any.
+    pub fn new(chain_id: chain::Id, options: Options, timeout: Option<Duration>) -> Self {
+        Self {
+            chain_id,
+            options,
+            timeout,
+        }
+    }
+
+    /// Discover up to `Options::max_peers` full nodes among the peers of the full node
+    /// at the given address, leaving out the already `known` peers.
+    ///
+    /// ## Errors
+    /// - If the `/net_info` endpoint of the given full node cannot be queried.
+    pub fn discover(
+        &self,
+        address: net::Address,
+        known: &[PeerId],
+    ) -> Result<Vec<DiscoveredPeer>, IoError> {
+        let net_info = block_on(self.rpc_client(address).net_info()).map_err(IoError::IoError)?;
+
+        Ok(self.select(&net_info.peers, known, |peer| self.probe(peer)))
+    }
+
+    /// Select up to `Options::max_peers` candidates among the given peers,
+    /// which pass the given probe.
+    fn select(
+        &self,
+        peers: &[PeerInfo],
+        known: &[PeerId],
+        probe: impl Fn(&DiscoveredPeer) -> bool,
+    ) -> Vec<DiscoveredPeer> {
+        peers
+            .iter()
+            .filter(|peer| self.is_candidate(peer, known))
+            .filter_map(|peer| {
+                rpc_address(peer).map(|address| DiscoveredPeer {
+                    peer_id: peer.node_info.id,
+                    address,
+                })
+            })
+            .filter(|peer| probe(peer))
+            .take(self.options.max_peers)
+            .collect()
+    }
+
+    /// Whether the given peer is on our chain, is not known yet and is allowed
+    /// to be discovered.
+    fn is_candidate(&self, peer: &PeerInfo, known: &[PeerId]) -> bool {
+        let peer_id = peer.node_info.id;
+
+        peer.node_info.network == self.chain_id
+            && !known.contains(&peer_id)
+            && !self.options.deny.contains(&peer_id)
+            && (self.options.allow.is_empty() || self.options.allow.contains(&peer_id))
+    }
+
+    /// Whether the RPC endpoint of the given peer is healthy, reports the expected
+    /// peer id and chain id, and is not catching up.
+    fn probe(&self, peer: &DiscoveredPeer) -> bool {
+        let client = self.rpc_client(peer.address.clone());
+
+        let status = block_on(async {
+            client.health().await?;
+            client.status().await
+        });
+
+        match status {
+            Ok(status) => {
+                status.node_info.id == peer.peer_id
+                    && status.node_info.network == self.chain_id
+                    && !status.sync_info.catching_up
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn rpc_client(&self, address: net::Address) -> rpc::Client {
+        let client = rpc::Client::new(address);
+
+        match self.timeout {
+            Some(timeout) => client.with_request_timeout(timeout),
+            None => client,
+        }
+    }
+}
+
+/// Compute the address of the RPC endpoint of the given peer.
+///
+/// Nodes typically advertise an unspecified or loopback RPC address, in which
+/// case the host of their P2P listen address is used instead, or the remote IP
+/// address of the connection if the latter is not routable either.
+fn rpc_address(peer: &PeerInfo) -> Option<net::Address> {
+    let (scheme, host, port) = match &peer.node_info.other.rpc_address {
+        net::Address::Tcp {
+            scheme, host, port, ..
+        } => (*scheme, host.clone(), *port),
+        net::Address::Unix { .. } => return None,
+    };
+
+    let host = if is_routable(&host) {
+        host
+    } else {
+        match peer.node_info.listen_addr.to_net_address() {
+            Some(net::Address::Tcp { host, .. }) if is_routable(&host) => host,
+            _ => peer.remote_ip.to_string(),
+        }
+    };
+
+    Some(net::Address::Tcp {
+        scheme,
+        peer_id: None,
+        host,
+        port,
+    })
+}
+
+/// Whether the given host can be reached from another machine.
+fn is_routable(host: &str) -> bool {
+    match host.parse::<IpAddr>() {
+        Ok(ip) => !ip.is_unspecified() && !ip.is_loopback(),
+        Err(_) => !host.is_empty() && host != "localhost",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint_rpc::{endpoint::net_info, Response as _};
+
+    /// The peers of `net_info.json`, which are on `cosmoshub-2` and advertise
+    /// an unspecified RPC address.
+    fn peers() -> Vec<PeerInfo> {
+        let json = std::fs::read_to_string("../rpc/tests/support/net_info.json").unwrap();
+        net_info::Response::from_string(json).unwrap().peers
+    }
+
+    fn peer_id(peer: &PeerInfo) -> PeerId {
+        peer.node_info.id
+    }
+
+    fn discovery(options: Options) -> Discovery {
+        Discovery::new("cosmoshub-2".parse().unwrap(), options, None)
+    }
+
+    fn select(discovery: &Discovery, known: &[PeerId]) -> Vec<PeerId> {
+        discovery
+            .select(&peers(), known, |_| true)
+            .iter()
+            .map(|peer| peer.peer_id)
+            .collect()
+    }
+
+    #[test]
+    fn rpc_address_falls_back_to_listen_address() {
+        assert_eq!(
+            rpc_address(&peers()[0]),
+            Some("tcp://11.22.33.44:26657".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn rpc_address_falls_back_to_remote_ip() {
+        assert_eq!(
+            rpc_address(&peers()[1]),
+            Some("tcp://77.66.55.44:26657".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn select_up_to_max_peers() {
+        let peers = peers();
+
+        let options = Options {
+            max_peers: 10,
+            ..Options::default()
+        };
+        assert_eq!(
+            select(&discovery(options), &[]),
+            vec![peer_id(&peers[0]), peer_id(&peers[1])]
+        );
+
+        let options = Options {
+            max_peers: 1,
+            ..Options::default()
+        };
+        assert_eq!(select(&discovery(options), &[]), vec![peer_id(&peers[0])]);
+    }
+
+    #[test]
+    fn select_only_allowed_peers() {
+        let peers = peers();
+
+        let options = Options {
+            max_peers: 10,
+            allow: vec![peer_id(&peers[1])],
+            ..Options::default()
+        };
+        assert_eq!(select(&discovery(options), &[]), vec![peer_id(&peers[1])]);
+    }
+
+    #[test]
+    fn leave_out_denied_and_known_peers() {
+        let peers = peers();
+
+        let options = Options {
+            max_peers: 10,
+            deny: vec![peer_id(&peers[0])],
+            ..Options::default()
+        };
+        assert_eq!(select(&discovery(options), &[]), vec![peer_id(&peers[1])]);
+
+        let options = Options {
+            max_peers: 10,
+            ..Options::default()
+        };
+        assert_eq!(
+            select(&discovery(options), &[peer_id(&peers[1])]),
+            vec![peer_id(&peers[0])]
+        );
+    }
+
+    #[test]
+    fn leave_out_peers_of_other_chains() {
+        let options = Options {
+            max_peers: 10,
+            ..Options::default()
+        };
+        let discovery = Discovery::new("cosmoshub-3".parse().unwrap(), options, None);
+
+        assert!(select(&discovery, &[]).is_empty());
+    }
+
+    #[test]
+    fn leave_out_peers_which_fail_the_probe() {
+        let options = Options {
+            max_peers: 10,
+            ..Options::default()
+        };
+        let selected = discovery(options).select(&peers(), &[], |_| false);
+
+        assert!(selected.is_empty());
+    }
+
+    #[test]
+    fn loopback_and_unspecified_hosts_are_not_routable() {
+        assert!(!is_routable("0.0.0.0"));
+        assert!(!is_routable("127.0.0.1"));
+        assert!(!is_routable("::"));
+        assert!(!is_routable("localhost"));
+        assert!(!is_routable(""));
+    }
+
+    #[test]
+    fn remote_hosts_are_routable() {
+        assert!(is_routable("10.0.0.2"));
+        assert!(is_routable("192.168.1.10"));
+        assert!(is_routable("node.example.com"));
+    }
+}
@@ -33,9 +33,18 @@ pub enum ErrorKind {
     #[error("no witness left")]
     NoWitnessLeft,
 
+    #[error("unknown peer: {0}")]
+    UnknownPeer(PeerId),
+
+    #[error("peer already registered: {0}")]
+    PeerAlreadyRegistered(PeerId),
+
     #[error("fork detected peers={0:?}")]
     ForkDetected(Vec<PeerId>),
 
+    #[error("witness has a conflicting block at the trusted height {0}")]
+    ConflictingTrustedState(Height),
+
     #[error("no initial trusted state")]
     NoInitialTrustedState,
 
@@ -1,63 +1,177 @@
 //! Fork evidence data structures and interfaces.
 
-use crate::{components::io::IoError, types::PeerId};
+use crate::{
+    components::io::{block_on, IoError},
+    types::{LightBlock, PeerId},
+};
 
 use tendermint::abci::transaction::Hash;
+use tendermint::evidence::{AttackType, ConflictingBlock, LightClientAttackEvidence};
+use tendermint::lite::ValidatorSet;
+use tendermint::vote;
 use tendermint_rpc as rpc;
 
 use contracts::{contract_trait, pre};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
 pub use tendermint::evidence::Evidence;
 
+/// Build the evidence of the attack which produced the `conflicting` light block,
+/// given the `trusted` light block at the same height and the `common` light block,
+/// ie. the last block on which both chains agree.
+pub fn attack_evidence(
+    conflicting: &LightBlock,
+    trusted: &LightBlock,
+    common: &LightBlock,
+) -> LightClientAttackEvidence {
+    let attack_type = AttackType::classify(&conflicting.signed_header, &trusted.signed_header);
+
+    let conflicting_block = ConflictingBlock {
+        signed_header: conflicting.signed_header.clone(),
+        validator_set: conflicting.validators.clone(),
+    };
+
+    let byzantine_validators = attack_type.byzantine_validators(
+        &conflicting_block,
+        &trusted.signed_header,
+        &common.validators,
+    );
+
+    // Lunatic attacks are attributed to the validators at the common height,
+    // while other attacks are attributed to the validators at the conflicting height.
+    let reference = match attack_type {
+        AttackType::Lunatic => common,
+        AttackType::Equivocation | AttackType::Amnesia => trusted,
+    };
+
+    LightClientAttackEvidence::new(
+        conflicting_block,
+        reference.signed_header.header.height,
+        byzantine_validators,
+        vote::Power::new(reference.validators.total_power()),
+        reference.signed_header.header.time,
+    )
+}
+
+/// An evidence along with the peers it has been or is yet to be reported to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EvidenceReport {
+    /// The evidence to report
+    pub evidence: Evidence,
+    /// Peers the evidence has yet to be reported to
+    pub pending: Vec<PeerId>,
+    /// Peers which acknowledged the evidence, along with the hash they returned for it
+    pub acknowledged: Vec<(PeerId, Hash)>,
+    /// Peers which were removed from the peer list before acknowledging the evidence
+    #[serde(default)]
+    pub undeliverable: Vec<PeerId>,
+}
+
+impl EvidenceReport {
+    /// Constructs a new report of the given evidence, to be sent to the given peers.
+    pub fn new(evidence: Evidence, peers: Vec<PeerId>) -> Self {
+        Self {
+            evidence,
+            pending: peers,
+            acknowledged: Vec::new(),
+            undeliverable: Vec::new(),
+        }
+    }
+
+    /// Record that the given peer acknowledged the evidence with the given hash.
+    pub fn acknowledge(&mut self, peer: PeerId, hash: Hash) {
+        self.pending.retain(|p| *p != peer);
+        self.acknowledged.push((peer, hash));
+    }
+
+    /// Record that the evidence cannot be delivered to the given peer, which left the peer list.
+    pub fn mark_undeliverable(&mut self, peer: PeerId) {
+        self.pending.retain(|p| *p != peer);
+        self.undeliverable.push(peer);
+    }
+
+    /// Whether the evidence has been either acknowledged by or found undeliverable
+    /// to all peers.
+    pub fn is_complete(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
 /// Interface for reporting evidence to full nodes, typically via the RPC client.
 #[contract_trait]
 pub trait EvidenceReporter: Send {
     /// Report evidence to all connected full nodes.
     fn report(&self, e: Evidence, peer: PeerId) -> Result<Hash, IoError>;
+
+    /// Register the network address of a peer added after the reporter was constructed.
+    fn add_peer(&mut self, _peer: PeerId, _address: tendermint::net::Address) {}
 }
 
 /// Production implementation of the EvidenceReporter component, which reports evidence to full
 /// nodes via RPC.
+///
+/// Evidence which fails to be reported because of a transient error (eg. a timeout)
+/// is reported again according to the reporter's retry policy. This is safe as
+/// full nodes ignore the evidence they already know of.
 #[derive(Clone, Debug)]
 pub struct ProdEvidenceReporter {
     peer_map: HashMap<PeerId, tendermint::net::Address>,
+    timeout: Option<Duration>,
+    retry_policy: rpc::RetryPolicy,
 }
 
 #[contract_trait]
 impl EvidenceReporter for ProdEvidenceReporter {
     #[pre(self.peer_map.contains_key(&peer))]
     fn report(&self, e: Evidence, peer: PeerId) -> Result<Hash, IoError> {
-        let res = block_on(self.rpc_client_for(peer).broadcast_evidence(e));
+        let client = self.rpc_client_for(peer);
+        let res = block_on(client.broadcast_evidence(e));
 
         match res {
             Ok(response) => Ok(response.hash),
+            Err(err) if err.code() == rpc::error::Code::Timeout => Err(IoError::Timeout(peer)),
             Err(err) => Err(IoError::IoError(err)),
         }
     }
+
+    fn add_peer(&mut self, peer: PeerId, address: tendermint::net::Address) {
+        self.peer_map.insert(peer, address);
+    }
 }
 
 impl ProdEvidenceReporter {
     /// Constructs a new ProdEvidenceReporter component.
     ///
     /// A peer map which maps peer IDS to their network address must be supplied.
-    pub fn new(peer_map: HashMap<PeerId, tendermint::net::Address>) -> Self {
-        Self { peer_map }
+    /// Each attempt at reporting evidence times out after the given `timeout`, if any.
+    pub fn new(
+        peer_map: HashMap<PeerId, tendermint::net::Address>,
+        timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            peer_map,
+            timeout,
+            retry_policy: rpc::RetryPolicy::exponential(3, Duration::from_millis(500)),
+        }
+    }
+
+    /// Use the given policy to retry reporting evidence which failed with a transient error.
+    pub fn with_retry_policy(mut self, retry_policy: rpc::RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
     }
 
     // FIXME: Cannot enable precondition because of "autoref lifetime" issue
     // #[pre(self.peer_map.contains_key(&peer))]
     fn rpc_client_for(&self, peer: PeerId) -> rpc::Client {
         let peer_addr = self.peer_map.get(&peer).unwrap().to_owned();
-        rpc::Client::new(peer_addr)
-    }
-}
+        let client = rpc::Client::new(peer_addr).with_retry_policy(self.retry_policy);
 
-fn block_on<F: std::future::Future>(f: F) -> F::Output {
-    tokio::runtime::Builder::new()
-        .basic_scheduler()
-        .enable_all()
-        .build()
-        .unwrap()
-        .block_on(f)
+        match self.timeout {
+            Some(timeout) => client.with_request_timeout(timeout),
+            None => client,
+        }
+    }
 }
@@ -23,9 +23,13 @@ pub enum ForkDetection {
 /// Types of fork
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Fork {
-    /// An actual fork was found for this `LightBlock`
+    /// An actual fork was found between the primary and a witness
     Forked {
+        /// The last block on which the primary and the witness agree
+        common: LightBlock,
+        /// The primary's block at the height where the primary and the witness diverge
         primary: LightBlock,
+        /// The witness's block at the height where the primary and the witness diverge
         witness: LightBlock,
     },
     /// The node has been deemed faulty for this `LightBlock`
@@ -38,26 +42,35 @@ pub enum Fork {
 pub trait ForkDetector: Send {
     /// Detect forks using the given verified block, trusted block,
     /// and list of witnesses to verify the given light block against.
+    ///
+    /// The `trace` contains the blocks the primary verified in order to go
+    /// from the trusted block to the verified block, by increasing height.
     fn detect_forks(
         &self,
         verified_block: &LightBlock,
         trusted_block: &LightBlock,
+        trace: &[LightBlock],
         witnesses: Vec<&Instance>,
     ) -> Result<ForkDetection, Error>;
 }
 
-/// A production-ready fork detector which compares
-/// light blocks fetched from the witnesses by hash.
-/// If the hashes don't match, this fork detector
-/// then attempts to verify the light block pulled from
-/// the witness against a light block containing only
-/// the given trusted state, and then:
+/// A production-ready fork detector which implements the divergence detection
+/// algorithm of the [Light Client Attack Detector specification][1].
+///
+/// For each witness, the light block at the verified height is fetched and
+/// compared by hash with the one verified by the primary. If the hashes don't match,
+/// the primary's verification trace is walked by increasing height, comparing each
+/// block against the witness's block at the same height, until the first conflicting
+/// block is found. The fork detector then attempts to verify the witness's conflicting
+/// block against the last block on which both agree, and then:
 ///
 /// - If the verification succeeds, we have a real fork
-/// - If verification fails because of lack of trust,
+/// - If verification fails because the common block has expired,
 ///   we have a potential fork.
 /// - If verification fails for any other reason, the
 ///   witness is deemed faulty.
+///
+/// [1]: https://github.com/tendermint/spec/blob/master/spec/light-client/detection/detection_003_reviewed.md
 pub struct ProdForkDetector {
     hasher: Box<dyn Hasher>,
 }
@@ -69,6 +82,81 @@ impl ProdForkDetector {
             hasher: Box::new(hasher),
         }
     }
+
+    /// Walk the primary's verification trace to find the first block on which
+    /// the given witness diverges, and check whether the witness's block at that
+    /// height can be verified from the last block both agree on.
+    ///
+    /// Returns `None` if the witness does not diverge from the primary.
+    fn examine_trace(
+        &self,
+        primary_trace: &[&LightBlock],
+        witness_target: LightBlock,
+        witness: &Instance,
+    ) -> Result<Option<Fork>, Error> {
+        let mut witness_state = State::new(MemoryStore::new());
+        witness_state
+            .light_store
+            .insert(witness_target, Status::Unverified);
+
+        let mut common: Option<&LightBlock> = None;
+
+        for &primary_block in primary_trace {
+            let (witness_block, _) = witness
+                .light_client
+                .get_or_fetch_block(primary_block.height(), &mut witness_state)?;
+
+            if self.hasher.hash_header(&witness_block.signed_header.header)
+                == self.hasher.hash_header(&primary_block.signed_header.header)
+            {
+                common = Some(primary_block);
+                continue;
+            }
+
+            let common = match common {
+                Some(common) => common,
+                None => {
+                    // The witness disagrees with our trusted state
+                    let height = witness_block.height();
+                    return Ok(Some(Fork::Faulty(
+                        witness_block,
+                        ErrorKind::ConflictingTrustedState(height),
+                    )));
+                }
+            };
+
+            let mut state = State::new(MemoryStore::new());
+            state.light_store.insert(common.clone(), Status::Verified);
+            state
+                .light_store
+                .insert(witness_block.clone(), Status::Unverified);
+
+            let result = witness
+                .light_client
+                .verify_to_target(witness_block.height(), &mut state);
+
+            let fork = match result {
+                Ok(_) => Fork::Forked {
+                    common: common.clone(),
+                    primary: primary_block.clone(),
+                    witness: witness_block,
+                },
+                Err(e) if e.kind().has_expired() => Fork::Forked {
+                    common: common.clone(),
+                    primary: primary_block.clone(),
+                    witness: witness_block,
+                },
+                Err(e) if e.kind().is_timeout() => {
+                    Fork::Timeout(witness_block.provider, e.kind().clone())
+                }
+                Err(e) => Fork::Faulty(witness_block, e.kind().clone()),
+            };
+
+            return Ok(Some(fork));
+        }
+
+        Ok(None)
+    }
 }
 
 impl Default for ProdForkDetector {
@@ -83,12 +171,21 @@ impl ForkDetector for ProdForkDetector {
         &self,
         verified_block: &LightBlock,
         trusted_block: &LightBlock,
+        trace: &[LightBlock],
         witnesses: Vec<&Instance>,
     ) -> Result<ForkDetection, Error> {
         let primary_hash = self
             .hasher
             .hash_header(&verified_block.signed_header.header);
 
+        // The full trace of the primary, from the trusted block to the verified block
+        let primary_trace = std::iter::once(trusted_block)
+            .chain(trace.iter().filter(|lb| {
+                lb.height() > trusted_block.height() && lb.height() < verified_block.height()
+            }))
+            .chain(std::iter::once(verified_block))
+            .collect::<Vec<_>>();
+
         let mut forks = Vec::with_capacity(witnesses.len());
 
         for witness in witnesses {
@@ -105,33 +202,8 @@ impl ForkDetector for ProdForkDetector {
                 continue;
             }
 
-            state
-                .light_store
-                .insert(trusted_block.clone(), Status::Verified);
-
-            state
-                .light_store
-                .insert(witness_block.clone(), Status::Unverified);
-
-            let result = witness
-                .light_client
-                .verify_to_target(verified_block.height(), &mut state);
-
-            match result {
-                Ok(_) => forks.push(Fork::Forked {
-                    primary: verified_block.clone(),
-                    witness: witness_block,
-                }),
-                Err(e) if e.kind().has_expired() => {
-                    forks.push(Fork::Forked {
-                        primary: verified_block.clone(),
-                        witness: witness_block,
-                    });
-                }
-                Err(e) if e.kind().is_timeout() => {
-                    forks.push(Fork::Timeout(witness_block.provider, e.kind().clone()))
-                }
-                Err(e) => forks.push(Fork::Faulty(witness_block, e.kind().clone())),
+            if let Some(fork) = self.examine_trace(&primary_trace, witness_block, witness)? {
+                forks.push(fork);
             }
         }
 
@@ -14,6 +14,7 @@
 
 pub mod components;
 pub mod contracts;
+pub mod discovery;
 pub mod errors;
 pub mod evidence;
 pub mod fork_detector;
@@ -6,6 +6,7 @@ use contracts::*;
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
 use std::{fmt, time::Duration};
+use tendermint::chain;
 
 use crate::components::{clock::Clock, io::*, scheduler::*, verifier::*};
 use crate::contracts::*;
@@ -13,7 +14,7 @@ use crate::{
     bail,
     errors::{Error, ErrorKind},
     state::State,
-    types::{Height, LightBlock, PeerId, Status, TrustThreshold},
+    types::{Height, LightBlock, PeerId, Status, Time, TrustThreshold},
 };
 
 /// Verification parameters
@@ -36,6 +37,10 @@ pub struct Options {
     /// is the maximum amount that the local clock may drift behind a timestamp from the
     /// blockchain.
     pub clock_drift: Duration,
+
+    /// Identifier of the chain the light client is following. Headers from any
+    /// other chain are rejected.
+    pub chain_id: chain::Id,
 }
 
 /// The light client implements a read operation of a header from the blockchain,
@@ -86,6 +91,11 @@ impl LightClient {
         }
     }
 
+    /// The current time, as given by this light client's clock.
+    pub(crate) fn now(&self) -> Time {
+        self.clock.now()
+    }
+
     /// Attempt to update the light client to the highest block of the primary node.
     ///
     /// Note: This function delegates the actual work to `verify_to_target`.
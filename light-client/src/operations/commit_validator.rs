@@ -5,6 +5,7 @@ use crate::{
 };
 
 use tendermint::block::CommitSig;
+use tendermint::encoding::Encoding;
 use tendermint::lite::types::ValidatorSet as _;
 
 pub trait CommitValidator: Send {
@@ -75,7 +76,7 @@ impl CommitValidator for ProdCommitValidator {
                 bail!(VerificationError::ImplementationSpecific(format!(
                     "Found a faulty signer ({}) not present in the validator set ({})",
                     validator_address,
-                    validator_set.hash()
+                    validator_set.hash_with_encoding(Encoding::from(&signed_header.header.version))
                 )));
             }
         }
@@ -1,12 +1,13 @@
 use crate::types::{Header, ValidatorSet};
 
+use tendermint::encoding::Encoding;
 use tendermint::lite::types::Header as _;
-use tendermint::merkle;
+use tendermint::lite::ValidatorSet as _;
 use tendermint::Hash;
 
 pub trait Hasher: Send {
     fn hash_header(&self, header: &Header) -> Hash;
-    fn hash_validator_set(&self, validator_set: &ValidatorSet) -> Hash;
+    fn hash_validator_set(&self, validator_set: &ValidatorSet, encoding: Encoding) -> Hash;
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -17,14 +18,9 @@ impl Hasher for ProdHasher {
         header.hash()
     }
 
-    /// Compute the Merkle root of the validator set
-    fn hash_validator_set(&self, validator_set: &ValidatorSet) -> Hash {
-        let validator_bytes: Vec<Vec<u8>> = validator_set
-            .validators()
-            .iter()
-            .map(|validator| validator.hash_bytes())
-            .collect();
-
-        Hash::Sha256(merkle::simple_hash_from_byte_vectors(validator_bytes))
+    /// Compute the Merkle root of the validator set, using the encoding
+    /// of the header it belongs to.
+    fn hash_validator_set(&self, validator_set: &ValidatorSet, encoding: Encoding) -> Hash {
+        validator_set.hash_with_encoding(encoding)
     }
 }
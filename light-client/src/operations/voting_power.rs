@@ -6,10 +6,13 @@ use crate::{
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::fmt;
 
 use tendermint::block::CommitSig;
+use tendermint::encoding::Encoding;
 use tendermint::lite::types::TrustThreshold as _;
+use tendermint::validator;
 use tendermint::vote::{SignedVote, Vote};
 
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -89,67 +92,48 @@ impl VotingPowerCalculator for ProdVotingPowerCalculator {
         validator_set: &ValidatorSet,
         trust_threshold: TrustThreshold,
     ) -> Result<VotingPowerTally, VerificationError> {
-        let signatures = &signed_header.commit.signatures;
+        let votes = commit_votes(signed_header, validator_set)?;
 
-        let mut tallied_voting_power = 0_u64;
-        let mut seen_validators = HashSet::new();
+        for vote in &votes {
+            verify_individually(vote)?;
 
-        // Get non-absent votes from the signatures
-        let non_absent_votes = signatures.iter().enumerate().flat_map(|(idx, signature)| {
-            if let Some(vote) = non_absent_vote(signature, idx as u64, &signed_header.commit) {
-                Some((signature, vote))
-            } else {
-                None
-            }
-        });
+            // TODO: Break out of the loop when we have enough voting power.
+            // See https://github.com/informalsystems/tendermint-rs/issues/235
+        }
 
-        for (signature, vote) in non_absent_votes {
-            // Ensure we only count a validator's power once
-            if seen_validators.contains(&vote.validator_address) {
-                bail!(VerificationError::DuplicateValidator(
-                    vote.validator_address
-                ));
-            } else {
-                seen_validators.insert(vote.validator_address);
-            }
+        let voting_power = VotingPowerTally {
+            total: self.total_power_of(validator_set),
+            tallied: tallied_power(&votes),
+            trust_threshold,
+        };
 
-            let validator = match validator_set.validator(vote.validator_address) {
-                Some(validator) => validator,
-                None => continue, // Cannot find matching validator, so we skip the vote
-            };
+        Ok(voting_power)
+    }
+}
 
-            let signed_vote = SignedVote::new(
-                (&vote).into(),
-                signed_header.header.chain_id.as_str(),
-                vote.validator_address,
-                vote.signature,
-            );
-
-            // Check vote is valid
-            let sign_bytes = signed_vote.sign_bytes();
-            if !validator.verify_signature(&sign_bytes, signed_vote.signature()) {
-                bail!(VerificationError::InvalidSignature {
-                    signature: signed_vote.signature().to_vec(),
-                    validator,
-                    sign_bytes,
-                });
-            }
+/// A `VotingPowerCalculator` which verifies the Ed25519 signatures of a commit all at once,
+/// using batch verification, rather than one by one.
+///
+/// If the batch verification fails, the signatures are checked individually in order to
+/// pinpoint the invalid one. Signatures by validators with other types of keys are always
+/// checked individually.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BatchVotingPowerCalculator;
 
-            // If the vote is neither absent nor nil, tally its power
-            if signature.is_commit() {
-                tallied_voting_power += validator.power();
-            } else {
-                // It's OK. We include stray signatures (~votes for nil)
-                // to measure validator availability.
-            }
+impl VotingPowerCalculator for BatchVotingPowerCalculator {
+    fn voting_power_in(
+        &self,
+        signed_header: &SignedHeader,
+        validator_set: &ValidatorSet,
+        trust_threshold: TrustThreshold,
+    ) -> Result<VotingPowerTally, VerificationError> {
+        let votes = commit_votes(signed_header, validator_set)?;
 
-            // TODO: Break out of the loop when we have enough voting power.
-            // See https://github.com/informalsystems/tendermint-rs/issues/235
-        }
+        verify_batch(&votes)?;
 
         let voting_power = VotingPowerTally {
             total: self.total_power_of(validator_set),
-            tallied: tallied_voting_power,
+            tallied: tallied_power(&votes),
             trust_threshold,
         };
 
@@ -157,6 +141,136 @@ impl VotingPowerCalculator for ProdVotingPowerCalculator {
     }
 }
 
+/// A non-absent vote of a known validator within a commit
+struct CommitVote {
+    signed_vote: SignedVote,
+    validator: validator::Info,
+    is_commit: bool,
+}
+
+/// Collect the non-absent votes of the known validators within the commit of the
+/// given signed header, without verifying their signatures.
+fn commit_votes(
+    signed_header: &SignedHeader,
+    validator_set: &ValidatorSet,
+) -> Result<Vec<CommitVote>, VerificationError> {
+    let signatures = &signed_header.commit.signatures;
+    let encoding = Encoding::from(&signed_header.header.version);
+
+    let mut votes = Vec::with_capacity(signatures.len());
+    let mut seen_validators = HashSet::new();
+
+    for (idx, signature) in signatures.iter().enumerate() {
+        let vote = match non_absent_vote(signature, idx as u64, &signed_header.commit) {
+            Some(vote) => vote,
+            None => continue,
+        };
+
+        // Ensure we only count a validator's power once
+        if !seen_validators.insert(vote.validator_address) {
+            bail!(VerificationError::DuplicateValidator(
+                vote.validator_address
+            ));
+        }
+
+        let validator = match validator_set.validator(vote.validator_address) {
+            Some(validator) => validator,
+            None => continue, // Cannot find matching validator, so we skip the vote
+        };
+
+        votes.push(CommitVote {
+            signed_vote: SignedVote::from_vote(
+                &vote,
+                signed_header.header.chain_id.as_str(),
+                encoding,
+            ),
+            validator,
+            is_commit: signature.is_commit(),
+        });
+    }
+
+    Ok(votes)
+}
+
+/// Tally the power of the validators which voted for the block.
+///
+/// Stray signatures (~votes for nil) are verified, in order to measure
+/// validator availability, but their power is not tallied.
+fn tallied_power(votes: &[CommitVote]) -> u64 {
+    votes
+        .iter()
+        .filter(|vote| vote.is_commit)
+        .map(|vote| vote.validator.power())
+        .sum()
+}
+
+/// Verify the signatures of the given votes, batching the Ed25519 ones.
+fn verify_batch(votes: &[CommitVote]) -> Result<(), VerificationError> {
+    let mut batch = Vec::with_capacity(votes.len());
+    let mut signatures = Vec::with_capacity(votes.len());
+    let mut public_keys = Vec::with_capacity(votes.len());
+
+    for vote in votes {
+        match ed25519_batch_item(vote) {
+            Some((signature, public_key)) => {
+                batch.push(vote);
+                signatures.push(signature);
+                public_keys.push(public_key);
+            }
+            None => verify_individually(vote)?,
+        }
+    }
+
+    if batch.is_empty() {
+        return Ok(());
+    }
+
+    let sign_bytes: Vec<Vec<u8>> = batch
+        .iter()
+        .map(|vote| vote.signed_vote.sign_bytes())
+        .collect();
+    let messages: Vec<&[u8]> = sign_bytes.iter().map(Vec::as_slice).collect();
+
+    if ed25519_dalek::verify_batch(&messages, &signatures, &public_keys).is_ok() {
+        return Ok(());
+    }
+
+    // Fall back to individual checks in order to pinpoint the invalid signature
+    for vote in batch {
+        verify_individually(vote)?;
+    }
+
+    Ok(())
+}
+
+/// Get the Ed25519 signature and public key of the given vote,
+/// if the validator has an Ed25519 key.
+fn ed25519_batch_item(
+    vote: &CommitVote,
+) -> Option<(ed25519_dalek::Signature, ed25519_dalek::PublicKey)> {
+    let public_key = vote.validator.pub_key.ed25519()?;
+    let public_key = ed25519_dalek::PublicKey::from_bytes(public_key.as_bytes()).ok()?;
+    let signature = ed25519_dalek::Signature::try_from(vote.signed_vote.signature()).ok()?;
+
+    Some((signature, public_key))
+}
+
+fn verify_individually(vote: &CommitVote) -> Result<(), VerificationError> {
+    let sign_bytes = vote.signed_vote.sign_bytes();
+    if !vote
+        .validator
+        .verify_signature(&sign_bytes, vote.signed_vote.signature())
+    {
+        bail!(VerificationError::InvalidSignature {
+            signature: vote.signed_vote.signature().to_vec(),
+            validator: vote.validator,
+            sign_bytes,
+        });
+    }
+
+    Ok(())
+}
+
 fn non_absent_vote(commit_sig: &CommitSig, validator_index: u64, commit: &Commit) -> Option<Vote> {
     let (validator_address, timestamp, signature, block_id) = match commit_sig {
         CommitSig::BlockIDFlagAbsent { .. } => return None,
@@ -228,16 +342,19 @@ mod tests {
     fn run_all_tests() {
         for entry in fs::read_dir(TEST_FILES_PATH).unwrap() {
             let entry = entry.unwrap();
-            let tc = read_test_case(entry.path());
             let name = entry.file_name().to_string_lossy().to_string();
-            run_test(tc, name);
+
+            let tc = read_test_case(entry.path());
+            run_test(tc, name.clone(), ProdVotingPowerCalculator::default());
+
+            let tc = read_test_case(entry.path());
+            run_test(tc, name, BatchVotingPowerCalculator::default());
         }
     }
 
-    fn run_test(tc: TestCase, file: String) {
+    fn run_test(tc: TestCase, file: String, calculator: impl VotingPowerCalculator) {
         println!("- Test '{}' in {}", tc.description, file);
 
-        let calculator = ProdVotingPowerCalculator::default();
         let trust_threshold = TrustThreshold::default();
 
         let tally =
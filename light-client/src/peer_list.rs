@@ -1,7 +1,7 @@
 use crate::{
     bail,
     errors::{Error, ErrorKind},
-    types::PeerId,
+    types::{PeerId, PeerStats},
 };
 
 use contracts::{post, pre};
@@ -10,8 +10,14 @@ use std::collections::{BTreeSet, HashMap};
 /// A generic container mapping `PeerId`s to some type `T`,
 /// which keeps track of the primary peer, witnesses, full nodes,
 /// and faulty nodes. Provides lifecycle methods to swap the primary,
-/// mark witnesses as faulty, and maintains an `invariant` for
-/// correctness.
+/// mark witnesses as faulty, add and remove peers, and maintains an
+/// `invariant` for correctness.
+///
+/// Whenever a witness is removed or promoted to primary, a spare full node,
+/// if any, is promoted to witness in its place.
+///
+/// The peer list also keeps statistics about each peer, and the witness with
+/// the best score is elected whenever a new primary is needed.
 #[derive(Debug)]
 pub struct PeerList<T> {
     values: HashMap<PeerId, T>,
@@ -19,6 +25,7 @@ pub struct PeerList<T> {
     witnesses: BTreeSet<PeerId>,
     full_nodes: BTreeSet<PeerId>,
     faulty_nodes: BTreeSet<PeerId>,
+    stats: HashMap<PeerId, PeerStats>,
 }
 
 impl<T> PeerList<T> {
@@ -104,6 +111,95 @@ impl<T> PeerList<T> {
         &self.faulty_nodes
     }
 
+    /// Get the statistics of the given peer.
+    pub fn stats(&self, peer_id: PeerId) -> PeerStats {
+        self.stats.get(&peer_id).copied().unwrap_or_default()
+    }
+
+    /// Get a mutable reference to the statistics of the given peer.
+    pub fn stats_mut(&mut self, peer_id: PeerId) -> &mut PeerStats {
+        self.stats.entry(peer_id).or_default()
+    }
+
+    /// Get the id of the witness with the best score, if any.
+    /// Ties are broken in favor of the lowest peer id.
+    pub fn best_witness(&self) -> Option<PeerId> {
+        self.witnesses
+            .iter()
+            .copied()
+            .fold(None, |best, id| match best {
+                Some(best) if self.stats(best).score() >= self.stats(id).score() => Some(best),
+                _ => Some(id),
+            })
+    }
+
+    /// Swap the primary for the given witness, without marking the primary as faulty.
+    /// The former primary becomes a witness.
+    ///
+    /// ## Precondition
+    /// - The given peer must be in the witness list
+    #[pre(self.witnesses.contains(&witness))]
+    #[post(Self::invariant(&self))]
+    pub fn swap_primary(&mut self, witness: PeerId) {
+        self.witnesses.remove(&witness);
+        self.witnesses.insert(self.primary);
+        self.primary = witness;
+    }
+
+    /// Register the given peer id and value as a witness.
+    /// A peer which was previously marked as faulty can be registered again.
+    ///
+    /// ## Errors
+    /// - If the peer is already registered and not faulty, returns `ErrorKind::PeerAlreadyRegistered`.
+    #[post(ret.is_ok() ==> Self::invariant(&self))]
+    pub fn add_witness(&mut self, peer_id: PeerId, value: T) -> Result<(), Error> {
+        self.register(peer_id, value)?;
+        self.witnesses.insert(peer_id);
+
+        Ok(())
+    }
+
+    /// Register the given peer id and value as a full node.
+    /// A peer which was previously marked as faulty can be registered again.
+    ///
+    /// ## Errors
+    /// - If the peer is already registered and not faulty, returns `ErrorKind::PeerAlreadyRegistered`.
+    #[post(ret.is_ok() ==> Self::invariant(&self))]
+    pub fn add_full_node(&mut self, peer_id: PeerId, value: T) -> Result<(), Error> {
+        self.register(peer_id, value)?;
+        self.full_nodes.insert(peer_id);
+
+        Ok(())
+    }
+
+    /// Remove the given peer from the list, and return its value.
+    ///
+    /// If the peer is the primary, the next available witness is promoted to primary.
+    /// If the peer is a witness, a full node is promoted to witness, if there are any left.
+    ///
+    /// ## Errors
+    /// - If the peer is not registered, returns `ErrorKind::UnknownPeer`.
+    /// - If the peer is the primary and there are no witness left, returns `ErrorKind::NoWitnessLeft`.
+    #[post(ret.is_ok() ==> Self::invariant(&self))]
+    pub fn remove(&mut self, peer_id: PeerId) -> Result<T, Error> {
+        if !self.values.contains_key(&peer_id) {
+            bail!(ErrorKind::UnknownPeer(peer_id));
+        }
+
+        if peer_id == self.primary {
+            self.promote_witness()?;
+        } else if self.witnesses.remove(&peer_id) {
+            self.promote_full_node();
+        } else {
+            self.full_nodes.remove(&peer_id);
+            self.faulty_nodes.remove(&peer_id);
+        }
+
+        self.stats.remove(&peer_id);
+
+        Ok(self.values.remove(&peer_id).unwrap()) // SAFETY: Checked above
+    }
+
     /// Remove the given peer from the list of witnesses,
     /// and mark it as faulty. Get a new witness from
     /// the list of full nodes, if there are any left.
@@ -115,22 +211,13 @@ impl<T> PeerList<T> {
     #[pre(faulty_witness != self.primary && self.witnesses.contains(&faulty_witness))]
     #[post(Self::invariant(&self))]
     pub fn replace_faulty_witness(&mut self, faulty_witness: PeerId) -> Option<PeerId> {
-        let mut result = None;
-
         self.witnesses.remove(&faulty_witness);
-
-        if let Some(new_witness) = self.full_nodes.iter().next().copied() {
-            self.witnesses.insert(new_witness);
-            self.full_nodes.remove(&new_witness);
-            result = Some(new_witness);
-        }
-
         self.faulty_nodes.insert(faulty_witness);
 
-        result
+        self.promote_full_node()
     }
 
-    /// Mark the primary as faulty and swap it for the next available witness, if any.
+    /// Mark the primary as faulty and swap it for the witness with the best score, if any.
     /// Returns the new primary on success.
     ///
     /// ## Errors
@@ -138,15 +225,52 @@ impl<T> PeerList<T> {
     #[post(ret.is_ok() ==> Self::invariant(&self))]
     pub fn replace_faulty_primary(&mut self) -> Result<PeerId, Error> {
         self.faulty_nodes.insert(self.primary);
+        self.promote_witness()
+    }
+
+    /// Register the given peer id and value, removing the peer from the faulty nodes.
+    fn register(&mut self, peer_id: PeerId, value: T) -> Result<(), Error> {
+        if self.values.contains_key(&peer_id) && !self.faulty_nodes.contains(&peer_id) {
+            bail!(ErrorKind::PeerAlreadyRegistered(peer_id));
+        }
+
+        self.faulty_nodes.remove(&peer_id);
+        self.values.insert(peer_id, value);
 
-        if let Some(new_primary) = self.witnesses.iter().next().copied() {
+        Ok(())
+    }
+
+    /// Swap the primary for the witness with the best score, if any, and promote a full node
+    /// to witness in its place. If there are no witnesses but some full nodes are left,
+    /// a full node is promoted to witness first. Returns the new primary on success.
+    ///
+    /// ## Errors
+    /// - If there are no witness left, returns `ErrorKind::NoWitnessLeft`.
+    fn promote_witness(&mut self) -> Result<PeerId, Error> {
+        if self.witnesses.is_empty() {
+            self.promote_full_node();
+        }
+
+        if let Some(new_primary) = self.best_witness() {
             self.primary = new_primary;
             self.witnesses.remove(&new_primary);
+            self.promote_full_node();
             return Ok(new_primary);
         }
 
         bail!(ErrorKind::NoWitnessLeft)
     }
+
+    /// Promote a full node to witness, if there are any left.
+    /// Returns the new witness, if any.
+    fn promote_full_node(&mut self) -> Option<PeerId> {
+        let new_witness = self.full_nodes.iter().next().copied()?;
+
+        self.full_nodes.remove(&new_witness);
+        self.witnesses.insert(new_witness);
+
+        Some(new_witness)
+    }
 }
 
 /// A builder of `PeerList` with a fluent API.
@@ -218,6 +342,7 @@ impl<T> PeerListBuilder<T> {
             witnesses: self.witnesses,
             full_nodes: self.full_nodes,
             faulty_nodes: self.faulty_nodes,
+            stats: HashMap::new(),
         }
     }
 }
@@ -284,13 +409,15 @@ mod tests {
         let new_primary = peer_list.replace_faulty_primary();
         assert_eq!(new_primary.unwrap(), b());
         assert_eq!(peer_list.primary(), &2);
-        assert!(peer_list.witnesses_ids().is_empty());
+        assert_eq!(peer_list.witnesses_ids().to_vec(), vec![c()]);
+        assert!(peer_list.full_nodes_ids().is_empty());
     }
 
     #[test]
     fn replace_faulty_primary_fails_if_no_more_witnesses() {
         let mut peer_list = dummy_peer_list();
         let _ = peer_list.replace_faulty_primary().unwrap();
+        let _ = peer_list.replace_faulty_primary().unwrap();
         let new_primary = peer_list.replace_faulty_primary();
         assert_eq!(
             new_primary.err().map(|e| e.kind().clone()),
@@ -317,4 +444,82 @@ mod tests {
         let _ = peer_list.replace_faulty_witness(d());
         unreachable!();
     }
+
+    #[test]
+    fn add_witness_succeeds() {
+        let mut peer_list = dummy_peer_list();
+        peer_list.add_witness(d(), 4_u32).unwrap();
+        assert_eq!(peer_list.get(&d()), Some(&4));
+        assert!(peer_list.witnesses_ids().contains(&d()));
+    }
+
+    #[test]
+    fn add_witness_fails_if_already_registered() {
+        let mut peer_list = dummy_peer_list();
+        let result = peer_list.add_witness(c(), 4_u32);
+        assert_eq!(
+            result.err().map(|e| e.kind().clone()),
+            Some(ErrorKind::PeerAlreadyRegistered(c()))
+        );
+    }
+
+    #[test]
+    fn add_witness_succeeds_if_faulty() {
+        let mut peer_list = dummy_peer_list();
+        let _ = peer_list.replace_faulty_witness(b());
+        peer_list.add_witness(b(), 4_u32).unwrap();
+        assert!(peer_list.faulty_nodes_ids().is_empty());
+        assert_eq!(peer_list.witnesses_ids().to_vec(), vec![b(), c()]);
+    }
+
+    #[test]
+    fn remove_witness_promotes_full_node() {
+        let mut peer_list = dummy_peer_list();
+        assert_eq!(peer_list.remove(b()).unwrap(), 2);
+        assert_eq!(peer_list.witnesses_ids().to_vec(), vec![c()]);
+        assert!(peer_list.full_nodes_ids().is_empty());
+        assert!(peer_list.faulty_nodes_ids().is_empty());
+    }
+
+    #[test]
+    fn remove_primary_promotes_witness() {
+        let mut peer_list = dummy_peer_list();
+        assert_eq!(peer_list.remove(a()).unwrap(), 1);
+        assert_eq!(peer_list.primary_id(), b());
+        assert_eq!(peer_list.witnesses_ids().to_vec(), vec![c()]);
+        assert!(peer_list.faulty_nodes_ids().is_empty());
+    }
+
+    #[test]
+    fn remove_fails_if_unknown_peer() {
+        let mut peer_list = dummy_peer_list();
+        let result = peer_list.remove(d());
+        assert_eq!(
+            result.err().map(|e| e.kind().clone()),
+            Some(ErrorKind::UnknownPeer(d()))
+        );
+    }
+
+    #[test]
+    fn replace_faulty_primary_elects_best_witness() {
+        let mut peer_list = dummy_peer_list();
+        peer_list.add_witness(d(), 4_u32).unwrap();
+        peer_list.stats_mut(b()).record_timeout();
+        peer_list
+            .stats_mut(d())
+            .record_success(std::time::Duration::from_millis(100));
+
+        assert_eq!(peer_list.best_witness(), Some(d()));
+        assert_eq!(peer_list.replace_faulty_primary().unwrap(), d());
+        assert_eq!(peer_list.witnesses_ids().to_vec(), vec![b(), c()]);
+    }
+
+    #[test]
+    fn swap_primary_succeeds() {
+        let mut peer_list = dummy_peer_list();
+        peer_list.swap_primary(b());
+        assert_eq!(peer_list.primary_id(), b());
+        assert_eq!(peer_list.witnesses_ids().to_vec(), vec![a()]);
+        assert!(peer_list.faulty_nodes_ids().is_empty());
+    }
 }
@@ -4,11 +4,12 @@ use crate::{
     ensure,
     light_client::Options,
     operations::{CommitValidator, Hasher, VotingPowerCalculator},
-    types::{Header, Height, LightBlock, SignedHeader, Time, TrustThreshold, ValidatorSet},
+    types::{Hash, Header, Height, LightBlock, SignedHeader, Time, TrustThreshold, ValidatorSet},
 };
 
 use errors::VerificationError;
 use std::time::Duration;
+use tendermint::{chain, encoding::Encoding, Genesis};
 
 pub mod errors;
 
@@ -25,12 +26,31 @@ impl VerificationPredicates for ProdPredicates {}
 /// This enables test implementations to only override a single method rather than
 /// have to re-define every predicate.
 pub trait VerificationPredicates: Send {
+    fn is_matching_chain_id(
+        &self,
+        header: &Header,
+        chain_id: &chain::Id,
+    ) -> Result<(), VerificationError> {
+        ensure!(
+            header.chain_id == *chain_id,
+            VerificationError::ChainIdMismatch {
+                got: header.chain_id,
+                expected: *chain_id,
+            }
+        );
+
+        Ok(())
+    }
+
     fn validator_sets_match(
         &self,
         light_block: &LightBlock,
         hasher: &dyn Hasher,
     ) -> Result<(), VerificationError> {
-        let validators_hash = hasher.hash_validator_set(&light_block.validators);
+        let validators_hash = hasher.hash_validator_set(
+            &light_block.validators,
+            Encoding::from(&light_block.signed_header.header.version),
+        );
 
         ensure!(
             light_block.signed_header.header.validators_hash == validators_hash,
@@ -48,7 +68,10 @@ pub trait VerificationPredicates: Send {
         light_block: &LightBlock,
         hasher: &dyn Hasher,
     ) -> Result<(), VerificationError> {
-        let next_validators_hash = hasher.hash_validator_set(&light_block.next_validators);
+        let next_validators_hash = hasher.hash_validator_set(
+            &light_block.next_validators,
+            Encoding::from(&light_block.signed_header.header.version),
+        );
 
         ensure!(
             light_block.signed_header.header.next_validators_hash == next_validators_hash,
@@ -194,6 +217,7 @@ pub trait VerificationPredicates: Send {
 /// Validate the given light block.
 ///
 /// - Ensure the latest trusted header hasn't expired
+/// - Ensure the untrusted header belongs to the expected chain
 /// - Ensure the header validator hashes match the given validators
 /// - Ensure the header next validator hashes match the given next validators
 /// - Additional implementation specific validation via `commit_validator`
@@ -221,6 +245,9 @@ pub fn verify(
         now,
     )?;
 
+    // Ensure the untrusted header belongs to the expected chain
+    vp.is_matching_chain_id(&untrusted.signed_header.header, &options.chain_id)?;
+
     // Ensure the header validator hashes match the given validators
     vp.validator_sets_match(&untrusted, &*hasher)?;
 
@@ -276,3 +303,121 @@ pub fn verify(
 
     Ok(())
 }
+
+/// Validate the block at height 1 against the given genesis document.
+///
+/// - Ensure the header chain id matches the genesis chain id
+/// - Ensure the header validators hash matches the genesis validators
+/// - Ensure the header validator hashes match the given validators
+/// - Ensure the header matches the commit
+/// - Additional implementation specific validation via `commit_validator`
+pub fn validate_genesis<AppState>(
+    vp: &dyn VerificationPredicates,
+    commit_validator: &dyn CommitValidator,
+    hasher: &dyn Hasher,
+    light_block: &LightBlock,
+    genesis: &Genesis<AppState>,
+) -> Result<(), VerificationError> {
+    let header = &light_block.signed_header.header;
+
+    // Ensure the header chain id matches the genesis chain id
+    vp.is_matching_chain_id(header, &genesis.chain_id)?;
+
+    // Ensure the header validators hash matches the genesis validators
+    let genesis_validators_hash = hasher.hash_validator_set(
+        &ValidatorSet::new(genesis.validators.clone()),
+        Encoding::from(&header.version),
+    );
+
+    ensure!(
+        header.validators_hash == genesis_validators_hash,
+        VerificationError::InvalidValidatorSet {
+            header_validators_hash: header.validators_hash,
+            validators_hash: genesis_validators_hash,
+        }
+    );
+
+    // Ensure the header validator hashes match the given validators
+    vp.validator_sets_match(&light_block, &*hasher)?;
+
+    // Ensure the header matches the commit
+    vp.header_matches_commit(&light_block.signed_header, hasher)?;
+
+    // Additional implementation specific validation
+    vp.valid_commit(
+        &light_block.signed_header,
+        &light_block.validators,
+        commit_validator,
+    )?;
+
+    Ok(())
+}
+
+/// Validate a subjectively trusted light block.
+///
+/// - Ensure the header hash matches the trusted hash
+/// - Ensure the header belongs to the expected chain
+/// - Ensure the header is within the trusting period
+/// - Ensure the header validator hashes match the given validators
+/// - Ensure the header next validator hashes match the given next validators
+/// - Ensure the header matches the commit
+/// - Additional implementation specific validation via `commit_validator`
+/// - Ensure more than 2/3 of the validators correctly committed the block
+#[allow(clippy::too_many_arguments)]
+pub fn validate_trusted_block(
+    vp: &dyn VerificationPredicates,
+    voting_power_calculator: &dyn VotingPowerCalculator,
+    commit_validator: &dyn CommitValidator,
+    hasher: &dyn Hasher,
+    light_block: &LightBlock,
+    trusted_hash: Hash,
+    options: &Options,
+    now: Time,
+) -> Result<(), VerificationError> {
+    // Ensure the header hash matches the trusted hash
+    let header_hash = hasher.hash_header(&light_block.signed_header.header);
+
+    ensure!(
+        header_hash == trusted_hash,
+        VerificationError::InvalidHeaderHash {
+            header_hash,
+            trusted_hash,
+        }
+    );
+
+    // Ensure the header belongs to the expected chain
+    vp.is_matching_chain_id(&light_block.signed_header.header, &options.chain_id)?;
+
+    // Ensure the header is within the trusting period
+    vp.is_within_trust_period(
+        &light_block.signed_header.header,
+        options.trusting_period,
+        options.clock_drift,
+        now,
+    )?;
+
+    // Ensure the header validator hashes match the given validators
+    vp.validator_sets_match(&light_block, &*hasher)?;
+
+    // Ensure the header next validator hashes match the given next validators
+    vp.next_validators_match(&light_block, &*hasher)?;
+
+    // Ensure the header matches the commit
+    vp.header_matches_commit(&light_block.signed_header, hasher)?;
+
+    // Additional implementation specific validation
+    vp.valid_commit(
+        &light_block.signed_header,
+        &light_block.validators,
+        commit_validator,
+    )?;
+
+    // Verify that more than 2/3 of the validators correctly committed the block.
+    vp.has_sufficient_signers_overlap(
+        &light_block.signed_header,
+        &light_block.validators,
+        voting_power_calculator,
+    )?;
+
+    Ok(())
+}
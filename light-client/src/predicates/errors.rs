@@ -1,5 +1,6 @@
 use anomaly::{BoxError, Context};
 use serde::{Deserialize, Serialize};
+use tendermint::chain;
 use thiserror::Error;
 
 use crate::errors::ErrorExt;
@@ -68,6 +69,15 @@ pub enum VerificationError {
 
     #[error("not withing trust period: at={at} now={now}")]
     NotWithinTrustPeriod { at: Time, now: Time },
+
+    #[error("invalid header hash: header_hash={header_hash} trusted_hash={trusted_hash}")]
+    InvalidHeaderHash {
+        header_hash: Hash,
+        trusted_hash: Hash,
+    },
+
+    #[error("chain id mismatch: got={got} expected={expected}")]
+    ChainIdMismatch { got: chain::Id, expected: chain::Id },
 }
 
 impl VerificationError {
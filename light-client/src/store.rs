@@ -1,11 +1,12 @@
-//! Interface and implementations of the light block store.
+//! Interface and implementations of the light block, evidence and fault stores.
 //!
 //! See the `memory` and `sled` modules for:
 //! - a transient, in-memory implementation for testing purposes
 //! - a persistent, on-disk, sled-backed implementation for production
 
+use crate::evidence::EvidenceReport;
 use crate::std_ext;
-use crate::types::{Height, LightBlock, Status};
+use crate::types::{FaultRecord, Height, LightBlock, PeerId, Status};
 
 pub mod memory;
 pub mod sled;
@@ -71,3 +72,44 @@ pub trait LightStore: std::fmt::Debug + Send {
             .or_else(|| self.get(height, Status::Verified))
     }
 }
+
+/// Store for the evidence reported by the supervisor.
+///
+/// The evidence store records each piece of evidence along with the peers it has
+/// been acknowledged by and the peers it is yet to be reported to, so that reporting
+/// can be resumed after a restart.
+pub trait EvidenceStore: std::fmt::Debug + Send {
+    /// Insert the given report in the store.
+    /// Overrides any other report for the same evidence.
+    fn insert(&mut self, report: &EvidenceReport);
+
+    /// Get all the reports in the store.
+    fn all(&self) -> Vec<EvidenceReport>;
+
+    /// Get the reports of the evidence which is yet to be reported to some peers.
+    fn pending(&self) -> Vec<EvidenceReport> {
+        self.all()
+            .into_iter()
+            .filter(|report| !report.is_complete())
+            .collect()
+    }
+}
+
+/// Store for the records of the peers deemed faulty by the supervisor.
+///
+/// The fault store keeps track of forks and faulty peers, so that faulty peers
+/// are not consulted again after a restart.
+pub trait FaultStore: std::fmt::Debug + Send {
+    /// Insert the given record in the store.
+    /// Overrides any other record for the same peer.
+    fn insert(&mut self, record: &FaultRecord);
+
+    /// Get the record for the given peer, if any.
+    fn get(&self, peer: PeerId) -> Option<FaultRecord>;
+
+    /// Remove the record for the given peer, and return it, if any.
+    fn remove(&mut self, peer: PeerId) -> Option<FaultRecord>;
+
+    /// Get all the records in the store.
+    fn all(&self) -> Vec<FaultRecord>;
+}
@@ -1,10 +1,11 @@
 use crate::{
-    store::{LightStore, Status},
-    types::{Height, LightBlock},
+    evidence::EvidenceReport,
+    store::{EvidenceStore, FaultStore, LightStore, Status},
+    types::{FaultRecord, Height, LightBlock, PeerId},
 };
 
 use std::collections::btree_map::Entry::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Internal entry for the memory store
 #[derive(Clone, Debug, PartialEq)]
@@ -81,3 +82,66 @@ impl LightStore for MemoryStore {
         Box::new(light_blocks.into_iter())
     }
 }
+
+/// Transient in-memory evidence store.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryEvidenceStore {
+    reports: Vec<EvidenceReport>,
+}
+
+impl MemoryEvidenceStore {
+    pub fn new() -> Self {
+        Self {
+            reports: Vec::new(),
+        }
+    }
+}
+
+impl EvidenceStore for MemoryEvidenceStore {
+    fn insert(&mut self, report: &EvidenceReport) {
+        match self
+            .reports
+            .iter_mut()
+            .find(|r| r.evidence == report.evidence)
+        {
+            Some(existing) => *existing = report.clone(),
+            None => self.reports.push(report.clone()),
+        }
+    }
+
+    fn all(&self) -> Vec<EvidenceReport> {
+        self.reports.clone()
+    }
+}
+
+/// Transient in-memory fault store.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFaultStore {
+    records: HashMap<PeerId, FaultRecord>,
+}
+
+impl MemoryFaultStore {
+    pub fn new() -> Self {
+        Self {
+            records: HashMap::new(),
+        }
+    }
+}
+
+impl FaultStore for MemoryFaultStore {
+    fn insert(&mut self, record: &FaultRecord) {
+        self.records.insert(record.peer, record.clone());
+    }
+
+    fn get(&self, peer: PeerId) -> Option<FaultRecord> {
+        self.records.get(&peer).cloned()
+    }
+
+    fn remove(&mut self, peer: PeerId) -> Option<FaultRecord> {
+        self.records.remove(&peer)
+    }
+
+    fn all(&self) -> Vec<FaultRecord> {
+        self.records.values().cloned().collect()
+    }
+}
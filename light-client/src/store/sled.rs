@@ -1,17 +1,20 @@
 pub mod utils;
 
 use crate::{
+    evidence::{Evidence, EvidenceReport},
     store::sled::utils::*,
-    types::{Height, LightBlock},
+    types::{FaultRecord, Height, LightBlock, PeerId},
 };
 
-use super::{LightStore, Status};
+use super::{EvidenceStore, FaultStore, LightStore, Status};
 use ::sled::Db as SledDb;
 
 const UNVERIFIED_PREFIX: &str = "light_store/unverified";
 const VERIFIED_PREFIX: &str = "light_store/verified";
 const TRUSTED_PREFIX: &str = "light_store/trusted";
 const FAILED_PREFIX: &str = "light_store/failed";
+const EVIDENCE_PREFIX: &str = "evidence_store/reports";
+const FAULT_PREFIX: &str = "fault_store/records";
 
 /// Persistent store backed by an on-disk `sled` database.
 #[derive(Debug, Clone)]
@@ -81,3 +84,67 @@ impl LightStore for SledStore {
         Box::new(self.db(status).iter(&self.db))
     }
 }
+
+/// Persistent evidence store backed by an on-disk `sled` database.
+#[derive(Debug, Clone)]
+pub struct SledEvidenceStore {
+    db: SledDb,
+    reports_db: KeyValueDb<Evidence, EvidenceReport>,
+}
+
+impl SledEvidenceStore {
+    pub fn new(db: SledDb) -> Self {
+        Self {
+            db,
+            reports_db: KeyValueDb::new(EVIDENCE_PREFIX),
+        }
+    }
+}
+
+impl EvidenceStore for SledEvidenceStore {
+    fn insert(&mut self, report: &EvidenceReport) {
+        self.reports_db
+            .insert(&self.db, &report.evidence, report)
+            .ok();
+    }
+
+    fn all(&self) -> Vec<EvidenceReport> {
+        self.reports_db.iter(&self.db).collect()
+    }
+}
+
+/// Persistent fault store backed by an on-disk `sled` database.
+#[derive(Debug, Clone)]
+pub struct SledFaultStore {
+    db: SledDb,
+    records_db: KeyValueDb<PeerId, FaultRecord>,
+}
+
+impl SledFaultStore {
+    pub fn new(db: SledDb) -> Self {
+        Self {
+            db,
+            records_db: KeyValueDb::new(FAULT_PREFIX),
+        }
+    }
+}
+
+impl FaultStore for SledFaultStore {
+    fn insert(&mut self, record: &FaultRecord) {
+        self.records_db.insert(&self.db, &record.peer, record).ok();
+    }
+
+    fn get(&self, peer: PeerId) -> Option<FaultRecord> {
+        self.records_db.get(&self.db, &peer).ok().flatten()
+    }
+
+    fn remove(&mut self, peer: PeerId) -> Option<FaultRecord> {
+        let record = self.get(peer)?;
+        self.records_db.remove(&self.db, &peer).ok()?;
+        Some(record)
+    }
+
+    fn all(&self) -> Vec<FaultRecord> {
+        self.records_db.iter(&self.db).collect()
+    }
+}
@@ -117,8 +117,12 @@ where
         Ok(())
     }
 
+    /// Iterate over the values stored at this view's prefix.
+    ///
+    /// Only the keys starting with the prefix are scanned, so that several views
+    /// can share the same database.
     pub fn iter(&self, db: &sled::Db) -> impl DoubleEndedIterator<Item = V> {
-        db.iter()
+        db.scan_prefix(&self.prefix)
             .flatten()
             .map(|(_, v)| serde_cbor::from_slice(&v))
             .flatten()
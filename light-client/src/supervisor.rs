@@ -1,17 +1,25 @@
 //! Supervisor and Handle implementation.
 
 use crossbeam_channel as channel;
+use std::collections::VecDeque;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Instant;
 
-use tendermint::evidence::{ConflictingHeadersEvidence, Evidence};
+use tendermint::{encoding::Encoding, net, Genesis};
 
 use crate::bail;
-use crate::errors::{Error, ErrorKind};
-use crate::evidence::EvidenceReporter;
+use crate::errors::{Error, ErrorExt, ErrorKind};
+use crate::evidence::{self, Evidence, EvidenceReport, EvidenceReporter};
 use crate::fork_detector::{Fork, ForkDetection, ForkDetector};
 use crate::light_client::LightClient;
+use crate::operations::{ProdCommitValidator, ProdHasher, ProdVotingPowerCalculator};
 use crate::peer_list::PeerList;
+use crate::predicates::{self, errors::VerificationError, ProdPredicates};
 use crate::state::State;
-use crate::types::{Height, LatestStatus, LightBlock, PeerId, Status};
+use crate::store::{EvidenceStore, FaultStore};
+use crate::types::{
+    FaultReason, FaultRecord, Hash, Height, LatestStatus, LightBlock, PeerId, Status, Time,
+};
 use tendermint::lite::{Header, ValidatorSet};
 
 pub trait Handle {
@@ -34,6 +42,31 @@ pub trait Handle {
         todo!()
     }
 
+    /// Get the reports of the evidence submitted by the [`Supervisor`], along with
+    /// the peers which acknowledged them.
+    fn evidence_reports(&self) -> Result<Vec<EvidenceReport>, Error>;
+
+    /// Add the given instance as a witness, along with the network address of its peer.
+    /// Any fault record of the peer is cleared.
+    fn add_witness(&self, instance: Instance, address: net::Address) -> Result<(), Error>;
+
+    /// Add the given instance as a full node, along with the network address of its peer.
+    /// Full nodes are promoted to witnesses as witnesses are removed or deemed faulty.
+    /// Any fault record of the peer is cleared.
+    fn add_full_node(&self, instance: Instance, address: net::Address) -> Result<(), Error>;
+
+    /// Remove the given peer. If the peer is the primary, a witness is promoted to primary.
+    fn remove_peer(&self, peer_id: PeerId) -> Result<(), Error>;
+
+    /// Get the records of the forks and faulty peers detected by the [`Supervisor`].
+    fn faulty_peers(&self) -> Result<Vec<FaultRecord>, Error>;
+
+    /// Clear the record of the given peer, eg. once the operator has dealt with a fork.
+    /// Verification resumes once no fork is on record anymore.
+    ///
+    /// Returns the cleared record, if any, so that it can be kept for auditing.
+    fn clear_fault(&self, peer_id: PeerId) -> Result<Option<FaultRecord>, Error>;
+
     /// Terminate the underlying [`Supervisor`].
     fn terminate(&self) -> Result<(), Error> {
         todo!()
@@ -42,6 +75,9 @@ pub trait Handle {
 
 /// Input events sent by the [`Handle`]s to the [`Supervisor`]. They carry a [`Callback`] which is
 /// used to communicate back the responses of the requests.
+///
+/// Read-only queries are not sent to the supervisor, but served by the handles
+/// from the supervisor's [`Snapshot`].
 #[derive(Debug)]
 enum HandleInput {
     /// Terminate the supervisor process
@@ -50,10 +86,68 @@ enum HandleInput {
     VerifyToHighest(channel::Sender<Result<LightBlock, Error>>),
     /// Verify to the given height, call the provided callback with result
     VerifyToTarget(Height, channel::Sender<Result<LightBlock, Error>>),
-    /// Get the latest trusted block.
-    LatestTrusted(channel::Sender<Option<LightBlock>>),
-    /// Get the current status of the LightClient
-    GetStatus(channel::Sender<LatestStatus>),
+    /// Add a witness, call the provided callback with the result
+    AddWitness(
+        Box<Instance>,
+        net::Address,
+        channel::Sender<Result<(), Error>>,
+    ),
+    /// Add a full node, call the provided callback with the result
+    AddFullNode(
+        Box<Instance>,
+        net::Address,
+        channel::Sender<Result<(), Error>>,
+    ),
+    /// Remove a peer, call the provided callback with the result
+    RemovePeer(PeerId, channel::Sender<Result<(), Error>>),
+    /// Clear the fault record of a peer, call the provided callback with the result
+    ClearFault(PeerId, channel::Sender<Result<Option<FaultRecord>, Error>>),
+}
+
+impl HandleInput {
+    /// Whether this event is a request to verify to the given target,
+    /// where `None` stands for the highest block.
+    fn is_verification_to(&self, target: Option<Height>) -> bool {
+        match (self, target) {
+            (HandleInput::VerifyToHighest(_), None) => true,
+            (HandleInput::VerifyToTarget(height, _), Some(target)) => *height == target,
+            _ => false,
+        }
+    }
+
+    /// Get the callback of a verification request.
+    fn into_verification_sender(self) -> Option<channel::Sender<Result<LightBlock, Error>>> {
+        match self {
+            HandleInput::VerifyToHighest(sender) => Some(sender),
+            HandleInput::VerifyToTarget(_, sender) => Some(sender),
+            _ => None,
+        }
+    }
+}
+
+/// A snapshot of the state of the [`Supervisor`], shared with its handles in order
+/// to serve read-only queries while a verification is in progress.
+#[derive(Clone, Debug)]
+struct Snapshot {
+    /// The latest block trusted by the primary
+    latest_trusted: Option<LightBlock>,
+    /// The latest status of the supervisor
+    latest_status: LatestStatus,
+    /// The reports of the submitted evidence
+    evidence_reports: Vec<EvidenceReport>,
+    /// The records of the forks and faulty peers
+    faulty_peers: Vec<FaultRecord>,
+}
+
+impl Default for Snapshot {
+    fn default() -> Self {
+        Self {
+            latest_trusted: None,
+            latest_status: LatestStatus::new(None, None, None, vec![]),
+            evidence_reports: vec![],
+            faulty_peers: vec![],
+        }
+    }
 }
 
 /// A light client `Instance` packages a `LightClient` together with its `State`.
@@ -81,6 +175,75 @@ impl Instance {
     pub fn trust_block(&mut self, lb: &LightBlock) {
         self.state.light_store.update(lb, Status::Trusted);
     }
+
+    /// Initialize the trusted state of this instance from a genesis document.
+    ///
+    /// Fetches the block at height 1 from the instance's peer, and checks that its
+    /// chain id and validator set match the ones of the given genesis document,
+    /// before storing it as trusted.
+    pub fn initialize_from_genesis<AppState>(
+        &mut self,
+        genesis: &Genesis<AppState>,
+    ) -> Result<LightBlock, Error> {
+        self.initialize_with(1, |light_block| {
+            predicates::validate_genesis(
+                &ProdPredicates,
+                &ProdCommitValidator,
+                &ProdHasher,
+                light_block,
+                genesis,
+            )
+        })
+    }
+
+    /// Initialize the trusted state of this instance from subjectively trusted
+    /// height and header hash.
+    ///
+    /// Fetches the block at the given height from the instance's peer, and checks that
+    /// its header hash matches the given one, that it is within the trusting period,
+    /// and that it is valid, before storing it as trusted.
+    pub fn initialize_from_trust_options(
+        &mut self,
+        height: Height,
+        header_hash: Hash,
+    ) -> Result<LightBlock, Error> {
+        let options = self.light_client.options;
+        let now = self.light_client.now();
+
+        self.initialize_with(height, |light_block| {
+            predicates::validate_trusted_block(
+                &ProdPredicates,
+                &ProdVotingPowerCalculator,
+                &ProdCommitValidator,
+                &ProdHasher,
+                light_block,
+                header_hash,
+                &options,
+                now,
+            )
+        })
+    }
+
+    /// Fetch the block at the given height and store it as trusted if it passes
+    /// the given validation, or as failed otherwise.
+    fn initialize_with(
+        &mut self,
+        height: Height,
+        validate: impl FnOnce(&LightBlock) -> Result<(), VerificationError>,
+    ) -> Result<LightBlock, Error> {
+        let (light_block, _) = self
+            .light_client
+            .get_or_fetch_block(height, &mut self.state)?;
+
+        if let Err(e) = validate(&light_block) {
+            self.state.light_store.update(&light_block, Status::Failed);
+            bail!(ErrorKind::InvalidLightBlock(e));
+        }
+
+        self.trust_block(&light_block);
+
+        Ok(light_block)
+    }
 }
 
 /// The supervisor manages multiple light client instances, of which one
@@ -92,8 +255,29 @@ impl Instance {
 /// is promoted to primary. If a witness is deemed faulty, then the witness is
 /// removed.
 ///
+/// The supervisor keeps statistics about the response time and reliability of each
+/// peer. If the primary times out, or if a witness scores significantly better than
+/// the primary, the best scoring witness is promoted to primary, and the former primary
+/// becomes a witness without being deemed faulty. After a timeout, verification is
+/// retried with the new primary. Witnesses which time out are kept, and only skipped
+/// for the current verification.
+///
+/// Witnesses and full nodes can be added or removed at runtime via a `Handle`.
+/// Whenever a witness is removed or deemed faulty, a full node is promoted to
+/// witness in its place, if there are any left.
+///
+/// Forks and peers which provided invalid data are recorded in a [`FaultStore`].
+/// Transient failures, such as I/O errors, only count against the statistics of
+/// the peer. Peers recorded as faulty are removed again when the supervisor is
+/// restarted, and verification is refused as long as a fork is on record.
+/// Records can be cleared by the operator via a `Handle`.
+///
 /// The supervisor is intended to be ran in its own thread, and queried
-/// via a `Handle`.
+/// via a `Handle`. Read-only queries, such as the latest trusted block or the
+/// latest status, are served by the handles from a snapshot of the supervisor's
+/// state, and thus do not wait for an ongoing verification to complete. Requests
+/// to verify to the same target which are received while a verification is in
+/// progress are coalesced, and share the outcome of a single verification.
 ///
 /// ## Example
 ///
@@ -126,12 +310,28 @@ pub struct Supervisor {
     fork_detector: Box<dyn ForkDetector>,
     /// Reporter of fork evidence
     evidence_reporter: Box<dyn EvidenceReporter>,
+    /// Store for the evidence to report, and the peers which acknowledged it
+    evidence_store: Box<dyn EvidenceStore>,
+    /// Store for the records of forks and faulty peers
+    fault_store: Box<dyn FaultStore>,
     /// Channel through which to reply to `Handle`s
     sender: channel::Sender<HandleInput>,
     /// Channel through which to receive events from the `Handle`s
     receiver: channel::Receiver<HandleInput>,
+    /// Events received from the `Handle`s which are yet to be processed
+    pending: VecDeque<HandleInput>,
+    /// Snapshot of the supervisor's state, shared with the `Handle`s
+    snapshot: Arc<RwLock<Snapshot>>,
 }
 
+/// Minimum number of requests served by both the primary and a witness
+/// before their scores are compared to rotate the primary.
+const MIN_SAMPLES_FOR_ROTATION: u64 = 10;
+
+/// Factor by which the score of a witness must exceed the score of the primary
+/// for the witness to be promoted to primary.
+const ROTATION_SCORE_FACTOR: f64 = 1.5;
+
 impl std::fmt::Debug for Supervisor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Supervisor")
@@ -145,25 +345,194 @@ static_assertions::assert_impl_all!(Supervisor: Send);
 
 impl Supervisor {
     /// Constructs a new supevisor from the given list of peers and fork detector instance.
+    ///
+    /// Evidence recorded in the given evidence store which has not been acknowledged
+    /// by all peers yet is reported again when the supervisor starts running.
+    /// Peers recorded as faulty in the given fault store are removed from the peer list.
     pub fn new(
         peers: PeerList<Instance>,
         fork_detector: impl ForkDetector + 'static,
         evidence_reporter: impl EvidenceReporter + 'static,
+        evidence_store: impl EvidenceStore + 'static,
+        fault_store: impl FaultStore + 'static,
     ) -> Self {
         let (sender, receiver) = channel::unbounded::<HandleInput>();
 
-        Self {
+        let mut supervisor = Self {
             peers,
             sender,
             receiver,
             fork_detector: Box::new(fork_detector),
             evidence_reporter: Box::new(evidence_reporter),
+            evidence_store: Box::new(evidence_store),
+            fault_store: Box::new(fault_store),
+            pending: VecDeque::new(),
+            snapshot: Arc::new(RwLock::new(Snapshot::default())),
+        };
+
+        supervisor.restore_faulty_peers();
+        supervisor.update_snapshot();
+        supervisor
+    }
+
+    /// Remove from the peer list the peers recorded as faulty in the fault store.
+    ///
+    /// Forked peers are kept, as a fork requires the intervention of the operator,
+    /// and verification is refused until then. Records which do not prove that
+    /// the peer misbehaved are ignored.
+    fn restore_faulty_peers(&mut self) {
+        for record in self.fault_store.all() {
+            if record.reason == FaultReason::Forked || !record.reason.proves_misbehaviour() {
+                continue;
+            }
+
+            if self.peers.witnesses_ids().contains(&record.peer) {
+                self.peers.replace_faulty_witness(record.peer);
+            } else if self.peers.primary_id() == record.peer
+                && !self.peers.witnesses_ids().is_empty()
+            {
+                // Cannot fail as there is at least one witness left
+                self.peers.replace_faulty_primary().ok();
+            }
+        }
+    }
+
+    /// Get the peers which have been recorded as forked.
+    fn forked_peers(&self) -> Vec<PeerId> {
+        self.fault_store
+            .all()
+            .into_iter()
+            .filter(|record| record.reason == FaultReason::Forked)
+            .map(|record| record.peer)
+            .collect()
+    }
+
+    /// Add the given instance as a witness or as a full node.
+    ///
+    /// If the instance has no trusted state yet, it is initialized with
+    /// the latest block trusted by the primary. As the peer is explicitly
+    /// added again by the operator, its fault record, if any, is cleared.
+    fn add_peer(
+        &mut self,
+        mut instance: Instance,
+        address: net::Address,
+        as_witness: bool,
+    ) -> Result<(), Error> {
+        let peer_id = instance.light_client.peer;
+
+        if instance.latest_trusted().is_none() {
+            if let Some(trusted) = self.latest_trusted() {
+                instance.trust_block(&trusted);
+            }
+        }
+
+        if as_witness {
+            self.peers.add_witness(peer_id, instance)?;
+        } else {
+            self.peers.add_full_node(peer_id, instance)?;
+        }
+
+        self.evidence_reporter.add_peer(peer_id, address);
+        self.clear_fault(peer_id);
+
+        Ok(())
+    }
+
+    /// Remove the given peer.
+    fn remove_peer(&mut self, peer_id: PeerId) -> Result<(), Error> {
+        self.peers.remove(peer_id)?;
+        Ok(())
+    }
+
+    /// Clear the fault record of the given peer, and return it, if any.
+    fn clear_fault(&mut self, peer_id: PeerId) -> Option<FaultRecord> {
+        self.fault_store.remove(peer_id)
+    }
+
+    /// Record the given peer as faulty for the given reason.
+    ///
+    /// Only faults which prove that the peer misbehaved are recorded.
+    fn record_fault(
+        &mut self,
+        peer: PeerId,
+        reason: FaultReason,
+        height: Option<Height>,
+        light_blocks: Vec<LightBlock>,
+    ) {
+        if !reason.proves_misbehaviour() {
+            return;
         }
+
+        let record = FaultRecord {
+            peer,
+            reason,
+            height,
+            light_blocks,
+            time: self.now(),
+        };
+
+        self.fault_store.insert(&record);
+    }
+
+    /// The current time, according to the primary's clock.
+    fn now(&self) -> Time {
+        self.peers.primary().light_client.now()
     }
 
     /// Create a new handle to this supervisor.
     pub fn handle(&mut self) -> impl Handle {
-        SupervisorHandle::new(self.sender.clone())
+        SupervisorHandle::new(self.sender.clone(), self.snapshot.clone())
+    }
+
+    /// Update the snapshot of the supervisor's state shared with the handles.
+    fn update_snapshot(&mut self) {
+        let snapshot = Snapshot {
+            latest_trusted: self.latest_trusted(),
+            latest_status: self.latest_status(),
+            evidence_reports: self.evidence_store.all(),
+            faulty_peers: self.fault_store.all(),
+        };
+
+        *self
+            .snapshot
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = snapshot;
+    }
+
+    /// Verify to the given target, where `None` stands for the highest block, and reply
+    /// with the outcome to the given callback as well as to the requests for the same
+    /// target received in the meantime.
+    fn process_verification(
+        &mut self,
+        target: Option<Height>,
+        sender: channel::Sender<Result<LightBlock, Error>>,
+    ) {
+        let outcome = self.verify(target);
+        self.update_snapshot();
+
+        // Coalesce the requests for the same target received during the verification
+        self.pending.extend(self.receiver.try_iter());
+        let (same_target, others): (VecDeque<_>, VecDeque<_>) = self
+            .pending
+            .drain(..)
+            .partition(|event| event.is_verification_to(target));
+        self.pending = others;
+
+        let coalesced = same_target
+            .into_iter()
+            .filter_map(HandleInput::into_verification_sender);
+
+        // Callers which have gone away in the meantime are ignored
+        for coalesced in coalesced {
+            let outcome = match &outcome {
+                Ok(light_block) => Ok(light_block.clone()),
+                Err(e) => Err(share_error(e)),
+            };
+
+            coalesced.send(outcome).ok();
+        }
+
+        sender.send(outcome).ok();
     }
 
     fn latest_trusted(&self) -> Option<LightBlock> {
@@ -186,7 +555,11 @@ impl Supervisor {
             Some(trusted) => LatestStatus::new(
                 Some(trusted.signed_header.header.height()),
                 Some(trusted.signed_header.header.hash()),
-                Some(trusted.next_validators.hash()),
+                Some(
+                    trusted
+                        .next_validators
+                        .hash_with_encoding(Encoding::from(&trusted.signed_header.header.version)),
+                ),
                 connected_nodes,
             ),
             // only return connected nodes to see what is going on:
@@ -201,35 +574,80 @@ impl Supervisor {
 
     /// Verify either to the latest block (if `height == None`) or to a given block (if `height == Some(height)`).
     fn verify(&mut self, height: Option<Height>) -> Result<LightBlock, Error> {
+        let failovers = self.peers.witnesses_ids().len();
+        self.verify_with_failovers(height, failovers)
+    }
+
+    /// Verify to the given target, failing over to the best scoring witness at most
+    /// `failovers` times if the primary times out or cannot be reached.
+    fn verify_with_failovers(
+        &mut self,
+        height: Option<Height>,
+        failovers: usize,
+    ) -> Result<LightBlock, Error> {
+        // Refuse to verify any further if a fork has been detected
+        let forked = self.forked_peers();
+        if !forked.is_empty() {
+            bail!(ErrorKind::ForkDetected(forked))
+        }
+
+        let primary_id = self.peers.primary_id();
         let primary = self.peers.primary_mut();
 
         // Perform light client core verification for the given height (or highest).
+        let started_at = Instant::now();
         let verdict = match height {
             None => primary.light_client.verify_to_highest(&mut primary.state),
             Some(height) => primary
                 .light_client
                 .verify_to_target(height, &mut primary.state),
         };
+        let response_time = started_at.elapsed();
 
         match verdict {
             // Verification succeeded, let's perform fork detection
             Ok(verified_block) => {
+                let primary = self.peers.primary();
                 let trusted_block = primary
                     .latest_trusted()
                     .ok_or_else(|| ErrorKind::NoTrustedState(Status::Trusted))?;
 
+                // The blocks verified by the primary on its way to the verified block,
+                // by increasing height.
+                let mut trace = primary.state.get_trace(verified_block.height());
+                trace.reverse();
+
+                // Record the response time per verified block, to make it comparable
+                // with the response time of the witnesses, which serve a single block.
+                let blocks = trace.len().max(1) as u32;
+                self.peers
+                    .stats_mut(primary_id)
+                    .record_success(response_time / blocks);
+
                 // Perform fork detection with the highest verified block and the trusted block.
-                let outcome = self.detect_forks(&verified_block, &trusted_block)?;
+                let outcome = self.detect_forks(&verified_block, &trusted_block, &trace)?;
 
                 match outcome {
                     // There was a fork or a faulty peer
                     ForkDetection::Detected(forks) => {
+                        let timeout = forks.iter().find_map(|fork| match fork {
+                            Fork::Timeout(_, error) => Some(error.clone()),
+                            _ => None,
+                        });
+
                         let forked = self.process_forks(forks)?;
                         if !forked.is_empty() {
                             // Fork detected, exiting
                             bail!(ErrorKind::ForkDetected(forked))
                         }
 
+                        // A witness which conflicts with the primary timed out, so the verified
+                        // block cannot be trusted for now. The witness will be consulted again
+                        // on the next verification.
+                        if let Some(error) = timeout {
+                            bail!(error)
+                        }
+
                         // If there were no hard forks, perform verification again
                         self.verify(height)
                     }
@@ -241,14 +659,39 @@ impl Supervisor {
                         // not that obvious).
                         self.peers.primary_mut().trust_block(&verified_block);
 
+                        // Rotate the primary if a witness performs significantly better
+                        self.rotate_primary();
+
                         // No fork detected, exiting
                         Ok(verified_block)
                     }
                 }
             }
-            // Verification failed
-            Err(_err) => {
-                // TODO: Log/record error
+            // The primary timed out or could not be reached. Promote the best scoring
+            // witness, if any, to primary, without deeming the current primary faulty,
+            // and try again.
+            Err(err) if matches!(err.kind(), ErrorKind::Io(_)) => {
+                let stats = self.peers.stats_mut(primary_id);
+                if err.kind().is_timeout() {
+                    stats.record_timeout();
+                } else {
+                    stats.record_failure();
+                }
+
+                match self.peers.best_witness() {
+                    Some(witness) if failovers > 0 => {
+                        self.swap_primary(witness);
+                        self.verify_with_failovers(height, failovers - 1)
+                    }
+                    _ => Err(err),
+                }
+            }
+            // Verification failed, the fault is recorded if the primary provided invalid data
+            Err(err) => {
+                self.peers.stats_mut(primary_id).record_failure();
+
+                let reason = FaultReason::VerificationFailed(err.kind().clone());
+                self.record_fault(primary_id, reason, height, vec![]);
 
                 // Swap primary, and continue with new primary, if there is any witness left.
                 self.peers.replace_faulty_primary()?;
@@ -263,22 +706,38 @@ impl Supervisor {
         for fork in forks {
             match fork {
                 // An actual fork was detected, report evidence and record forked peer.
-                // TODO: also report to primary
-                Fork::Forked { primary, witness } => {
+                Fork::Forked {
+                    common,
+                    primary,
+                    witness,
+                } => {
                     let provider = witness.provider;
-                    self.report_evidence(provider, &primary, &witness)?;
+                    self.report_fork(&common, &primary, &witness);
+
+                    let height = Some(witness.height());
+                    let light_blocks = vec![common, primary, witness];
+                    self.record_fault(provider, FaultReason::Forked, height, light_blocks);
 
                     forked.push(provider);
                 }
-                // A witness has timed out, remove it from the peer list.
+                // A witness has timed out, only record it in its statistics.
                 Fork::Timeout(provider, _error) => {
-                    self.peers.replace_faulty_witness(provider);
-                    // TODO: Log/record the error
+                    self.peers.stats_mut(provider).record_timeout();
                 }
-                // A witness has been deemed faulty, remove it from the peer list.
-                Fork::Faulty(block, _error) => {
-                    self.peers.replace_faulty_witness(block.provider);
-                    // TODO: Log/record the error
+                // A witness has been deemed faulty. If it provided invalid data, record it
+                // and remove it from the peer list. Otherwise, eg. if it could not be reached,
+                // only record the failure in its statistics.
+                Fork::Faulty(block, error) => {
+                    let provider = block.provider;
+                    self.peers.stats_mut(provider).record_failure();
+
+                    let reason = FaultReason::Faulty(error);
+                    if reason.proves_misbehaviour() {
+                        let height = Some(block.height());
+                        self.record_fault(provider, reason, height, vec![block]);
+
+                        self.peers.replace_faulty_witness(provider);
+                    }
                 }
             }
         }
@@ -286,90 +745,238 @@ impl Supervisor {
         Ok(forked)
     }
 
-    /// Report the given evidence of a fork.
-    fn report_evidence(
-        &mut self,
-        provider: PeerId,
-        primary: &LightBlock,
-        witness: &LightBlock,
-    ) -> Result<(), Error> {
-        let evidence = ConflictingHeadersEvidence::new(
-            primary.signed_header.clone(),
-            witness.signed_header.clone(),
-        );
+    /// Report to the primary and all witnesses the evidence of the attacks which
+    /// may have produced either the primary's or the witness's block, given the
+    /// last block on which both agree.
+    fn report_fork(&mut self, common: &LightBlock, primary: &LightBlock, witness: &LightBlock) {
+        let against_primary = evidence::attack_evidence(primary, witness, common);
+        let against_witness = evidence::attack_evidence(witness, primary, common);
 
-        self.evidence_reporter
-            .report(Evidence::ConflictingHeaders(Box::new(evidence)), provider)
-            .map_err(ErrorKind::Io)?;
+        let mut peers = vec![self.peers.primary_id()];
+        peers.extend(self.peers.witnesses_ids().iter().copied());
 
-        Ok(())
+        for evidence in vec![against_primary, against_witness] {
+            let evidence = Evidence::LightClientAttack(Box::new(evidence));
+            let report = EvidenceReport::new(evidence, peers.clone());
+
+            // Persist the evidence before reporting it, so that it can be reported
+            // again after a restart if some peers could not be reached.
+            self.evidence_store.insert(&report);
+            self.report_evidence(report);
+        }
+    }
+
+    /// Report again the evidence which some peers have not acknowledged yet.
+    fn report_pending_evidence(&mut self) {
+        for report in self.evidence_store.pending() {
+            self.report_evidence(report);
+        }
+    }
+
+    /// Report the given evidence to the peers it is pending for, and record
+    /// which of them acknowledged it.
+    ///
+    /// Peers which could not be reached are kept as pending, and the evidence
+    /// will be reported to them again the next time the supervisor starts.
+    /// Peers which are not in the peer list anymore are marked as undeliverable.
+    fn report_evidence(&mut self, mut report: EvidenceReport) {
+        for peer in report.pending.clone() {
+            // Only report to the peers we know the address of
+            if self.peers.get(&peer).is_none() {
+                report.mark_undeliverable(peer);
+                continue;
+            }
+
+            // TODO: Log/record the error
+            if let Ok(hash) = self.evidence_reporter.report(report.evidence.clone(), peer) {
+                report.acknowledge(peer, hash);
+            }
+        }
+
+        self.evidence_store.insert(&report);
     }
 
     /// Perform fork detection with the given verified block and trusted block.
+    ///
+    /// Each witness is consulted in turn, in order to record its response time.
+    /// Witnesses which time out or cannot be reached are skipped, unless none of
+    /// the witnesses could be consulted.
     fn detect_forks(
-        &self,
+        &mut self,
         verified_block: &LightBlock,
         trusted_block: &LightBlock,
+        trace: &[LightBlock],
     ) -> Result<ForkDetection, Error> {
         if self.peers.witnesses_ids().is_empty() {
             bail!(ErrorKind::NoWitnesses);
         }
 
-        let witnesses = self
-            .peers
-            .witnesses_ids()
-            .iter()
-            .filter_map(|id| self.peers.get(id))
-            .collect();
+        let witnesses_ids: Vec<PeerId> = self.peers.witnesses_ids().iter().copied().collect();
+        let mut forks = Vec::new();
+        let mut last_io_error = None;
+        let mut consulted = 0;
+
+        for witness_id in witnesses_ids {
+            let witness = match self.peers.get(&witness_id) {
+                Some(witness) => witness,
+                None => continue,
+            };
+
+            let started_at = Instant::now();
+            let outcome = self.fork_detector.detect_forks(
+                verified_block,
+                &trusted_block,
+                trace,
+                vec![witness],
+            );
+            let response_time = started_at.elapsed();
+
+            // Forks and faulty witnesses are recorded when processing the forks
+            let stats = self.peers.stats_mut(witness_id);
+            match &outcome {
+                Ok(ForkDetection::NotDetected) => stats.record_success(response_time),
+                Ok(ForkDetection::Detected(_)) => (),
+                Err(e) if e.kind().is_timeout() => stats.record_timeout(),
+                Err(_) => stats.record_failure(),
+            }
+
+            match outcome {
+                Ok(ForkDetection::NotDetected) => (),
+                Ok(ForkDetection::Detected(mut witness_forks)) => forks.append(&mut witness_forks),
+                Err(e) if matches!(e.kind(), ErrorKind::Io(_)) => {
+                    last_io_error = Some(e);
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+
+            consulted += 1;
+        }
+
+        if let (0, Some(e)) = (consulted, last_io_error) {
+            return Err(e);
+        }
+
+        if forks.is_empty() {
+            Ok(ForkDetection::NotDetected)
+        } else {
+            Ok(ForkDetection::Detected(forks))
+        }
+    }
+
+    /// Promote the best scoring witness to primary if it scores significantly
+    /// better than the primary, and both have served enough requests.
+    fn rotate_primary(&mut self) {
+        let primary_stats = self.peers.stats(self.peers.primary_id());
+        if primary_stats.samples() < MIN_SAMPLES_FOR_ROTATION {
+            return;
+        }
+
+        if let Some(witness) = self.peers.best_witness() {
+            let witness_stats = self.peers.stats(witness);
 
-        self.fork_detector
-            .detect_forks(verified_block, &trusted_block, witnesses)
+            if witness_stats.samples() >= MIN_SAMPLES_FOR_ROTATION
+                && witness_stats.score() > primary_stats.score() * ROTATION_SCORE_FACTOR
+            {
+                self.swap_primary(witness);
+            }
+        }
+    }
+
+    /// Promote the given witness to primary, without deeming the current primary faulty.
+    ///
+    /// If the new primary has no trusted state yet, it is initialized with
+    /// the latest block trusted by the former primary.
+    fn swap_primary(&mut self, witness: PeerId) {
+        let latest_trusted = self.latest_trusted();
+        self.peers.swap_primary(witness);
+
+        let primary = self.peers.primary_mut();
+        if primary.latest_trusted().is_none() {
+            if let Some(trusted) = latest_trusted {
+                primary.trust_block(&trusted);
+            }
+        }
     }
 
     /// Run the supervisor event loop in the same thread.
     ///
     /// This method should typically be called within a new thread with `std::thread::spawn`.
     pub fn run(mut self) -> Result<(), Error> {
+        self.report_pending_evidence();
+        self.update_snapshot();
+
         loop {
-            let event = self.receiver.recv().map_err(ErrorKind::from)?;
+            let event = match self.pending.pop_front() {
+                Some(event) => event,
+                None => self.receiver.recv().map_err(ErrorKind::from)?,
+            };
 
             match event {
-                HandleInput::LatestTrusted(sender) => {
-                    let outcome = self.latest_trusted();
-                    sender.send(outcome).map_err(ErrorKind::from)?;
-                }
                 HandleInput::Terminate(sender) => {
                     sender.send(()).map_err(ErrorKind::from)?;
                     return Ok(());
                 }
                 HandleInput::VerifyToTarget(height, sender) => {
-                    let outcome = self.verify_to_target(height);
-                    sender.send(outcome).map_err(ErrorKind::from)?;
+                    self.process_verification(Some(height), sender);
                 }
                 HandleInput::VerifyToHighest(sender) => {
-                    let outcome = self.verify_to_highest();
+                    self.process_verification(None, sender);
+                }
+                HandleInput::AddWitness(instance, address, sender) => {
+                    let outcome = self.add_peer(*instance, address, true);
+                    self.update_snapshot();
+                    sender.send(outcome).map_err(ErrorKind::from)?;
+                }
+                HandleInput::AddFullNode(instance, address, sender) => {
+                    let outcome = self.add_peer(*instance, address, false);
+                    self.update_snapshot();
                     sender.send(outcome).map_err(ErrorKind::from)?;
                 }
-                HandleInput::GetStatus(sender) => {
-                    let outcome = self.latest_status();
+                HandleInput::RemovePeer(peer_id, sender) => {
+                    let outcome = self.remove_peer(peer_id);
+                    self.update_snapshot();
                     sender.send(outcome).map_err(ErrorKind::from)?;
                 }
+                HandleInput::ClearFault(peer_id, sender) => {
+                    let record = self.clear_fault(peer_id);
+                    self.update_snapshot();
+                    sender.send(Ok(record)).map_err(ErrorKind::from)?;
+                }
             }
         }
     }
 }
 
+/// Copy the given error, in order to reply with it to several callers.
+/// Its source is kept as a message, as it cannot be cloned.
+fn share_error(e: &Error) -> Error {
+    match std::error::Error::source(e) {
+        Some(source) => e.kind().clone().context(source.to_string()).into(),
+        None => e.kind().clone().into(),
+    }
+}
+
 /// A [`Handle`] to the [`Supervisor`] which allows to communicate with
 /// the supervisor across thread boundaries via message passing.
+///
+/// Read-only queries are served from the snapshot of the supervisor's state.
 struct SupervisorHandle {
     sender: channel::Sender<HandleInput>,
+    snapshot: Arc<RwLock<Snapshot>>,
 }
 
 impl SupervisorHandle {
     /// Crate a new handle that sends events to the supervisor via
-    /// the given channel. For internal use only.
-    fn new(sender: channel::Sender<HandleInput>) -> Self {
-        Self { sender }
+    /// the given channel, and reads the given snapshot of its state.
+    /// For internal use only.
+    fn new(sender: channel::Sender<HandleInput>, snapshot: Arc<RwLock<Snapshot>>) -> Self {
+        Self { sender, snapshot }
+    }
+
+    /// Read the given part of the snapshot of the supervisor's state.
+    fn read<T>(&self, f: impl FnOnce(&Snapshot) -> T) -> T {
+        f(&self.snapshot.read().unwrap_or_else(PoisonError::into_inner))
     }
 
     fn verify(
@@ -383,34 +990,60 @@ impl SupervisorHandle {
 
         receiver.recv().map_err(ErrorKind::from)?
     }
+
+    fn update_peers<T>(
+        &self,
+        make_event: impl FnOnce(channel::Sender<Result<T, Error>>) -> HandleInput,
+    ) -> Result<T, Error> {
+        let (sender, receiver) = channel::bounded::<Result<T, Error>>(1);
+
+        let event = make_event(sender);
+        self.sender.send(event).map_err(ErrorKind::from)?;
+
+        receiver.recv().map_err(ErrorKind::from)?
+    }
 }
 impl Handle for SupervisorHandle {
     fn latest_trusted(&self) -> Result<Option<LightBlock>, Error> {
-        let (sender, receiver) = channel::bounded::<Option<LightBlock>>(1);
-
-        self.sender
-            .send(HandleInput::LatestTrusted(sender))
-            .map_err(ErrorKind::from)?;
-
-        Ok(receiver.recv().map_err(ErrorKind::from)?)
+        Ok(self.read(|snapshot| snapshot.latest_trusted.clone()))
     }
 
     fn latest_status(&self) -> Result<LatestStatus, Error> {
-        let (sender, receiver) = channel::bounded::<LatestStatus>(1);
-        self.sender
-            .send(HandleInput::GetStatus(sender))
-            .map_err(ErrorKind::from)?;
-        Ok(receiver.recv().map_err(ErrorKind::from)?)
+        Ok(self.read(|snapshot| snapshot.latest_status.clone()))
     }
 
     fn verify_to_highest(&self) -> Result<LightBlock, Error> {
         self.verify(HandleInput::VerifyToHighest)
     }
 
+    fn evidence_reports(&self) -> Result<Vec<EvidenceReport>, Error> {
+        Ok(self.read(|snapshot| snapshot.evidence_reports.clone()))
+    }
+
     fn verify_to_target(&self, height: Height) -> Result<LightBlock, Error> {
         self.verify(|sender| HandleInput::VerifyToTarget(height, sender))
     }
 
+    fn faulty_peers(&self) -> Result<Vec<FaultRecord>, Error> {
+        Ok(self.read(|snapshot| snapshot.faulty_peers.clone()))
+    }
+
+    fn add_witness(&self, instance: Instance, address: net::Address) -> Result<(), Error> {
+        self.update_peers(|sender| HandleInput::AddWitness(Box::new(instance), address, sender))
+    }
+
+    fn add_full_node(&self, instance: Instance, address: net::Address) -> Result<(), Error> {
+        self.update_peers(|sender| HandleInput::AddFullNode(Box::new(instance), address, sender))
+    }
+
+    fn remove_peer(&self, peer_id: PeerId) -> Result<(), Error> {
+        self.update_peers(|sender| HandleInput::RemovePeer(peer_id, sender))
+    }
+
+    fn clear_fault(&self, peer_id: PeerId) -> Result<Option<FaultRecord>, Error> {
+        self.update_peers(|sender| HandleInput::ClearFault(peer_id, sender))
+    }
+
     fn terminate(&self) -> Result<(), Error> {
         let (sender, receiver) = channel::bounded::<()>(1);
 
@@ -11,6 +11,8 @@ use crate::components::io::{AtHeight, Io, IoError};
 use crate::evidence::EvidenceReporter;
 use contracts::contract_trait;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 use tendermint::block::Height as HeightStr;
 use tendermint::evidence::{Duration as DurationStr, Evidence};
 
@@ -98,6 +100,10 @@ pub struct MockIo {
     chain_id: String,
     light_blocks: HashMap<Height, LightBlock>,
     latest_height: Height,
+    /// Whether requests time out, shared with the clones of this `MockIo`
+    timeout: Arc<AtomicBool>,
+    /// Number of requests received, shared with the clones of this `MockIo`
+    fetches: Arc<AtomicUsize>,
 }
 
 impl MockIo {
@@ -113,13 +119,35 @@ impl MockIo {
             chain_id,
             light_blocks,
             latest_height,
+            timeout: Arc::new(AtomicBool::new(false)),
+            fetches: Arc::new(AtomicUsize::new(0)),
         }
     }
+
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    /// Make the requests to this `MockIo` and its clones time out, or not.
+    pub fn set_timeout(&self, timeout: bool) {
+        self.timeout.store(timeout, Ordering::SeqCst);
+    }
+
+    /// The number of requests received by this `MockIo` and its clones.
+    pub fn fetches(&self) -> usize {
+        self.fetches.load(Ordering::SeqCst)
+    }
 }
 
 #[contract_trait]
 impl Io for MockIo {
-    fn fetch_light_block(&self, _peer: PeerId, height: AtHeight) -> Result<LightBlock, IoError> {
+    fn fetch_light_block(&self, peer: PeerId, height: AtHeight) -> Result<LightBlock, IoError> {
+        self.fetches.fetch_add(1, Ordering::SeqCst);
+
+        if self.timeout.load(Ordering::SeqCst) {
+            return Err(IoError::Timeout(peer));
+        }
+
         let height = match height {
             AtHeight::Highest => self.latest_height,
             AtHeight::At(height) => height,
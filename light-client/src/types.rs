@@ -2,6 +2,10 @@
 
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::errors::ErrorKind;
+use crate::predicates::errors::VerificationError;
 
 use tendermint::{
     account::Id as TMAccountId,
@@ -148,6 +152,126 @@ impl LatestStatus {
     }
 }
 
+/// The reason for which a peer has been deemed faulty by the supervisor.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FaultReason {
+    /// The peer provided a block which conflicts with the one verified by the primary,
+    /// and which could be verified from the last block on which both agree.
+    Forked,
+    /// The peer provided a block which could not be verified, or which conflicts
+    /// with our trusted state.
+    Faulty(ErrorKind),
+    /// The peer, acting as the primary, failed to provide a verifiable block.
+    VerificationFailed(ErrorKind),
+}
+
+impl FaultReason {
+    /// Whether this fault proves that the peer misbehaved, ie. that it provided
+    /// a forked block or invalid data.
+    ///
+    /// Other faults, such as I/O errors, or blocks which cannot be trusted yet or
+    /// anymore according to our clock, may be transient and are not recorded.
+    pub fn proves_misbehaviour(&self) -> bool {
+        match self {
+            Self::Forked => true,
+            Self::Faulty(kind) | Self::VerificationFailed(kind) => match kind {
+                ErrorKind::ConflictingTrustedState(_) => true,
+                ErrorKind::InvalidLightBlock(e) => !matches!(
+                    e,
+                    VerificationError::NotEnoughTrust(_)
+                        | VerificationError::NotWithinTrustPeriod { .. }
+                        | VerificationError::HeaderFromTheFuture { .. }
+                ),
+                _ => false,
+            },
+        }
+    }
+}
+
+/// A record of a peer having been deemed faulty by the supervisor,
+/// along with the offending light blocks, if any.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FaultRecord {
+    /// The faulty peer.
+    pub peer: PeerId,
+    /// The reason for which the peer has been deemed faulty.
+    pub reason: FaultReason,
+    /// The height at which the fault occured, if known.
+    pub height: Option<Height>,
+    /// The offending light blocks.
+    ///
+    /// For a fork, these are the last block on which the primary and the peer agree,
+    /// followed by the primary's and the peer's blocks at the height where they diverge.
+    pub light_blocks: Vec<LightBlock>,
+    /// The time at which the fault was recorded.
+    pub time: Time,
+}
+
+/// Statistics about the requests served by a peer, from which a score
+/// is derived to elect the primary among the witnesses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeerStats {
+    /// Number of requests served successfully.
+    pub successes: u64,
+    /// Number of requests which timed out.
+    pub timeouts: u64,
+    /// Number of requests which failed, eg. because of a failed verification.
+    pub failures: u64,
+    /// Exponential moving average of the response time of successful requests.
+    pub average_latency: Option<Duration>,
+}
+
+impl PeerStats {
+    /// Weight of the latest response time in the average response time.
+    const LATENCY_SMOOTHING: f64 = 0.2;
+
+    /// Response time assumed for a peer which has not served any request yet,
+    /// so that it does not rank above peers known to respond reasonably fast.
+    const PRIOR_LATENCY: Duration = Duration::from_secs(1);
+
+    /// Record a request served successfully, along with its response time.
+    pub fn record_success(&mut self, latency: Duration) {
+        self.successes += 1;
+        self.average_latency = Some(match self.average_latency {
+            Some(average) => {
+                average.mul_f64(1.0 - Self::LATENCY_SMOOTHING)
+                    + latency.mul_f64(Self::LATENCY_SMOOTHING)
+            }
+            None => latency,
+        });
+    }
+
+    /// Record a request which timed out.
+    pub fn record_timeout(&mut self) {
+        self.timeouts += 1;
+    }
+
+    /// Record a request which failed.
+    pub fn record_failure(&mut self) {
+        self.failures += 1;
+    }
+
+    /// Total number of requests recorded.
+    pub fn samples(&self) -> u64 {
+        self.successes + self.timeouts + self.failures
+    }
+
+    /// Score of the peer, between 0 and 1, higher is better.
+    ///
+    /// The score is the product of the peer's reliability, ie. its (smoothed) rate of
+    /// successful requests, and of its speed, which decreases as its average response
+    /// time increases. A peer without any recorded request scores 0.25, ie. as
+    /// a peer succeeding half of the time, in one second.
+    pub fn score(&self) -> f64 {
+        let reliability = (self.successes + 1) as f64 / (self.samples() + 2) as f64;
+
+        let latency = self.average_latency.unwrap_or(Self::PRIOR_LATENCY);
+        let speed = 1.0 / (1.0 + latency.as_secs_f64());
+
+        reliability * speed
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -183,4 +307,62 @@ mod tests {
                 .collect()
         }
     }
+
+    mod peer_stats {
+        use crate::types::PeerStats;
+        use std::time::Duration;
+
+        #[test]
+        fn unknown_peer_scores_a_quarter() {
+            assert!((PeerStats::default().score() - 0.25).abs() < f64::EPSILON);
+        }
+
+        #[test]
+        fn sampled_peer_scores_better_than_unknown_peer() {
+            let unknown = PeerStats::default();
+            let mut sampled = PeerStats::default();
+
+            sampled.record_success(Duration::from_millis(500));
+
+            assert!(sampled.score() > unknown.score());
+        }
+
+        #[test]
+        fn failing_peer_scores_worse_than_unknown_peer() {
+            let unknown = PeerStats::default();
+            let mut failing = PeerStats::default();
+
+            failing.record_failure();
+
+            assert!(failing.score() < unknown.score());
+        }
+
+        #[test]
+        fn fast_peer_scores_better_than_slow_peer() {
+            let mut fast = PeerStats::default();
+            let mut slow = PeerStats::default();
+
+            for _ in 0..10 {
+                fast.record_success(Duration::from_millis(100));
+                slow.record_success(Duration::from_secs(2));
+            }
+
+            assert!(fast.score() > slow.score());
+        }
+
+        #[test]
+        fn reliable_peer_scores_better_than_unreliable_peer() {
+            let mut reliable = PeerStats::default();
+            let mut unreliable = PeerStats::default();
+
+            for _ in 0..10 {
+                reliable.record_success(Duration::from_millis(100));
+                unreliable.record_success(Duration::from_millis(100));
+                unreliable.record_timeout();
+            }
+
+            assert_eq!(unreliable.samples(), 20);
+            assert!(reliable.score() > unreliable.score());
+        }
+    }
 }
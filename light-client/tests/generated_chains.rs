@@ -0,0 +1,227 @@
+//! Light client tests against chains built with `tendermint-testgen`,
+//! rather than against static JSON fixtures.
+
+use tendermint_light_client::{
+    components::{scheduler, verifier::ProdVerifier},
+    errors::ErrorKind,
+    fork_detector::ProdForkDetector,
+    light_client::{self, LightClient},
+    peer_list::PeerList,
+    predicates::errors::VerificationError,
+    state::State,
+    store::memory::{MemoryEvidenceStore, MemoryFaultStore, MemoryStore},
+    supervisor::{Handle, Instance, Supervisor},
+    tests::{MockClock, MockEvidenceReporter, MockIo},
+    types::{LightBlock, PeerId, Status, TrustThreshold},
+};
+
+use tendermint_testgen::{Fault, LightChain, Validator};
+
+use std::time::Duration;
+
+fn light_blocks(chain: &LightChain, provider: PeerId) -> Vec<LightBlock> {
+    chain
+        .blocks()
+        .iter()
+        .cloned()
+        .map(|block| {
+            LightBlock::new(
+                block.signed_header,
+                block.validators,
+                block.next_validators,
+                provider,
+            )
+        })
+        .collect()
+}
+
+/// Make an instance which fetches the blocks of the given chain, and trusts its first block.
+fn make_instance(peer_id: PeerId, chain: &LightChain) -> Instance {
+    let io = MockIo::new(chain.chain_id().to_string(), light_blocks(chain, peer_id));
+
+    let options = light_client::Options {
+        trust_threshold: TrustThreshold::default(),
+        trusting_period: Duration::from_secs(24 * 60 * 60),
+        clock_drift: Duration::from_secs(10),
+        chain_id: chain.chain_id(),
+    };
+
+    let clock = MockClock {
+        now: LightChain::time_at(chain.height() + 1),
+    };
+
+    let light_client = LightClient::new(
+        peer_id,
+        options,
+        clock,
+        scheduler::basic_bisecting_schedule,
+        ProdVerifier::default(),
+        io,
+    );
+
+    let mut state = State::new(MemoryStore::new());
+    state
+        .light_store
+        .insert(light_blocks(chain, peer_id).remove(0), Status::Trusted);
+
+    Instance::new(light_client, state)
+}
+
+fn make_chain(validators: Vec<Validator>) -> LightChain {
+    LightChain::new("test-chain".parse().unwrap(), validators)
+}
+
+#[test]
+fn verify_across_validator_set_changes() {
+    let validators = Validator::from_ids(&["a", "b", "c", "d", "e", "f", "g", "h"]);
+
+    let mut chain = make_chain(validators[..4].to_vec());
+    chain.advance_to(3).unwrap();
+
+    // Half of the validators are replaced at height 5, the other half at height 9
+    chain.set_next_validators(validators[2..6].to_vec());
+    chain.advance_to(7).unwrap();
+    chain.set_next_validators(validators[4..].to_vec());
+    chain.advance_to(12).unwrap();
+
+    let peer_id = "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE".parse().unwrap();
+    let mut instance = make_instance(peer_id, &chain);
+
+    let verified = instance
+        .light_client
+        .verify_to_target(12, &mut instance.state)
+        .unwrap();
+
+    assert_eq!(
+        verified.signed_header,
+        chain.block(12).unwrap().signed_header
+    );
+}
+
+#[test]
+fn reject_headers_of_another_chain() {
+    let mut chain = make_chain(Validator::from_ids(&["a", "b", "c", "d"]));
+    chain.advance_to(2).unwrap();
+
+    let peer_id = "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE".parse().unwrap();
+    let mut instance = make_instance(peer_id, &chain);
+    instance.light_client.options.chain_id = "other-chain".parse().unwrap();
+
+    let result = instance
+        .light_client
+        .verify_to_target(2, &mut instance.state);
+
+    match result {
+        Err(e) => match e.kind() {
+            ErrorKind::InvalidLightBlock(VerificationError::ChainIdMismatch { got, expected }) => {
+                assert_eq!(got.as_str(), "test-chain");
+                assert_eq!(expected.as_str(), "other-chain");
+            }
+            kind => panic!("expected a chain id mismatch, got: {}", kind),
+        },
+        Ok(_) => panic!("expected a chain id mismatch"),
+    }
+}
+
+#[test]
+fn reject_block_with_an_invalid_signature() {
+    let validators = Validator::from_ids(&["a", "b", "c", "d"]);
+
+    let mut chain = make_chain(validators.clone());
+    chain.advance().unwrap();
+    chain
+        .advance_with(&[Fault::InvalidSignature(validators[1].clone())])
+        .unwrap();
+
+    let peer_id = "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE".parse().unwrap();
+    let mut instance = make_instance(peer_id, &chain);
+
+    let result = instance
+        .light_client
+        .verify_to_target(2, &mut instance.state);
+
+    match result {
+        Err(e) => match e.kind() {
+            ErrorKind::InvalidLightBlock(VerificationError::InvalidSignature {
+                validator, ..
+            }) => {
+                assert_eq!(validator.address, validators[1].address());
+            }
+            kind => panic!("expected an invalid signature, got: {}", kind),
+        },
+        Ok(_) => panic!("expected an invalid signature"),
+    }
+}
+
+#[test]
+fn reject_block_without_enough_signatures() {
+    let validators = Validator::from_ids(&["a", "b", "c", "d"]);
+
+    let mut chain = make_chain(validators.clone());
+    chain.advance().unwrap();
+
+    // Only half of the voting power signs the block, which is not more than 2/3
+    chain
+        .advance_with(&[
+            Fault::Absent(validators[0].clone()),
+            Fault::Absent(validators[1].clone()),
+        ])
+        .unwrap();
+
+    let peer_id = "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE".parse().unwrap();
+    let mut instance = make_instance(peer_id, &chain);
+
+    let result = instance
+        .light_client
+        .verify_to_target(2, &mut instance.state);
+
+    match result {
+        Err(e) => match e.kind() {
+            ErrorKind::InvalidLightBlock(VerificationError::InsufficientSignersOverlap(tally)) => {
+                assert_eq!(tally.tallied * 2, tally.total);
+            }
+            kind => panic!("expected not enough signatures, got: {}", kind),
+        },
+        Ok(_) => panic!("expected not enough signatures"),
+    }
+}
+
+#[test]
+fn detect_fork_signed_by_the_same_validators() {
+    let validators = Validator::from_ids(&["a", "b", "c", "d"]);
+
+    let mut chain = make_chain(validators);
+    chain.advance_to(5).unwrap();
+
+    let mut fork = chain.fork_at(3, "fork");
+    fork.advance_to(5).unwrap();
+
+    let primary = "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE".parse().unwrap();
+    let witness = "CEFEEDBADFADAD0C0CEEFACADE0ADEADBEEFC0FF".parse().unwrap();
+
+    let peer_list = PeerList::builder()
+        .primary(primary, make_instance(primary, &chain))
+        .witness(witness, make_instance(witness, &fork))
+        .build();
+
+    let mut supervisor = Supervisor::new(
+        peer_list,
+        ProdForkDetector::default(),
+        MockEvidenceReporter::new(),
+        MemoryEvidenceStore::new(),
+        MemoryFaultStore::new(),
+    );
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let result = handle.verify_to_target(5);
+
+    match result {
+        Err(e) => match e.kind() {
+            ErrorKind::ForkDetected(_) => {}
+            kind => panic!("expected a fork to be detected, got: {}", kind),
+        },
+        Ok(_) => panic!("expected a fork to be detected"),
+    }
+}
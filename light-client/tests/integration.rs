@@ -19,7 +19,10 @@ use tendermint_light_client::{
     light_client::{self, LightClient},
     peer_list::PeerList,
     state::State,
-    store::{memory::MemoryStore, LightStore},
+    store::{
+        memory::{MemoryEvidenceStore, MemoryFaultStore, MemoryStore},
+        LightStore,
+    },
     supervisor::{Handle, Instance, Supervisor},
     types::{PeerId, Status, TrustThreshold},
 };
@@ -89,6 +92,7 @@ fn sync() {
         },
         trusting_period: Duration::from_secs(60 * 60), // 60 minutes
         clock_drift: Duration::from_secs(5 * 60),      // 5 minutes
+        chain_id: "dockerchain".parse().unwrap(),
     };
 
     let primary_instance = make_instance(primary, options, io.clone());
@@ -99,8 +103,13 @@ fn sync() {
         .witness(witness, witness_instance)
         .build();
 
-    let mut supervisor =
-        Supervisor::new(peer_list, ProdForkDetector::default(), TestEvidenceReporter);
+    let mut supervisor = Supervisor::new(
+        peer_list,
+        ProdForkDetector::default(),
+        TestEvidenceReporter,
+        MemoryEvidenceStore::new(),
+        MemoryFaultStore::new(),
+    );
 
     let handle = supervisor.handle();
     std::thread::spawn(|| supervisor.run());
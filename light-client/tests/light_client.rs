@@ -49,6 +49,7 @@ fn verify_single(
         trust_threshold,
         trusting_period,
         clock_drift,
+        chain_id: trusted_state.signed_header.header.chain_id,
     };
 
     let result = verifier.verify(&input, &trusted_state, &options, now.into());
@@ -142,6 +143,7 @@ fn run_bisection_test(tc: TestBisection<LightBlock>) -> BisectionTestResult {
         trust_threshold,
         trusting_period: trusting_period.into(),
         clock_drift,
+        chain_id: tc.primary.chain_id.parse().unwrap(),
     };
 
     let provider = tc.primary;
@@ -1,18 +1,24 @@
 use tendermint_light_client::{
     components::{
-        io::{AtHeight, Io},
+        io::{AtHeight, Io, IoError},
         scheduler,
         verifier::ProdVerifier,
     },
+    errors::ErrorKind,
+    evidence::{attack_evidence, Evidence, EvidenceReport},
     fork_detector::ProdForkDetector,
     light_client::{self, LightClient},
     peer_list::PeerList,
     state::State,
-    store::LightStore,
+    store::{EvidenceStore, FaultStore},
     supervisor::{Handle, Instance, Supervisor},
-    types::{LightBlock, PeerId, Status, Time},
+    types::{FaultReason, FaultRecord, LightBlock, PeerId, Status, Time},
 };
 
+use tendermint::evidence::AttackType;
+use tendermint::lite::Header;
+use tendermint::Genesis;
+
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::{
@@ -21,9 +27,10 @@ use std::{
     time::Duration,
 };
 
-use tendermint_light_client::store::memory::MemoryStore;
+use tendermint_light_client::store::memory::{MemoryEvidenceStore, MemoryFaultStore, MemoryStore};
 use tendermint_light_client::tests::{
-    AnonLightBlock, MockClock, MockEvidenceReporter, MockIo, TestBisection, TrustOptions,
+    peer_id_at, AnonLightBlock, MockClock, MockEvidenceReporter, MockIo, TestBisection,
+    TrustOptions,
 };
 
 const TEST_FILES_PATH: &str = "./tests/support/";
@@ -43,17 +50,35 @@ fn load_multi_peer_testcases(dir: &str) -> Vec<TestBisection<LightBlock>> {
         .collect::<Vec<TestBisection<LightBlock>>>()
 }
 
+fn load_multi_peer_testcase(file: &str) -> TestBisection<LightBlock> {
+    let path = PathBuf::from(TEST_FILES_PATH)
+        .join("bisection/multi_peer")
+        .join(file);
+
+    serde_json::from_str::<TestBisection<AnonLightBlock>>(&read_json_fixture(path))
+        .unwrap()
+        .into()
+}
+
 fn make_instance(peer_id: PeerId, trust_options: TrustOptions, io: MockIo, now: Time) -> Instance {
     let trusted_height = trust_options.height.value();
     let trusted_state = io
         .fetch_light_block(peer_id, AtHeight::At(trusted_height))
         .expect("could not 'request' light block");
 
-    let mut light_store = MemoryStore::new();
-    light_store.insert(trusted_state, Status::Trusted);
+    let mut instance = make_uninitialized_instance(peer_id, trust_options, io, now);
+    instance.trust_block(&trusted_state);
+    instance
+}
 
+fn make_uninitialized_instance(
+    peer_id: PeerId,
+    trust_options: TrustOptions,
+    io: MockIo,
+    now: Time,
+) -> Instance {
     let state = State {
-        light_store: Box::new(light_store),
+        light_store: Box::new(MemoryStore::new()),
         verification_trace: HashMap::new(),
     };
 
@@ -61,6 +86,7 @@ fn make_instance(peer_id: PeerId, trust_options: TrustOptions, io: MockIo, now:
         trust_threshold: trust_options.trust_level,
         trusting_period: trust_options.period.into(),
         clock_drift: Duration::from_secs(10),
+        chain_id: io.chain_id().parse().unwrap(),
     };
 
     let verifier = ProdVerifier::default();
@@ -72,6 +98,24 @@ fn make_instance(peer_id: PeerId, trust_options: TrustOptions, io: MockIo, now:
     Instance::new(light_client, state)
 }
 
+fn make_genesis(light_block: &LightBlock) -> Genesis<()> {
+    let consensus_params = serde_json::from_value(serde_json::json!({
+        "block": { "max_bytes": "22020096", "max_gas": "-1", "time_iota_ms": "1000" },
+        "evidence": { "max_age_num_blocks": "100000", "max_age_duration": "172800000000000" },
+        "validator": { "pub_key_types": ["ed25519"] }
+    }))
+    .unwrap();
+
+    Genesis {
+        genesis_time: light_block.signed_header.header.time,
+        chain_id: light_block.signed_header.header.chain_id,
+        consensus_params,
+        validators: light_block.validators.validators().clone(),
+        app_hash: vec![],
+        app_state: (),
+    }
+}
+
 fn run_multipeer_test(tc: TestBisection<LightBlock>) {
     let primary = tc.primary.lite_blocks[0].provider;
 
@@ -103,6 +147,8 @@ fn run_multipeer_test(tc: TestBisection<LightBlock>) {
         peer_list.build(),
         ProdForkDetector::default(),
         MockEvidenceReporter::new(),
+        MemoryEvidenceStore::new(),
+        MemoryFaultStore::new(),
     );
 
     // TODO: Add method to `Handle` to get a copy of the current peer list
@@ -148,3 +194,542 @@ fn run_multipeer_tests() {
         run_multipeer_test(testcase);
     }
 }
+
+#[test]
+fn initialize_from_genesis() {
+    let testcases = load_multi_peer_testcases("bisection/multi_peer");
+    let tc = testcases.into_iter().next().unwrap();
+
+    let primary = tc.primary.lite_blocks[0].provider;
+    let genesis = make_genesis(&tc.primary.lite_blocks[0]);
+
+    let io = MockIo::new(tc.primary.chain_id, tc.primary.lite_blocks);
+    let mut instance = make_uninitialized_instance(primary, tc.trust_options, io, tc.now);
+
+    let light_block = instance.initialize_from_genesis(&genesis).unwrap();
+
+    assert_eq!(light_block.height(), 1);
+    assert_eq!(instance.latest_trusted(), Some(light_block));
+}
+
+#[test]
+fn initialize_from_genesis_with_wrong_chain_id() {
+    let testcases = load_multi_peer_testcases("bisection/multi_peer");
+    let tc = testcases.into_iter().next().unwrap();
+
+    let primary = tc.primary.lite_blocks[0].provider;
+    let mut genesis = make_genesis(&tc.primary.lite_blocks[0]);
+    genesis.chain_id = "other-chain".parse().unwrap();
+
+    let io = MockIo::new(tc.primary.chain_id, tc.primary.lite_blocks);
+    let mut instance = make_uninitialized_instance(primary, tc.trust_options, io, tc.now);
+
+    assert!(instance.initialize_from_genesis(&genesis).is_err());
+    assert_eq!(instance.latest_trusted(), None);
+}
+
+#[test]
+fn initialize_from_trust_options() {
+    let testcases = load_multi_peer_testcases("bisection/multi_peer");
+    let tc = testcases.into_iter().next().unwrap();
+
+    let primary = tc.primary.lite_blocks[0].provider;
+    let trusted_height = tc.trust_options.height.value();
+    let trusted_hash = tc.trust_options.hash;
+
+    let io = MockIo::new(tc.primary.chain_id, tc.primary.lite_blocks);
+    let mut instance = make_uninitialized_instance(primary, tc.trust_options, io, tc.now);
+
+    let light_block = instance
+        .initialize_from_trust_options(trusted_height, trusted_hash)
+        .unwrap();
+
+    assert_eq!(light_block.height(), trusted_height);
+    assert_eq!(instance.latest_trusted(), Some(light_block));
+}
+
+#[test]
+fn initialize_from_trust_options_with_wrong_hash() {
+    let testcases = load_multi_peer_testcases("bisection/multi_peer");
+    let tc = testcases.into_iter().next().unwrap();
+
+    let primary = tc.primary.lite_blocks[0].provider;
+    let trusted_height = tc.trust_options.height.value();
+    let wrong_hash = tc.primary.lite_blocks[1].signed_header.header.hash();
+
+    let io = MockIo::new(tc.primary.chain_id, tc.primary.lite_blocks);
+    let mut instance = make_uninitialized_instance(primary, tc.trust_options, io, tc.now);
+
+    let result = instance.initialize_from_trust_options(trusted_height, wrong_hash);
+
+    assert!(result.is_err());
+    assert_eq!(instance.latest_trusted(), None);
+}
+
+#[test]
+fn initialize_from_trust_options_with_wrong_chain_id() {
+    let testcases = load_multi_peer_testcases("bisection/multi_peer");
+    let tc = testcases.into_iter().next().unwrap();
+
+    let primary = tc.primary.lite_blocks[0].provider;
+    let trusted_height = tc.trust_options.height.value();
+    let trusted_hash = tc.trust_options.hash;
+
+    let io = MockIo::new(tc.primary.chain_id, tc.primary.lite_blocks);
+    let mut instance = make_uninitialized_instance(primary, tc.trust_options, io, tc.now);
+    instance.light_client.options.chain_id = "other-chain".parse().unwrap();
+
+    let result = instance.initialize_from_trust_options(trusted_height, trusted_hash);
+
+    assert!(result.is_err());
+    assert_eq!(instance.latest_trusted(), None);
+}
+
+#[test]
+fn lunatic_attack_evidence() {
+    let tc = load_multi_peer_testcase("malicious_validator_set.json");
+
+    let common = &tc.primary.lite_blocks[0];
+    let trusted = &tc.primary.lite_blocks[10];
+    let conflicting = &tc.witnesses[0].value.lite_blocks[10];
+
+    assert_eq!(
+        AttackType::classify(&conflicting.signed_header, &trusted.signed_header),
+        AttackType::Lunatic
+    );
+
+    let evidence = attack_evidence(conflicting, trusted, common);
+
+    assert_eq!(evidence.common_height(), common.signed_header.header.height);
+    assert_eq!(evidence.timestamp(), common.signed_header.header.time);
+    assert_eq!(
+        evidence.conflicting_block().signed_header,
+        conflicting.signed_header
+    );
+
+    for validator in evidence.byzantine_validators() {
+        assert!(common.validators.validator(validator.address).is_some());
+    }
+}
+
+#[test]
+fn equivocation_attack_evidence() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+
+    let common = &tc.primary.lite_blocks[3];
+    let trusted = &tc.primary.lite_blocks[4];
+    let conflicting = &tc.witnesses[0].value.lite_blocks[4];
+
+    assert_eq!(
+        AttackType::classify(&conflicting.signed_header, &trusted.signed_header),
+        AttackType::Equivocation
+    );
+
+    let evidence = attack_evidence(conflicting, trusted, common);
+
+    assert_eq!(
+        evidence.common_height(),
+        trusted.signed_header.header.height
+    );
+    assert!(!evidence.byzantine_validators().is_empty());
+}
+
+/// Make a supervisor for the given multi-peer test case, using the given fault store.
+/// Returns the supervisor along with the ids of the primary and the witnesses.
+fn make_multi_peer_supervisor(
+    tc: TestBisection<LightBlock>,
+    evidence_store: MemoryEvidenceStore,
+    fault_store: MemoryFaultStore,
+) -> (Supervisor, Vec<PeerId>) {
+    let primary = tc.primary.lite_blocks[0].provider;
+
+    let io = MockIo::new(tc.primary.chain_id, tc.primary.lite_blocks);
+    let primary_instance = make_instance(primary, tc.trust_options.clone(), io, tc.now);
+
+    let mut peers = vec![primary];
+    let mut peer_list = PeerList::builder().primary(primary, primary_instance);
+
+    for provider in tc.witnesses.into_iter() {
+        let peer_id = provider.value.lite_blocks[0].provider;
+        let io = MockIo::new(provider.value.chain_id, provider.value.lite_blocks);
+        let instance = make_instance(peer_id, tc.trust_options.clone(), io, tc.now);
+        peer_list = peer_list.witness(peer_id, instance);
+        peers.push(peer_id);
+    }
+
+    let supervisor = Supervisor::new(
+        peer_list.build(),
+        ProdForkDetector::default(),
+        MockEvidenceReporter::new(),
+        evidence_store,
+        fault_store,
+    );
+
+    (supervisor, peers)
+}
+
+#[test]
+fn fork_evidence_is_reported_to_all_peers() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let target_height = tc.height_to_verify.try_into().unwrap();
+
+    let (mut supervisor, peers) =
+        make_multi_peer_supervisor(tc, MemoryEvidenceStore::new(), MemoryFaultStore::new());
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let result = handle.verify_to_target(target_height);
+    assert!(result.is_err());
+
+    let reports = handle.evidence_reports().unwrap();
+
+    // Evidence against both the primary's and the witness's block
+    assert_eq!(reports.len(), 2);
+
+    for report in reports {
+        assert!(report.is_complete());
+
+        let mut acknowledged = report
+            .acknowledged
+            .iter()
+            .map(|(peer, _)| *peer)
+            .collect::<Vec<_>>();
+        acknowledged.sort();
+
+        let mut expected = peers.clone();
+        expected.sort();
+
+        assert_eq!(acknowledged, expected);
+    }
+}
+
+#[test]
+fn pending_evidence_is_not_reported_to_removed_peers() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let trusted_height = tc.trust_options.height.value();
+
+    let primary = tc.primary.lite_blocks[0].provider;
+    let removed_peer: PeerId = "CEFEEDBADFADAD0C0CEEFACADE0ADEADBEEFC0FF".parse().unwrap();
+
+    let evidence = attack_evidence(
+        &tc.witnesses[0].value.lite_blocks[4],
+        &tc.primary.lite_blocks[4],
+        &tc.primary.lite_blocks[0],
+    );
+
+    let mut evidence_store = MemoryEvidenceStore::new();
+    let report = EvidenceReport::new(
+        Evidence::LightClientAttack(Box::new(evidence)),
+        vec![primary, removed_peer],
+    );
+    evidence_store.insert(&report);
+
+    let (mut supervisor, _) =
+        make_multi_peer_supervisor(tc, evidence_store, MemoryFaultStore::new());
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    // Wait for the supervisor to have started, and reported the pending evidence
+    handle.verify_to_target(trusted_height).unwrap();
+
+    let reports = handle.evidence_reports().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert!(reports[0].is_complete());
+    assert_eq!(reports[0].undeliverable, vec![removed_peer]);
+    assert_eq!(
+        reports[0]
+            .acknowledged
+            .iter()
+            .map(|(peer, _)| *peer)
+            .collect::<Vec<_>>(),
+        vec![primary]
+    );
+}
+
+#[test]
+fn forks_are_recorded() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let target_height = tc.height_to_verify.try_into().unwrap();
+
+    let (mut supervisor, peers) =
+        make_multi_peer_supervisor(tc, MemoryEvidenceStore::new(), MemoryFaultStore::new());
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let result = handle.verify_to_target(target_height);
+    assert!(result.is_err());
+
+    let records = handle.faulty_peers().unwrap();
+    assert_eq!(records.len(), 1);
+
+    let record = &records[0];
+    assert_eq!(record.reason, FaultReason::Forked);
+    assert_eq!(record.peer, peers[1]);
+    assert_eq!(record.height, Some(target_height));
+    assert_eq!(record.light_blocks.len(), 3);
+
+    // Verification is refused as long as the fork is on record
+    let result = handle.verify_to_target(target_height);
+    assert!(result.is_err());
+}
+
+#[test]
+fn verification_resumes_once_the_fork_is_cleared() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let target_height = tc.height_to_verify.try_into().unwrap();
+    let trusted_height = tc.trust_options.height.value();
+
+    let (mut supervisor, peers) =
+        make_multi_peer_supervisor(tc, MemoryEvidenceStore::new(), MemoryFaultStore::new());
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let result = handle.verify_to_target(target_height);
+    assert_eq!(
+        result.err().map(|e| e.kind().clone()),
+        Some(ErrorKind::ForkDetected(vec![peers[1]]))
+    );
+
+    let result = handle.verify_to_target(trusted_height);
+    assert_eq!(
+        result.err().map(|e| e.kind().clone()),
+        Some(ErrorKind::ForkDetected(vec![peers[1]]))
+    );
+
+    let cleared = handle.clear_fault(peers[1]).unwrap();
+    assert_eq!(cleared.map(|record| record.peer), Some(peers[1]));
+    assert!(handle.faulty_peers().unwrap().is_empty());
+    assert_eq!(handle.clear_fault(peers[1]).unwrap(), None);
+
+    // The witness agrees with the primary on the trusted block
+    let result = handle.verify_to_target(trusted_height);
+    assert_eq!(result.unwrap().height(), trusted_height);
+}
+
+#[test]
+fn fault_records_are_cleared_when_peers_are_added_again() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let target_height = tc.height_to_verify.try_into().unwrap();
+    let witness = tc.witnesses[0].value.clone();
+
+    let (mut supervisor, peers) = make_multi_peer_supervisor(
+        tc.clone(),
+        MemoryEvidenceStore::new(),
+        MemoryFaultStore::new(),
+    );
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let result = handle.verify_to_target(target_height);
+    assert!(result.is_err());
+    assert_eq!(handle.faulty_peers().unwrap().len(), 1);
+
+    handle.remove_peer(peers[1]).unwrap();
+
+    let io = MockIo::new(witness.chain_id, witness.lite_blocks);
+    let instance = make_uninitialized_instance(peers[1], tc.trust_options, io, tc.now);
+    let address = "tcp://127.0.0.1:26657".parse().unwrap();
+    handle.add_witness(instance, address).unwrap();
+
+    assert!(handle.faulty_peers().unwrap().is_empty());
+}
+
+#[test]
+fn faulty_peers_are_removed_on_restart() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let witness = tc.witnesses[0].value.lite_blocks[0].provider;
+
+    let mut fault_store = MemoryFaultStore::new();
+    fault_store.insert(&FaultRecord {
+        peer: witness,
+        reason: FaultReason::Faulty(ErrorKind::ConflictingTrustedState(1)),
+        height: Some(1),
+        light_blocks: vec![],
+        time: tc.now,
+    });
+
+    let (mut supervisor, peers) =
+        make_multi_peer_supervisor(tc, MemoryEvidenceStore::new(), fault_store);
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let status = handle.latest_status().unwrap();
+    assert_eq!(status.connected_nodes, vec![peers[0]]);
+}
+
+#[test]
+fn transient_faults_do_not_remove_peers_on_restart() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let witness = tc.witnesses[0].value.lite_blocks[0].provider;
+
+    let mut fault_store = MemoryFaultStore::new();
+    fault_store.insert(&FaultRecord {
+        peer: witness,
+        reason: FaultReason::Faulty(ErrorKind::Io(IoError::Timeout(witness))),
+        height: None,
+        light_blocks: vec![],
+        time: tc.now,
+    });
+
+    let (mut supervisor, peers) =
+        make_multi_peer_supervisor(tc, MemoryEvidenceStore::new(), fault_store);
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let status = handle.latest_status().unwrap();
+    assert_eq!(status.connected_nodes, peers);
+}
+
+#[test]
+fn verification_fails_over_when_the_primary_times_out() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let target_height = tc.height_to_verify.try_into().unwrap();
+
+    let primary = tc.primary.lite_blocks[0].provider;
+    let witnesses = [tc.witnesses[0].value.lite_blocks[0].provider, peer_id_at(0)];
+
+    // All peers agree with the primary
+    let make_io = || MockIo::new(tc.primary.chain_id.clone(), tc.primary.lite_blocks.clone());
+
+    let primary_io = make_io();
+    let primary_instance = make_instance(
+        primary,
+        tc.trust_options.clone(),
+        primary_io.clone(),
+        tc.now,
+    );
+
+    let mut peer_list = PeerList::builder().primary(primary, primary_instance);
+    for &witness in &witnesses {
+        let instance = make_instance(witness, tc.trust_options.clone(), make_io(), tc.now);
+        peer_list = peer_list.witness(witness, instance);
+    }
+
+    let mut supervisor = Supervisor::new(
+        peer_list.build(),
+        ProdForkDetector::default(),
+        MockEvidenceReporter::new(),
+        MemoryEvidenceStore::new(),
+        MemoryFaultStore::new(),
+    );
+
+    primary_io.set_timeout(true);
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let result = handle.verify_to_target(target_height);
+    assert_eq!(result.unwrap().height(), target_height);
+
+    // The former primary is kept as a witness, without being deemed faulty
+    let status = handle.latest_status().unwrap();
+    assert_ne!(status.connected_nodes[0], primary);
+    assert!(status.connected_nodes.contains(&primary));
+    assert!(handle.faulty_peers().unwrap().is_empty());
+}
+
+#[test]
+fn witnesses_can_be_added_and_removed_at_runtime() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let target_height = tc.height_to_verify.try_into().unwrap();
+
+    let primary = tc.primary.lite_blocks[0].provider;
+    let witness = tc.witnesses[0].value.lite_blocks[0].provider;
+
+    let io = MockIo::new(tc.primary.chain_id, tc.primary.lite_blocks);
+    let primary_instance = make_instance(primary, tc.trust_options.clone(), io.clone(), tc.now);
+
+    let mut supervisor = Supervisor::new(
+        PeerList::builder()
+            .primary(primary, primary_instance)
+            .build(),
+        ProdForkDetector::default(),
+        MockEvidenceReporter::new(),
+        MemoryEvidenceStore::new(),
+        MemoryFaultStore::new(),
+    );
+
+    let handle = supervisor.handle();
+    std::thread::spawn(|| supervisor.run());
+
+    let result = handle.verify_to_target(target_height);
+    assert_eq!(
+        result.err().map(|e| e.kind().clone()),
+        Some(ErrorKind::NoWitnesses)
+    );
+
+    // A witness which agrees with the primary
+    let witness_instance = make_uninitialized_instance(witness, tc.trust_options, io, tc.now);
+    let address = "tcp://127.0.0.1:26657".parse().unwrap();
+    handle.add_witness(witness_instance, address).unwrap();
+
+    let status = handle.latest_status().unwrap();
+    assert_eq!(status.connected_nodes, vec![primary, witness]);
+
+    let result = handle.verify_to_target(target_height);
+    assert!(result.is_ok());
+
+    handle.remove_peer(witness).unwrap();
+
+    let status = handle.latest_status().unwrap();
+    assert_eq!(status.connected_nodes, vec![primary]);
+
+    let result = handle.remove_peer(witness);
+    assert_eq!(
+        result.err().map(|e| e.kind().clone()),
+        Some(ErrorKind::UnknownPeer(witness))
+    );
+}
+
+#[test]
+fn concurrent_verifications_share_the_outcome() {
+    let tc = load_multi_peer_testcase("conflicting_headers.json");
+    let target_height = tc.height_to_verify.try_into().unwrap();
+
+    let primary = tc.primary.lite_blocks[0].provider;
+    let witness = tc.witnesses[0].value.lite_blocks[0].provider;
+
+    // The witness agrees with the primary
+    let primary_io = MockIo::new(tc.primary.chain_id.clone(), tc.primary.lite_blocks.clone());
+    let witness_io = MockIo::new(tc.primary.chain_id, tc.primary.lite_blocks);
+
+    let primary_instance = make_instance(primary, tc.trust_options.clone(), primary_io, tc.now);
+    let witness_instance = make_instance(witness, tc.trust_options, witness_io.clone(), tc.now);
+
+    let mut supervisor = Supervisor::new(
+        PeerList::builder()
+            .primary(primary, primary_instance)
+            .witness(witness, witness_instance)
+            .build(),
+        ProdForkDetector::default(),
+        MockEvidenceReporter::new(),
+        MemoryEvidenceStore::new(),
+        MemoryFaultStore::new(),
+    );
+
+    let fetches_before = witness_io.fetches();
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| supervisor.handle())
+        .map(|handle| std::thread::spawn(move || handle.verify_to_target(target_height)))
+        .collect();
+
+    // Let the requests queue up before the supervisor starts processing them
+    std::thread::sleep(Duration::from_millis(100));
+    std::thread::spawn(|| supervisor.run());
+
+    for thread in threads {
+        let result = thread.join().unwrap();
+        assert_eq!(result.unwrap().height(), target_height);
+    }
+
+    // Fork detection consulted the witness for a single verification
+    assert_eq!(witness_io.fetches() - fetches_before, 1);
+}
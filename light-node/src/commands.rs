@@ -1,7 +1,7 @@
 //! LightNode Subcommands
 //!
 //! The light client supports the following subcommands:
-//! - `initialize`: subjectively initializes the light node with a given height and hash
+//! - `initialize`: initializes the light node from a genesis file, or subjectively with a given height and hash
 //! - `start`: launches the light client
 //! - `version`: print application version
 //!
@@ -32,7 +32,7 @@ pub enum LightNodeCmd {
 
     /// `intialize` the light node
     #[options(
-        help = "subjectively initialize the light client with given subjective height and validator set hash"
+        help = "initialize the light client from a genesis file, or subjectively with given subjective height and header hash"
     )]
     Initialize(InitCmd),
 
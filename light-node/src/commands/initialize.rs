@@ -4,6 +4,8 @@ use crate::application::app_config;
 use crate::config::LightClientConfig;
 
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use abscissa_core::status_err;
 use abscissa_core::status_warn;
@@ -12,15 +14,18 @@ use abscissa_core::Options;
 use abscissa_core::Runnable;
 
 use tendermint::hash;
-use tendermint::lite::Header;
-use tendermint::Hash;
+use tendermint::{chain, Genesis, Hash};
 
+use tendermint_light_client::components::clock::SystemClock;
 use tendermint_light_client::components::io::{AtHeight, Io, ProdIo};
-use tendermint_light_client::operations::ProdHasher;
-use tendermint_light_client::predicates::{ProdPredicates, VerificationPredicates};
+use tendermint_light_client::components::scheduler;
+use tendermint_light_client::components::verifier::ProdVerifier;
+use tendermint_light_client::light_client::{self, LightClient};
+use tendermint_light_client::state::State;
 use tendermint_light_client::store::sled::SledStore;
 use tendermint_light_client::store::LightStore;
-use tendermint_light_client::types::Status;
+use tendermint_light_client::supervisor::Instance;
+use tendermint_light_client::types::{Height, PeerId};
 
 /// `initialize` subcommand
 #[derive(Command, Debug, Default, Options)]
@@ -29,19 +34,20 @@ pub struct InitCmd {
         free,
         help = "subjective height of the initial trusted state to initialize the node with"
     )]
-    pub height: u64,
+    pub height: Option<u64>,
 
     #[options(
         free,
         help = "hash of the initial subjectively trusted header to initialize the node with"
     )]
-    pub header_hash: String,
+    pub header_hash: Option<String>,
+
+    #[options(help = "path to a genesis.json file to initialize the node from")]
+    pub genesis: Option<PathBuf>,
 }
 
 impl Runnable for InitCmd {
     fn run(&self) {
-        let subjective_header_hash =
-            Hash::from_hex_upper(hash::Algorithm::Sha256, &self.header_hash).unwrap();
         let app_cfg = app_config();
 
         let lc = app_cfg.light_clients.first().unwrap();
@@ -51,29 +57,95 @@ impl Runnable for InitCmd {
 
         let io = ProdIo::new(peer_map, Some(app_cfg.rpc_config.request_timeout));
 
-        initialize_subjectively(self.height, subjective_header_hash, &lc, &io);
+        let result = match (&self.genesis, self.height, &self.header_hash) {
+            (Some(genesis_path), None, None) => {
+                let genesis = load_genesis(genesis_path);
+
+                if let Some(chain_id) = app_cfg.chain_id {
+                    if genesis.chain_id != chain_id {
+                        status_err!(
+                            "genesis file {} is for chain {} instead of {}",
+                            genesis_path.display(),
+                            genesis.chain_id,
+                            chain_id
+                        );
+                        std::process::exit(1);
+                    }
+                }
+
+                let options = app_cfg.light_client_options(genesis.chain_id);
+                make_instance(&lc, io, options).initialize_from_genesis(&genesis)
+            }
+            (None, Some(height), Some(header_hash)) => {
+                let subjective_header_hash =
+                    Hash::from_hex_upper(hash::Algorithm::Sha256, header_hash).unwrap_or_else(
+                        |e| {
+                            status_err!("invalid header hash {}: {}", header_hash, e);
+                            std::process::exit(1);
+                        },
+                    );
+
+                let chain_id = app_cfg
+                    .chain_id
+                    .unwrap_or_else(|| fetch_chain_id(&io, lc.peer_id, height));
+
+                let options = app_cfg.light_client_options(chain_id);
+                make_instance(&lc, io, options)
+                    .initialize_from_trust_options(height, subjective_header_hash)
+            }
+            _ => {
+                status_err!("expected either `--genesis <file>` or `<height> <header-hash>`");
+                std::process::exit(1);
+            }
+        };
+
+        if let Err(e) = result {
+            status_err!("could not initialize the light client: {}", e);
+            std::process::exit(1);
+        }
     }
 }
 
-// TODO(ismail): sth along these lines should live in the light-client crate / library
-// instead of here.
-// TODO(ismail): additionally here and everywhere else, we should return errors
-// instead of std::process::exit because no destructors will be run.
-fn initialize_subjectively(
-    height: u64,
-    subjective_header_hash: Hash,
+fn load_genesis(genesis_path: &Path) -> Genesis {
+    let genesis_json = fs::read_to_string(genesis_path).unwrap_or_else(|e| {
+        status_err!("could not read {}: {}", genesis_path.display(), e);
+        std::process::exit(1);
+    });
+
+    serde_json::from_str(&genesis_json).unwrap_or_else(|e| {
+        status_err!("could not parse {}: {}", genesis_path.display(), e);
+        std::process::exit(1);
+    })
+}
+
+/// Fetch the chain id of the header at the given height from the given peer.
+///
+/// The chain id is then authenticated along with the rest of the header, whose
+/// hash must match the subjectively trusted one.
+fn fetch_chain_id(io: &ProdIo, peer_id: PeerId, height: Height) -> chain::Id {
+    let light_block = io
+        .fetch_light_block(peer_id, AtHeight::At(height))
+        .unwrap_or_else(|e| {
+            status_err!("could not fetch the header at height {}: {}", height, e);
+            std::process::exit(1);
+        });
+
+    light_block.signed_header.header.chain_id
+}
+
+fn make_instance(
     l_conf: &LightClientConfig,
-    io: &ProdIo,
-) {
+    io: ProdIo,
+    options: light_client::Options,
+) -> Instance {
     let db = sled::open(l_conf.db_path.clone()).unwrap_or_else(|e| {
         status_err!("could not open database: {}", e);
         std::process::exit(1);
     });
 
-    let mut light_store = SledStore::new(db);
+    let light_store = SledStore::new(db);
 
-    if light_store.latest_trusted_or_verified().is_some() {
-        let lb = light_store.latest_trusted_or_verified().unwrap();
+    if let Some(lb) = light_store.latest_trusted_or_verified() {
         status_warn!(
             "already existing trusted or verified state of height {} in database: {:?}",
             lb.signed_header.header.height,
@@ -81,30 +153,19 @@ fn initialize_subjectively(
         );
     }
 
-    let trusted_state = io
-        .fetch_light_block(l_conf.peer_id, AtHeight::At(height))
-        .unwrap_or_else(|e| {
-            status_err!("could not retrieve trusted header: {}", e);
-            std::process::exit(1);
-        });
-
-    let predicates = ProdPredicates;
-    let hasher = ProdHasher;
-    if let Err(err) = predicates.validator_sets_match(&trusted_state, &hasher) {
-        status_err!("invalid light block: {}", err);
-        std::process::exit(1);
-    }
-    // TODO(ismail): actually verify more predicates of light block before storing!?
-    let got_header_hash = trusted_state.signed_header.header.hash();
-    if got_header_hash != subjective_header_hash {
-        status_err!(
-            "received LightBlock's header hash: {} does not match the subjective hash: {}",
-            got_header_hash,
-            subjective_header_hash
-        );
-        std::process::exit(1);
-    }
-    // TODO(liamsi): it is unclear if this should be Trusted or only Verified
-    //  - update the spec first and then use library method instead of this:
-    light_store.insert(trusted_state, Status::Verified);
+    let state = State {
+        light_store: Box::new(light_store),
+        verification_trace: HashMap::new(),
+    };
+
+    let light_client = LightClient::new(
+        l_conf.peer_id,
+        options,
+        SystemClock,
+        scheduler::basic_bisecting_schedule,
+        ProdVerifier::default(),
+        io,
+    );
+
+    Instance::new(light_client, state)
 }
@@ -5,7 +5,7 @@ use std::process;
 use crate::application::{app_config, APPLICATION};
 use crate::config::{LightClientConfig, LightNodeConfig};
 use crate::rpc;
-use crate::rpc::Server;
+use crate::rpc::{AdminServer, Server};
 
 use abscissa_core::config;
 use abscissa_core::path::PathBuf;
@@ -18,23 +18,27 @@ use abscissa_core::Runnable;
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::ops::Deref;
 use std::time::Duration;
 
+use tendermint::{chain, net};
+
 use tendermint_light_client::components::clock::SystemClock;
 use tendermint_light_client::components::io::ProdIo;
 use tendermint_light_client::components::scheduler;
 use tendermint_light_client::components::verifier::ProdVerifier;
+use tendermint_light_client::discovery::{self, Discovery};
 use tendermint_light_client::evidence::ProdEvidenceReporter;
 use tendermint_light_client::fork_detector::ProdForkDetector;
 use tendermint_light_client::light_client;
 use tendermint_light_client::light_client::LightClient;
 use tendermint_light_client::peer_list::{PeerList, PeerListBuilder};
 use tendermint_light_client::state::State;
-use tendermint_light_client::store::sled::SledStore;
+use tendermint_light_client::store::memory::MemoryStore;
+use tendermint_light_client::store::sled::{SledEvidenceStore, SledFaultStore, SledStore};
 use tendermint_light_client::store::LightStore;
 use tendermint_light_client::supervisor::Handle;
 use tendermint_light_client::supervisor::{Instance, Supervisor};
+use tendermint_light_client::types::PeerId;
 
 /// `start` subcommand
 ///
@@ -57,12 +61,22 @@ impl Runnable for StartCmd {
     /// Start the application.
     fn run(&self) {
         if let Err(err) = abscissa_tokio::run(&APPLICATION, async {
-            StartCmd::assert_init_was_run();
-            let mut supervisor = self.construct_supervisor();
+            let chain_id = StartCmd::trusted_chain_id();
+            let mut supervisor = self.construct_supervisor(chain_id);
 
             let rpc_handler = supervisor.handle();
             StartCmd::start_rpc_server(rpc_handler);
 
+            if let Some(laddr) = app_config().rpc_config.admin_listen_addr {
+                let admin_handler = supervisor.handle();
+                StartCmd::start_admin_rpc_server(admin_handler, laddr, chain_id);
+            }
+
+            if let Some(options) = app_config().discovery.clone() {
+                let discovery_handler = supervisor.handle();
+                StartCmd::start_discovery(discovery_handler, options, chain_id);
+            }
+
             let handle = supervisor.handle();
             std::thread::spawn(|| supervisor.run());
 
@@ -101,7 +115,9 @@ impl config::Override<LightNodeConfig> for StartCmd {
     }
 }
 impl StartCmd {
-    fn assert_init_was_run() {
+    /// Get the id of the chain of the primary's trusted state, which must match
+    /// the configured chain id, if any.
+    fn trusted_chain_id() -> chain::Id {
         // TODO(liamsi): handle errors properly:
         let primary_db_path = app_config().light_clients.first().unwrap().db_path.clone();
         let db = sled::open(primary_db_path).unwrap_or_else(|e| {
@@ -111,10 +127,25 @@ impl StartCmd {
 
         let primary_store = SledStore::new(db);
 
-        if primary_store.latest_trusted_or_verified().is_none() {
+        let trusted = primary_store.latest_trusted_or_verified().unwrap_or_else(|| {
             status_err!("no trusted or verified state in store for primary, please initialize with the `initialize` subcommand first");
             std::process::exit(1);
+        });
+
+        let chain_id = trusted.signed_header.header.chain_id;
+
+        if let Some(configured) = app_config().chain_id {
+            if configured != chain_id {
+                status_err!(
+                    "trusted state of the primary is for chain {} instead of {}",
+                    chain_id,
+                    configured
+                );
+                std::process::exit(1);
+            }
         }
+
+        chain_id
     }
     // TODO: this should do proper error handling, be gerneralized
     // then moved to to the light-client crate.
@@ -148,6 +179,68 @@ impl StartCmd {
         Instance::new(light_client, state)
     }
 
+    /// Make the instance of a peer added at runtime via the RPC server.
+    ///
+    /// The instance keeps its state in memory, and is initialized by the supervisor
+    /// with the latest block trusted by the primary.
+    fn make_runtime_instance(
+        peer_id: PeerId,
+        address: net::Address,
+        chain_id: chain::Id,
+    ) -> Instance {
+        let mut peer_map = HashMap::new();
+        peer_map.insert(peer_id, address);
+
+        let io = ProdIo::new(peer_map, Some(app_config().rpc_config.request_timeout));
+        let options = app_config().light_client_options(chain_id);
+
+        let state = State {
+            light_store: Box::new(MemoryStore::new()),
+            verification_trace: HashMap::new(),
+        };
+
+        let verifier = ProdVerifier::default();
+        let clock = SystemClock;
+        let scheduler = scheduler::basic_bisecting_schedule;
+
+        let light_client = LightClient::new(peer_id, options, clock, scheduler, verifier, io);
+
+        Instance::new(light_client, state)
+    }
+
+    /// Discover full nodes among the peers of the primary in a separate thread,
+    /// and add them as full nodes to the supervisor's peer list.
+    fn start_discovery<H>(h: H, options: discovery::Options, chain_id: chain::Id)
+    where
+        H: Handle + Send + 'static,
+    {
+        let config = app_config();
+        let primary_address = config.light_clients.first().unwrap().address.clone();
+        let known_peers: Vec<PeerId> = config.light_clients.iter().map(|lc| lc.peer_id).collect();
+
+        let discovery = Discovery::new(chain_id, options, Some(config.rpc_config.request_timeout));
+
+        std::thread::spawn(move || {
+            let discovered = match discovery.discover(primary_address, &known_peers) {
+                Ok(discovered) => discovered,
+                Err(e) => {
+                    status_err!("peer discovery failed: {}", e);
+                    return;
+                }
+            };
+
+            for peer in discovered {
+                let instance =
+                    StartCmd::make_runtime_instance(peer.peer_id, peer.address.clone(), chain_id);
+
+                match h.add_full_node(instance, peer.address) {
+                    Ok(()) => status_info!("discovered full node:", peer.peer_id.to_string()),
+                    Err(e) => status_err!("could not add full node {}: {}", peer.peer_id, e),
+                }
+            }
+        });
+    }
+
     fn start_rpc_server<H>(h: H)
     where
         H: Handle + Send + Sync + 'static,
@@ -158,10 +251,31 @@ impl StartCmd {
         std::thread::spawn(move || rpc::run(server, &laddr.to_string()));
         status_info!("started RPC server:", laddr.to_string());
     }
+
+    /// Start the admin RPC server, which adds and removes peers, on the given
+    /// loopback address.
+    fn start_admin_rpc_server<H>(h: H, laddr: SocketAddr, chain_id: chain::Id)
+    where
+        H: Handle + Send + Sync + 'static,
+    {
+        if !laddr.ip().is_loopback() {
+            status_err!(
+                "admin RPC server must listen on a loopback address, not {}",
+                laddr
+            );
+            process::exit(1);
+        }
+
+        let server = AdminServer::new(h, move |peer_id, address| {
+            StartCmd::make_runtime_instance(peer_id, address, chain_id)
+        });
+        std::thread::spawn(move || rpc::run_admin(server, &laddr.to_string()));
+        status_info!("started admin RPC server:", laddr.to_string());
+    }
 }
 
 impl StartCmd {
-    fn construct_supervisor(&self) -> Supervisor {
+    fn construct_supervisor(&self, chain_id: chain::Id) -> Supervisor {
         // TODO(ismail): we need to verify the addr <-> peerId mappings somewhere!
         let mut peer_map = HashMap::new();
         for light_conf in &app_config().light_clients {
@@ -171,8 +285,7 @@ impl StartCmd {
             peer_map.clone(),
             Some(app_config().rpc_config.request_timeout),
         );
-        let conf = app_config().deref().clone();
-        let options: light_client::Options = conf.into();
+        let options = app_config().light_client_options(chain_id);
 
         let mut peer_list: PeerListBuilder<Instance> = PeerList::builder();
         for (i, light_conf) in app_config().light_clients.iter().enumerate() {
@@ -186,10 +299,17 @@ impl StartCmd {
         }
         let peer_list = peer_list.build();
 
+        let evidence_db = sled::open(&app_config().evidence_db_path).unwrap_or_else(|e| {
+            status_err!("could not open supervisor database: {}", e);
+            std::process::exit(1);
+        });
+
         Supervisor::new(
             peer_list,
             ProdForkDetector::default(),
-            ProdEvidenceReporter::new(peer_map),
+            ProdEvidenceReporter::new(peer_map, Some(app_config().rpc_config.request_timeout)),
+            SledEvidenceStore::new(evidence_db.clone()),
+            SledFaultStore::new(evidence_db),
         )
     }
 }
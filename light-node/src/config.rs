@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::time::Duration;
 
+use tendermint::chain;
+use tendermint_light_client::discovery;
 use tendermint_light_client::light_client;
 use tendermint_light_client::types::{PeerId, TrustThreshold};
 
@@ -16,6 +18,11 @@ use tendermint_light_client::types::{PeerId, TrustThreshold};
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct LightNodeConfig {
+    /// Identifier of the chain the light node is following. If not set, this is
+    /// the chain of the initial trusted state, ie. of the genesis file or of the
+    /// subjectively trusted header given to the `initialize` subcommand.
+    #[serde(default)]
+    pub chain_id: Option<chain::Id>,
     /// The fraction of the total voting power of a known
     /// and trusted validator set is sufficient for a commit to be
     /// accepted going forward.
@@ -28,6 +35,15 @@ pub struct LightNodeConfig {
     /// RPC related config parameters.
     pub rpc_config: RpcConfig,
 
+    /// The data base folder for the store of the evidence to report,
+    /// and of the forks and faulty peers detected by the supervisor.
+    pub evidence_db_path: PathBuf,
+
+    /// Options for discovering additional full nodes among the peers of the primary,
+    /// which are added as full nodes to the peer list. Discovery is disabled if not set.
+    #[serde(default)]
+    pub discovery: Option<discovery::Options>,
+
     // TODO "now" should probably always be passed in as `Time::now()`
     /// The actual light client instances' configuration.
     /// Note: the first config will be used in the subjectively initialize
@@ -55,6 +71,10 @@ pub struct LightClientConfig {
 pub struct RpcConfig {
     /// The address the RPC server will serve.
     pub listen_addr: SocketAddr,
+    /// The loopback address the admin RPC server will serve, whose methods add and
+    /// remove peers. The admin server is disabled if not set.
+    #[serde(default)]
+    pub admin_listen_addr: Option<SocketAddr>,
     /// The duration after which any RPC request to tendermint node will time out.
     pub request_timeout: Duration,
 }
@@ -76,6 +96,7 @@ impl Default for LightClientConfig {
 impl Default for LightNodeConfig {
     fn default() -> Self {
         Self {
+            chain_id: None,
             trusting_period: Duration::from_secs(864_000), // 60*60*24*10
             trust_threshold: TrustThreshold {
                 numerator: 1,
@@ -84,8 +105,11 @@ impl Default for LightNodeConfig {
             clock_drift: Duration::from_secs(1),
             rpc_config: RpcConfig {
                 listen_addr: "127.0.0.1:8888".parse().unwrap(),
+                admin_listen_addr: None,
                 request_timeout: Duration::from_secs(60),
             },
+            evidence_db_path: "./lightstore/evidence".parse().unwrap(),
+            discovery: None,
             // TODO(ismail): need at least 2 peers for a proper init
             // otherwise the light node will complain on `start` with `no witness left`
             light_clients: vec![LightClientConfig::default()],
@@ -93,12 +117,14 @@ impl Default for LightNodeConfig {
     }
 }
 
-impl From<LightNodeConfig> for light_client::Options {
-    fn from(lnc: LightNodeConfig) -> Self {
-        Self {
-            trust_threshold: lnc.trust_threshold,
-            trusting_period: lnc.trusting_period,
-            clock_drift: lnc.clock_drift,
+impl LightNodeConfig {
+    /// Options of the light client instances following the given chain.
+    pub fn light_client_options(&self, chain_id: chain::Id) -> light_client::Options {
+        light_client::Options {
+            trust_threshold: self.trust_threshold,
+            trusting_period: self.trusting_period,
+            clock_drift: self.clock_drift,
+            chain_id,
         }
     }
 }
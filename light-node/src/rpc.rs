@@ -5,7 +5,7 @@ use tendermint_light_client::supervisor::Handle;
 
 use crate::error;
 
-pub use sealed::{Client, Rpc, Server};
+pub use sealed::{AdminClient, AdminRpc, AdminServer, Client, Rpc, Server};
 
 /// Run the given [`Server`] on the given address and blocks until closed.
 ///
@@ -32,14 +32,56 @@ where
     Ok(())
 }
 
+/// Run the given [`AdminServer`] on the given loopback address and blocks until closed.
+///
+/// As the admin methods change the peers of the light node, the server refuses to listen
+/// on any other than a loopback address. It does not allow any cross-origin request, and
+/// only accepts requests made to the address it is bound to, so that web pages cannot
+/// reach it either directly or through DNS rebinding.
+pub fn run_admin<H>(server: AdminServer<H>, addr: &str) -> Result<(), error::Error>
+where
+    H: Handle + Send + Sync + 'static,
+{
+    let addr: std::net::SocketAddr = addr.parse().map_err(error::Kind::from)?;
+
+    if !addr.ip().is_loopback() {
+        return Err(error::Kind::Config
+            .context(format!(
+                "admin RPC server must listen on a loopback address, not {}",
+                addr
+            ))
+            .into());
+    }
+
+    let mut io = IoHandler::new();
+    io.extend_with(server.to_delegate());
+
+    let srv = ServerBuilder::new(io)
+        .cors(DomainsValidation::AllowOnly(vec![]))
+        .allow_only_bind_host()
+        .start_http(&addr)
+        .map_err(|e| error::Kind::Io.context(e))?;
+
+    srv.wait();
+
+    Ok(())
+}
+
 mod sealed {
     use jsonrpc_core::futures::future::{self, Future, FutureResult};
     use jsonrpc_core::types::Error;
     use jsonrpc_derive::rpc;
 
-    use tendermint_light_client::supervisor::Handle;
+    use abscissa_core::status_warn;
+
+    use tendermint::net;
+
+    use tendermint_light_client::evidence::EvidenceReport;
+    use tendermint_light_client::supervisor::{Handle, Instance};
+    use tendermint_light_client::types::FaultRecord;
     use tendermint_light_client::types::LatestStatus;
     use tendermint_light_client::types::LightBlock;
+    use tendermint_light_client::types::PeerId;
 
     #[rpc]
     pub trait Rpc {
@@ -50,10 +92,44 @@ mod sealed {
         /// Returns the latest status.
         #[rpc(name = "status")]
         fn status(&self) -> FutureResult<LatestStatus, Error>;
+
+        /// Returns the reported evidence, along with the peers which acknowledged it.
+        #[rpc(name = "evidence")]
+        fn evidence(&self) -> FutureResult<Vec<EvidenceReport>, Error>;
+
+        /// Returns the records of the forks and faulty peers detected by the light node.
+        #[rpc(name = "faulty_peers")]
+        fn faulty_peers(&self) -> FutureResult<Vec<FaultRecord>, Error>;
+    }
+
+    /// Methods changing the peers of the light node and their fault records, served by
+    /// the admin server only.
+    #[rpc]
+    pub trait AdminRpc {
+        /// Adds a witness with the given peer id and address.
+        #[rpc(name = "add_witness")]
+        fn add_witness(&self, peer_id: PeerId, address: net::Address) -> FutureResult<(), Error>;
+
+        /// Adds a full node with the given peer id and address.
+        #[rpc(name = "add_full_node")]
+        fn add_full_node(&self, peer_id: PeerId, address: net::Address) -> FutureResult<(), Error>;
+
+        /// Removes the peer with the given id.
+        #[rpc(name = "remove_peer")]
+        fn remove_peer(&self, peer_id: PeerId) -> FutureResult<(), Error>;
+
+        /// Clears the fault record of the peer with the given id, and returns it.
+        #[rpc(name = "clear_fault")]
+        fn clear_fault(&self, peer_id: PeerId) -> FutureResult<Option<FaultRecord>, Error>;
     }
 
+    pub use self::rpc_impl_AdminRpc::gen_client::Client as AdminClient;
     pub use self::rpc_impl_Rpc::gen_client::Client;
 
+    /// Constructs the light client instance of a peer added at runtime,
+    /// given its peer id and address.
+    pub type MakeInstance = Box<dyn Fn(PeerId, net::Address) -> Instance + Send + Sync>;
+
     pub struct Server<H>
     where
         H: Handle + Send + Sync,
@@ -70,32 +146,95 @@ mod sealed {
         }
     }
 
+    pub struct AdminServer<H>
+    where
+        H: Handle + Send + Sync,
+    {
+        handle: H,
+        make_instance: MakeInstance,
+    }
+
+    impl<H> AdminServer<H>
+    where
+        H: Handle + Send + Sync,
+    {
+        pub fn new(
+            handle: H,
+            make_instance: impl Fn(PeerId, net::Address) -> Instance + Send + Sync + 'static,
+        ) -> Self {
+            Self {
+                handle,
+                make_instance: Box::new(make_instance),
+            }
+        }
+    }
+
     impl<H> Rpc for Server<H>
     where
         H: Handle + Send + Sync + 'static,
     {
         fn state(&self) -> FutureResult<Option<LightBlock>, Error> {
-            let res = self.handle.latest_trusted().map_err(|e| {
-                let mut err = Error::internal_error();
-                err.message = e.to_string();
-                err.data = serde_json::to_value(e.kind()).ok();
-                err
-            });
-
-            future::result(res)
+            respond(self.handle.latest_trusted())
         }
 
         fn status(&self) -> FutureResult<LatestStatus, Error> {
-            let res = self.handle.latest_status().map_err(|e| {
-                let mut err = Error::internal_error();
-                err.message = e.to_string();
-                err.data = serde_json::to_value(e.kind()).ok();
-                err
-            });
-
-            future::result(res)
+            respond(self.handle.latest_status())
+        }
+
+        fn evidence(&self) -> FutureResult<Vec<EvidenceReport>, Error> {
+            respond(self.handle.evidence_reports())
+        }
+
+        fn faulty_peers(&self) -> FutureResult<Vec<FaultRecord>, Error> {
+            respond(self.handle.faulty_peers())
         }
     }
+
+    impl<H> AdminRpc for AdminServer<H>
+    where
+        H: Handle + Send + Sync + 'static,
+    {
+        fn add_witness(&self, peer_id: PeerId, address: net::Address) -> FutureResult<(), Error> {
+            let instance = (self.make_instance)(peer_id, address.clone());
+            respond(self.handle.add_witness(instance, address))
+        }
+
+        fn add_full_node(&self, peer_id: PeerId, address: net::Address) -> FutureResult<(), Error> {
+            let instance = (self.make_instance)(peer_id, address.clone());
+            respond(self.handle.add_full_node(instance, address))
+        }
+
+        fn remove_peer(&self, peer_id: PeerId) -> FutureResult<(), Error> {
+            respond(self.handle.remove_peer(peer_id))
+        }
+
+        fn clear_fault(&self, peer_id: PeerId) -> FutureResult<Option<FaultRecord>, Error> {
+            let res = self.handle.clear_fault(peer_id);
+
+            // Keep the cleared record in the logs, as it is gone from the store
+            if let Ok(Some(record)) = &res {
+                let record = serde_json::to_string(record).unwrap_or_default();
+                status_warn!("cleared fault record of peer {}: {}", peer_id, record);
+            }
+
+            respond(res)
+        }
+    }
+
+    /// Turn the result of a request to the supervisor into a response, where errors
+    /// carry their kind as data.
+    fn respond<T>(
+        res: Result<T, tendermint_light_client::errors::Error>,
+    ) -> FutureResult<T, Error> {
+        let res = res.map_err(|e| {
+            let mut err = Error::internal_error();
+            err.message = e.to_string();
+            err.data = serde_json::to_value(e.kind()).ok();
+            err
+        });
+
+        future::result(res)
+    }
 }
 
 #[cfg(test)]
@@ -106,12 +245,24 @@ mod test {
     use jsonrpc_core_client::transports::local;
     use pretty_assertions::assert_eq;
 
-    use tendermint_light_client::errors::Error;
-    use tendermint_light_client::supervisor::Handle;
+    use jsonrpc_core_client::RpcError;
+    use std::time::Duration;
+
+    use tendermint::net;
+    use tendermint_light_client::components::{scheduler, verifier::ProdVerifier};
+    use tendermint_light_client::errors::{Error, ErrorKind};
+    use tendermint_light_client::evidence::EvidenceReport;
+    use tendermint_light_client::light_client::{LightClient, Options};
+    use tendermint_light_client::state::State;
+    use tendermint_light_client::store::memory::MemoryStore;
+    use tendermint_light_client::supervisor::{Handle, Instance};
+    use tendermint_light_client::tests::{MockClock, MockIo};
+    use tendermint_light_client::types::FaultRecord;
     use tendermint_light_client::types::LatestStatus;
     use tendermint_light_client::types::LightBlock;
+    use tendermint_light_client::types::PeerId;
 
-    use super::{Client, Rpc as _, Server};
+    use super::{AdminClient, AdminRpc as _, AdminServer, Client, Rpc as _, Server};
 
     #[tokio::test]
     async fn state() {
@@ -143,6 +294,108 @@ mod test {
         assert_eq!(have, want);
     }
 
+    #[tokio::test]
+    async fn evidence() {
+        let server = Server::new(MockHandle {});
+        let fut = {
+            let mut io = IoHandler::new();
+            io.extend_with(server.to_delegate());
+            let (client, server) = local::connect::<Client, _, _>(io);
+            client.evidence().join(server)
+        };
+        let (have, _) = fut.compat().await.unwrap();
+
+        assert!(have.is_empty());
+    }
+
+    #[tokio::test]
+    async fn faulty_peers() {
+        let server = Server::new(MockHandle {});
+        let fut = {
+            let mut io = IoHandler::new();
+            io.extend_with(server.to_delegate());
+            let (client, server) = local::connect::<Client, _, _>(io);
+            client.faulty_peers().join(server)
+        };
+        let (have, _) = fut.compat().await.unwrap();
+
+        assert!(have.is_empty());
+    }
+
+    #[tokio::test]
+    async fn add_witness() {
+        let server = AdminServer::new(MockHandle {}, make_instance);
+        let fut = {
+            let mut io = IoHandler::new();
+            io.extend_with(server.to_delegate());
+            let (client, server) = local::connect::<AdminClient, _, _>(io);
+            client
+                .add_witness(PEER_ID.parse().unwrap(), ADDRESS.parse().unwrap())
+                .join(server)
+        };
+
+        fut.compat().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn add_full_node() {
+        let server = AdminServer::new(MockHandle {}, make_instance);
+        let fut = {
+            let mut io = IoHandler::new();
+            io.extend_with(server.to_delegate());
+            let (client, server) = local::connect::<AdminClient, _, _>(io);
+            client
+                .add_full_node(PEER_ID.parse().unwrap(), ADDRESS.parse().unwrap())
+                .join(server)
+        };
+
+        fut.compat().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn remove_peer() {
+        let server = AdminServer::new(MockHandle {}, make_instance);
+        let fut = {
+            let mut io = IoHandler::new();
+            io.extend_with(server.to_delegate());
+            let (client, server) = local::connect::<AdminClient, _, _>(io);
+            client
+                .remove_peer(UNKNOWN_PEER_ID.parse().unwrap())
+                .join(server)
+        };
+
+        match fut.compat().await {
+            Err(RpcError::JsonRpcError(err)) => {
+                let kind = ErrorKind::UnknownPeer(UNKNOWN_PEER_ID.parse().unwrap());
+                assert_eq!(err.data, serde_json::to_value(kind).ok());
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[tokio::test]
+    async fn clear_fault() {
+        let server = AdminServer::new(MockHandle {}, make_instance);
+        let fut = {
+            let mut io = IoHandler::new();
+            io.extend_with(server.to_delegate());
+            let (client, server) = local::connect::<AdminClient, _, _>(io);
+            client.clear_fault(PEER_ID.parse().unwrap()).join(server)
+        };
+
+        let (have, _) = fut.compat().await.unwrap();
+
+        assert_eq!(have, None);
+    }
+
+    #[test]
+    fn admin_server_refuses_non_loopback_address() {
+        let server = AdminServer::new(MockHandle {}, make_instance);
+
+        let err = super::run_admin(server, "0.0.0.0:8889").unwrap_err();
+        assert!(matches!(err.kind(), crate::error::Kind::Config));
+    }
+
     struct MockHandle;
 
     impl Handle for MockHandle {
@@ -156,8 +409,61 @@ mod test {
 
             Ok(status)
         }
+        fn evidence_reports(&self) -> Result<Vec<EvidenceReport>, Error> {
+            Ok(vec![])
+        }
+        fn faulty_peers(&self) -> Result<Vec<FaultRecord>, Error> {
+            Ok(vec![])
+        }
+        fn add_witness(&self, instance: Instance, _address: net::Address) -> Result<(), Error> {
+            assert_eq!(instance.light_client.peer, PEER_ID.parse().unwrap());
+
+            Ok(())
+        }
+        fn add_full_node(&self, instance: Instance, _address: net::Address) -> Result<(), Error> {
+            assert_eq!(instance.light_client.peer, PEER_ID.parse().unwrap());
+
+            Ok(())
+        }
+        fn remove_peer(&self, peer_id: PeerId) -> Result<(), Error> {
+            Err(ErrorKind::UnknownPeer(peer_id).into())
+        }
+        fn clear_fault(&self, _peer_id: PeerId) -> Result<Option<FaultRecord>, Error> {
+            Ok(None)
+        }
     }
 
+    /// Make the instance of a peer added at runtime, which serves `LIGHTBLOCK_JSON`.
+    fn make_instance(peer_id: PeerId, _address: net::Address) -> Instance {
+        let light_block: LightBlock = serde_json::from_str(LIGHTBLOCK_JSON).unwrap();
+        let header = &light_block.signed_header.header;
+
+        let options = Options {
+            trust_threshold: Default::default(),
+            trusting_period: Duration::from_secs(60 * 60 * 24 * 14),
+            clock_drift: Duration::from_secs(10),
+            chain_id: header.chain_id.clone(),
+        };
+
+        let clock = MockClock { now: header.time };
+        let io = MockIo::new(header.chain_id.to_string(), vec![light_block]);
+
+        let light_client = LightClient::new(
+            peer_id,
+            options,
+            clock,
+            scheduler::basic_bisecting_schedule,
+            ProdVerifier::default(),
+            io,
+        );
+
+        Instance::new(light_client, State::new(MemoryStore::new()))
+    }
+
+    const PEER_ID: &str = "CEFEEDBADFADAD0C0CEEFACADE0ADEADBEEFC0FF";
+    const UNKNOWN_PEER_ID: &str = "BADFADAD0BEFEEDC0C0ADEADBEEFC0FFEEFACADE";
+    const ADDRESS: &str = "tcp://127.0.0.1:26657";
+
     const LIGHTBLOCK_JSON: &str = r#"
 {
     "signed_header": {
@@ -0,0 +1,217 @@
+//! JSONRPC batch requests
+
+use serde_json::Value;
+use std::{collections::BTreeMap, marker::PhantomData};
+
+use super::{request, response, Error, Id, Request, Response};
+
+/// A batch of (possibly heterogeneous) JSONRPC requests, sent to the node as
+/// a single JSON array.
+///
+/// Adding a request to the batch yields a `Handle`, which is later used to
+/// extract the matching typed response from the `Responses` to the batch.
+#[derive(Clone, Debug)]
+pub struct Batch {
+    requests: Vec<Value>,
+
+    /// Are all requests in the batch idempotent?
+    idempotent: bool,
+}
+
+impl Batch {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        Self {
+            requests: vec![],
+            idempotent: true,
+        }
+    }
+
+    /// Add a request to the batch
+    pub fn add<R>(&mut self, request: R) -> Handle<R::Response>
+    where
+        R: Request,
+    {
+        self.idempotent &= request.method().is_idempotent();
+
+        let wrapper = request::Wrapper::new(request);
+        let id = wrapper.id().clone();
+
+        self.requests
+            .push(serde_json::to_value(&wrapper).expect("request serialization failed"));
+
+        Handle {
+            id,
+            response: PhantomData,
+        }
+    }
+
+    /// Number of requests in the batch
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Is this batch empty?
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Can this batch safely be sent more than once?
+    pub fn is_idempotent(&self) -> bool {
+        self.idempotent
+    }
+
+    /// Serialize this batch as JSON
+    pub fn into_json(self) -> String {
+        serde_json::to_string_pretty(&self.requests).unwrap()
+    }
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Handle to a request added to a `Batch`, used to retrieve its response.
+#[derive(Debug)]
+pub struct Handle<R> {
+    /// Identifier of the request
+    id: Id,
+
+    /// Type of the response to the request
+    response: PhantomData<fn() -> R>,
+}
+
+impl<R> Handle<R> {
+    /// Get the JSONRPC ID of the request
+    pub fn id(&self) -> &Id {
+        &self.id
+    }
+}
+
+/// Responses to a `Batch`, indexed by request ID
+#[derive(Clone, Debug)]
+pub struct Responses {
+    responses: BTreeMap<Id, Value>,
+}
+
+impl Responses {
+    /// Parse the JSONRPC responses to a batch from a JSON string
+    pub fn from_string(response: impl AsRef<[u8]>) -> Result<Self, Error> {
+        let value: Value = serde_json::from_slice(response.as_ref()).map_err(Error::parse_error)?;
+
+        let items = match value {
+            Value::Array(items) => items,
+            // The node answers with a single response object when it
+            // rejects the batch as a whole (e.g. because it is malformed)
+            other => return Err(batch_error(other)),
+        };
+
+        let mut responses = BTreeMap::new();
+
+        for item in items {
+            let id = item
+                .get("id")
+                .cloned()
+                .map(serde_json::from_value::<Id>)
+                .transpose()
+                .map_err(Error::parse_error)?
+                .unwrap_or(Id::None);
+
+            responses.insert(id, item);
+        }
+
+        Ok(Self { responses })
+    }
+
+    /// Take the typed response to the request identified by `handle`
+    pub fn take<R>(&mut self, handle: &Handle<R>) -> Result<R, Error>
+    where
+        R: Response,
+    {
+        let response = self.responses.remove(&handle.id).ok_or_else(|| {
+            Error::server_error(format!("no response for request ID {:?}", handle.id))
+        })?;
+
+        serde_json::from_value::<response::Wrapper<R>>(response)
+            .map_err(Error::parse_error)?
+            .into_result()
+    }
+
+    /// Number of responses which have not been taken yet
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Have all responses been taken?
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+}
+
+/// Extract the error from a response rejecting a whole batch
+fn batch_error(response: Value) -> Error {
+    response
+        .get("error")
+        .cloned()
+        .and_then(|error| serde_json::from_value::<Error>(error).ok())
+        .unwrap_or_else(|| {
+            Error::server_error("server returned malformatted JSON (expected a batch response)")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoint::{health, status};
+
+    #[test]
+    fn serializes_as_array() {
+        let mut batch = Batch::new();
+        let status = batch.add(status::Request);
+        let health = batch.add(health::Request);
+        assert_eq!(batch.len(), 2);
+        assert!(batch.is_idempotent());
+        assert_ne!(status.id(), health.id());
+
+        let json: Value = serde_json::from_str(&batch.into_json()).unwrap();
+        let requests = json.as_array().unwrap();
+        assert_eq!(requests[0]["method"], "status");
+        assert_eq!(requests[1]["method"], "health");
+        assert_eq!(
+            requests[1]["id"],
+            serde_json::to_value(health.id()).unwrap()
+        );
+    }
+
+    #[test]
+    fn matches_responses_by_id() {
+        let mut batch = Batch::new();
+        let first = batch.add(health::Request);
+        let second = batch.add(health::Request);
+
+        // Responses may come back in any order
+        let json = serde_json::json!([
+            {
+                "jsonrpc": "2.0",
+                "id": second.id(),
+                "error": { "code": -32603, "message": "Internal error", "data": "oops" }
+            },
+            { "jsonrpc": "2.0", "id": first.id(), "result": {} },
+        ]);
+
+        let mut responses = Responses::from_string(json.to_string()).unwrap();
+        assert!(responses.take(&first).is_ok());
+        assert_eq!(responses.take(&second).unwrap_err().data(), Some("oops"));
+        assert!(responses.is_empty());
+        assert!(responses.take(&first).is_err());
+    }
+
+    #[test]
+    fn rejected_batch() {
+        let json = r#"{"jsonrpc":"2.0","id":"","error":{"code":-32700,"message":"Parse error. Invalid JSON","data":"bad"}}"#;
+        let err = Responses::from_string(json).unwrap_err();
+        assert_eq!(err.code(), crate::error::Code::ParseError);
+    }
+}
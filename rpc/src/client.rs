@@ -1,7 +1,6 @@
 //! Tendermint RPC client
 
-use bytes::buf::ext::BufExt;
-use hyper::header;
+use std::{sync::Arc, time::Duration};
 
 use tendermint::abci::{self, Transaction};
 use tendermint::block::Height;
@@ -9,23 +8,71 @@ use tendermint::evidence::Evidence;
 use tendermint::net;
 use tendermint::Genesis;
 
-use crate::{endpoint::*, Error, Request, Response};
+use crate::{batch, endpoint::*, Batch, Error, Request, Response};
+
+use self::retry::RetryPolicy;
+use self::tls::TlsConfig;
+use self::transport::{HttpTransport, Transport};
 
 pub mod event_listener;
+pub mod retry;
+pub mod tls;
+pub mod transport;
 
 /// Tendermint RPC client.
 ///
-/// Presently supports JSONRPC via HTTP.
+/// Presently supports JSONRPC via HTTP and HTTPS, as well as HTTP over UNIX
+/// domain sockets. Other ways of reaching a node can be plugged in through
+/// the `Transport` trait.
+///
+/// By default, requests are sent once and without timeout; see
+/// `with_request_timeout` and `with_retry_policy`.
 #[derive(Clone, Debug)]
 pub struct Client {
-    /// Address of the RPC server
-    address: net::Address,
+    /// Transport used to reach the RPC server
+    transport: Arc<dyn Transport>,
+
+    /// Maximum time to wait for the response to a single attempt
+    request_timeout: Option<Duration>,
+
+    /// Policy for retrying idempotent requests after transient failures
+    retry_policy: RetryPolicy,
 }
 
 impl Client {
     /// Create a new Tendermint RPC client, connecting to the given address
     pub fn new(address: net::Address) -> Self {
-        Self { address }
+        Self::with_transport(HttpTransport::new(address))
+    }
+
+    /// Create a new Tendermint RPC client, connecting to the given address
+    /// using custom TLS settings if it is an `https://` address
+    pub fn with_tls_config(address: net::Address, tls_config: TlsConfig) -> Self {
+        Self::with_transport(HttpTransport::with_tls_config(address, tls_config))
+    }
+
+    /// Create a new Tendermint RPC client sending its requests through the
+    /// given transport
+    pub fn with_transport(transport: impl Transport + 'static) -> Self {
+        Self {
+            transport: Arc::new(transport),
+            request_timeout: None,
+            retry_policy: RetryPolicy::none(),
+        }
+    }
+
+    /// Fail requests (with a `Timeout` error) when no response is received
+    /// within the given duration
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Retry idempotent requests which failed with a transient error
+    /// according to the given policy
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
     }
 
     /// `/abci_info`: get information about the ABCI application.
@@ -164,36 +211,57 @@ impl Client {
     where
         R: Request,
     {
-        let request_body = request.into_json();
-
-        let (host, port) = match &self.address {
-            net::Address::Tcp { host, port, .. } => (host, port),
-            other => {
-                return Err(Error::invalid_params(&format!(
-                    "invalid RPC address: {:?}",
-                    other
-                )))
+        let idempotent = request.method().is_idempotent();
+        let response = self.send(request.into_json(), idempotent).await?;
+        R::Response::from_string(response)
+    }
+
+    /// Perform a batch of requests against the RPC endpoint in a single
+    /// round trip.
+    ///
+    /// The typed responses are retrieved from the returned `Responses` using
+    /// the `Handle`s obtained when adding requests to the batch.
+    pub async fn perform_batch(&self, batch: Batch) -> Result<batch::Responses, Error> {
+        if batch.is_empty() {
+            return Err(Error::invalid_params("empty batch"));
+        }
+
+        let idempotent = batch.is_idempotent();
+        let response = self.send(batch.into_json(), idempotent).await?;
+        batch::Responses::from_string(response)
+    }
+
+    /// Send a serialized request through the transport, enforcing the request
+    /// timeout and retrying transient failures if the request is idempotent
+    async fn send(&self, request: String, idempotent: bool) -> Result<String, Error> {
+        let mut retry = 0;
+
+        loop {
+            let attempt = self.transport.request(request.clone());
+
+            let result = match self.request_timeout {
+                Some(timeout) => {
+                    tokio::time::timeout(timeout, attempt)
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(Error::timeout(format!("no response within {:?}", timeout)))
+                        })
+                }
+                None => attempt.await,
+            };
+
+            match result {
+                Err(e) if idempotent && e.is_transient() => {
+                    match self.retry_policy.backoff(retry) {
+                        Some(backoff) => {
+                            tokio::time::delay_for(backoff).await;
+                            retry += 1;
+                        }
+                        None => return Err(e),
+                    }
+                }
+                result => return result,
             }
-        };
-
-        let mut request = hyper::Request::builder()
-            .method("POST")
-            .uri(&format!("http://{}:{}/", host, port))
-            .body(hyper::Body::from(request_body.into_bytes()))?;
-
-        {
-            let headers = request.headers_mut();
-            headers.insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
-            headers.insert(
-                header::USER_AGENT,
-                format!("tendermint.rs/{}", env!("CARGO_PKG_VERSION"))
-                    .parse()
-                    .unwrap(),
-            );
         }
-        let http_client = hyper::Client::builder().build_http();
-        let response = http_client.request(request).await?;
-        let response_body = hyper::body::aggregate(response.into_body()).await?;
-        R::Response::from_reader(response_body.reader())
     }
 }
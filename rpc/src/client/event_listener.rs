@@ -3,20 +3,21 @@
 // TODO(ismail): document fields or re-use the abci types
 #![allow(missing_docs)]
 
-use async_tungstenite::{tokio::connect_async, tokio::TokioAdapter, tungstenite::Message};
+use async_tungstenite::tungstenite::Message;
 use futures::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error as stdError;
-use tokio::net::TcpStream;
 
 use tendermint::block;
 use tendermint::net;
 
+use crate::client::transport::{connect_websocket, WebSocket};
 use crate::error::Code;
 use crate::response;
 use crate::response::Wrapper;
 use crate::Request;
+use crate::TlsConfig;
 use crate::{endpoint::subscribe, Error as RPCError};
 
 /// There are only two valid queries to the websocket. A query that subscribes to all transactions
@@ -42,24 +43,22 @@ impl EventSubscription {
 /// Event Listener over websocket.
 /// See: <https://docs.tendermint.com/master/rpc/#/Websocket/subscribe>
 pub struct EventListener {
-    socket: async_tungstenite::WebSocketStream<TokioAdapter<TcpStream>>,
+    socket: WebSocket,
 }
 
 impl EventListener {
     /// Constructor for event listener
     pub async fn connect(address: net::Address) -> Result<EventListener, RPCError> {
-        let (host, port) = match address {
-            net::Address::Tcp { host, port, .. } => (host, port),
-            other => {
-                return Err(RPCError::invalid_params(&format!(
-                    "invalid RPC address: {:?}",
-                    other
-                )));
-            }
-        };
-        //TODO This doesn't have any way to handle a connection over TLS
-        let (ws_stream, _unused_tls_stream) =
-            connect_async(&format!("ws://{}:{}/websocket", host, port)).await?;
+        Self::connect_with_tls_config(address, &TlsConfig::default()).await
+    }
+
+    /// Constructor for event listener using custom TLS settings if the
+    /// address is a `wss://` or `https://` address
+    pub async fn connect_with_tls_config(
+        address: net::Address,
+        tls_config: &TlsConfig,
+    ) -> Result<EventListener, RPCError> {
+        let ws_stream = connect_websocket(address, tls_config).await?;
         Ok(EventListener { socket: ws_stream })
     }
 
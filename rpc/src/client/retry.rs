@@ -0,0 +1,100 @@
+//! Retry policy for transient RPC failures
+
+use std::time::Duration;
+
+/// Default upper bound on the delay between two attempts
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// How (and whether) the `Client` retries idempotent requests which failed
+/// with a transient error.
+///
+/// The delay before the `n`-th retry is `initial_backoff * 2^(n - 1)`,
+/// capped at `max_backoff`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    max_retries: u32,
+
+    /// Delay before the first retry
+    initial_backoff: Duration,
+
+    /// Upper bound on the delay between two attempts
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retry failed requests
+    pub fn none() -> Self {
+        Self::exponential(0, Duration::from_secs(0))
+    }
+
+    /// Retry failed requests up to `max_retries` times, doubling the delay
+    /// between two attempts every time, starting with `initial_backoff`.
+    pub fn exponential(max_retries: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Set the upper bound on the delay between two attempts
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Maximum number of retries after the first attempt
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Delay to wait for before performing the given retry (starting at 0),
+    /// or `None` if the policy allows no further retry.
+    pub fn backoff(&self, retry: u32) -> Option<Duration> {
+        if retry >= self.max_retries {
+            return None;
+        }
+
+        let backoff = 1u32
+            .checked_shl(retry)
+            .and_then(|factor| self.initial_backoff.checked_mul(factor))
+            .unwrap_or(self.max_backoff);
+
+        Some(backoff.min(self.max_backoff))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_retries() {
+        assert_eq!(RetryPolicy::none().backoff(0), None);
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let policy = RetryPolicy::exponential(4, Duration::from_millis(100))
+            .with_max_backoff(Duration::from_millis(500));
+
+        assert_eq!(policy.backoff(0), Some(Duration::from_millis(100)));
+        assert_eq!(policy.backoff(1), Some(Duration::from_millis(200)));
+        assert_eq!(policy.backoff(2), Some(Duration::from_millis(400)));
+        assert_eq!(policy.backoff(3), Some(Duration::from_millis(500)));
+        assert_eq!(policy.backoff(4), None);
+    }
+
+    #[test]
+    fn backoff_overflow() {
+        let policy = RetryPolicy::exponential(100, Duration::from_secs(1));
+        assert_eq!(policy.backoff(64), Some(DEFAULT_MAX_BACKOFF));
+    }
+}
@@ -0,0 +1,202 @@
+//! TLS configuration for `https://` and `wss://` connections
+
+use std::{
+    fmt, fs,
+    io::{self, BufReader},
+    path::Path,
+    sync::Arc,
+};
+
+use hyper::client::HttpConnector;
+use hyper_rustls::HttpsConnector;
+use rustls::internal::pemfile;
+use tokio::net::TcpStream;
+use tokio_rustls::{client::TlsStream, webpki::DNSNameRef, TlsConnector};
+
+use crate::Error;
+
+/// TLS settings used by the RPC client and event listener when connecting to
+/// an `https://` or `wss://` address.
+///
+/// By default, the Mozilla root certificates bundled with `webpki-roots` are
+/// trusted and no client certificate is presented.
+#[derive(Clone)]
+pub struct TlsConfig {
+    /// Trust the bundled `webpki-roots` certificate authorities
+    webpki_roots: bool,
+
+    /// Additional PEM-encoded CA certificates to trust
+    ca_certs: Vec<Vec<u8>>,
+
+    /// PEM-encoded client certificate chain and private key
+    client_cert: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl TlsConfig {
+    /// Create a TLS configuration trusting the bundled root certificates
+    pub fn new() -> Self {
+        Self {
+            webpki_roots: true,
+            ca_certs: vec![],
+            client_cert: None,
+        }
+    }
+
+    /// Stop trusting the bundled root certificates, i.e. only trust the CA
+    /// certificates added through `add_ca_cert_pem`.
+    pub fn without_webpki_roots(mut self) -> Self {
+        self.webpki_roots = false;
+        self
+    }
+
+    /// Trust the PEM-encoded CA certificate(s) in `pem`
+    pub fn add_ca_cert_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.ca_certs.push(pem.into());
+        self
+    }
+
+    /// Trust the PEM-encoded CA certificate(s) stored in the file at `path`
+    pub fn add_ca_cert_file(self, path: impl AsRef<Path>) -> Result<Self, Error> {
+        Ok(self.add_ca_cert_pem(read_file(path.as_ref())?))
+    }
+
+    /// Present the given PEM-encoded certificate chain and private key
+    /// (PKCS#8 or RSA) to servers requesting client authentication
+    pub fn client_cert_pem(
+        mut self,
+        cert_chain: impl Into<Vec<u8>>,
+        key: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.client_cert = Some((cert_chain.into(), key.into()));
+        self
+    }
+
+    /// Present the PEM-encoded certificate chain and private key stored in the
+    /// given files to servers requesting client authentication
+    pub fn client_cert_files(
+        self,
+        cert_chain: impl AsRef<Path>,
+        key: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        Ok(self.client_cert_pem(read_file(cert_chain.as_ref())?, read_file(key.as_ref())?))
+    }
+
+    /// Build the `rustls` client configuration
+    pub(crate) fn client_config(&self) -> Result<rustls::ClientConfig, Error> {
+        let mut config = rustls::ClientConfig::new();
+
+        if self.webpki_roots {
+            config
+                .root_store
+                .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+        }
+
+        for pem in &self.ca_certs {
+            let (valid, _invalid) = config
+                .root_store
+                .add_pem_file(&mut BufReader::new(pem.as_slice()))
+                .map_err(|_| Error::invalid_params("malformed PEM CA certificate"))?;
+
+            if valid == 0 {
+                return Err(Error::invalid_params(
+                    "no valid CA certificate found in PEM",
+                ));
+            }
+        }
+
+        if let Some((cert_pem, key_pem)) = &self.client_cert {
+            let cert_chain = pemfile::certs(&mut BufReader::new(cert_pem.as_slice()))
+                .map_err(|_| Error::invalid_params("malformed PEM client certificate"))?;
+
+            let key = private_key(key_pem)?;
+
+            config
+                .set_single_client_cert(cert_chain, key)
+                .map_err(|e| {
+                    Error::invalid_params(&format!("invalid client certificate: {}", e))
+                })?;
+        }
+
+        Ok(config)
+    }
+
+    /// Wrap the given connector into a hyper connector speaking both HTTP
+    /// and HTTPS
+    pub(crate) fn https_connector(
+        &self,
+        mut http: HttpConnector,
+    ) -> Result<HttpsConnector<HttpConnector>, Error> {
+        http.enforce_http(false);
+        Ok(HttpsConnector::from((http, self.client_config()?)))
+    }
+
+    /// Establish a TLS session with `host` over an already connected stream
+    pub(crate) async fn connect(
+        &self,
+        host: &str,
+        stream: TcpStream,
+    ) -> Result<TlsStream<TcpStream>, Error> {
+        let domain = DNSNameRef::try_from_ascii_str(host)
+            .map_err(|_| Error::invalid_params(&format!("invalid TLS server name: {}", host)))?;
+
+        TlsConnector::from(Arc::new(self.client_config()?))
+            .connect(domain, stream)
+            .await
+            .map_err(|e| Error::websocket_error(format!("TLS handshake failed: {}", e)))
+    }
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the client's private key
+        f.debug_struct("TlsConfig")
+            .field("webpki_roots", &self.webpki_roots)
+            .field("ca_certs", &self.ca_certs.len())
+            .field("client_cert", &self.client_cert.is_some())
+            .finish()
+    }
+}
+
+/// Parse the first PKCS#8 or RSA private key found in the given PEM data
+fn private_key(pem: &[u8]) -> Result<rustls::PrivateKey, Error> {
+    let malformed = |_| Error::invalid_params("malformed PEM client private key");
+
+    let mut keys = pemfile::pkcs8_private_keys(&mut BufReader::new(pem)).map_err(malformed)?;
+
+    if keys.is_empty() {
+        keys = pemfile::rsa_private_keys(&mut BufReader::new(pem)).map_err(malformed)?;
+    }
+
+    keys.into_iter()
+        .next()
+        .ok_or_else(|| Error::invalid_params("no private key found in PEM"))
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, Error> {
+    fs::read(path).map_err(|e: io::Error| {
+        Error::invalid_params(&format!("couldn't read {}: {}", path.display(), e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_garbage_ca_cert() {
+        let config = TlsConfig::new().add_ca_cert_pem("not a certificate");
+        assert!(config.client_config().is_err());
+    }
+
+    #[test]
+    fn rejects_missing_client_key() {
+        let config = TlsConfig::new().client_cert_pem("", "");
+        assert!(config.client_config().is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! Transports carrying JSONRPC requests from the `Client` to a node
+
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use crate::Error;
+
+mod http;
+mod mock;
+mod websocket;
+
+pub use self::{
+    http::{HttpConfig, HttpTransport},
+    mock::MockTransport,
+    websocket::WebSocketTransport,
+};
+
+pub(crate) use self::websocket::{connect as connect_websocket, WebSocket};
+
+/// A transport sends serialized JSONRPC requests to a Tendermint node and
+/// returns the node's serialized responses.
+///
+/// The `Client` takes care of (de)serializing the typed requests and
+/// responses, so transports only deal with raw JSON strings.
+#[async_trait]
+pub trait Transport: Debug + Send + Sync {
+    /// Send the given serialized JSONRPC request and wait for the response
+    async fn request(&self, request: String) -> Result<String, Error>;
+}
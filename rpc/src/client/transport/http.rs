@@ -0,0 +1,151 @@
+//! HTTP(S) transport backed by a connection-pooling `hyper::Client`
+
+use async_trait::async_trait;
+use hyper::{client::HttpConnector, header};
+use hyper_rustls::HttpsConnector;
+use hyperlocal::UnixConnector;
+use std::time::Duration;
+
+use tendermint::net;
+
+use super::Transport;
+use crate::{Error, TlsConfig};
+
+/// JSONRPC over HTTP, HTTPS or HTTP over a UNIX domain socket, depending on
+/// the address it was created with.
+///
+/// Connections are kept alive and reused across requests made through the
+/// same transport (and its clones).
+#[derive(Clone, Debug)]
+pub struct HttpTransport {
+    /// Endpoint of the RPC server, or the error which prevented setting it up
+    /// (returned by every request)
+    endpoint: Result<Endpoint, Error>,
+}
+
+#[derive(Clone, Debug)]
+struct Endpoint {
+    /// URI the JSONRPC requests are POSTed to
+    uri: hyper::Uri,
+
+    /// Pooled HTTP client
+    client: HyperClient,
+}
+
+/// Settings of an `HttpTransport`
+#[derive(Clone, Debug, Default)]
+pub struct HttpConfig {
+    /// TLS settings used for `https://` addresses
+    pub tls: TlsConfig,
+
+    /// Maximum time to wait for a TCP connection to be established
+    /// (not applicable to UNIX domain sockets)
+    pub connect_timeout: Option<Duration>,
+}
+
+#[derive(Clone, Debug)]
+enum HyperClient {
+    Http(hyper::Client<HttpConnector>),
+    Https(hyper::Client<HttpsConnector<HttpConnector>>),
+    Unix(hyper::Client<UnixConnector>),
+}
+
+impl HttpTransport {
+    /// Create a new HTTP transport for the given address
+    pub fn new(address: net::Address) -> Self {
+        Self::with_tls_config(address, TlsConfig::default())
+    }
+
+    /// Create a new HTTP transport for the given address, using custom TLS
+    /// settings if it is an `https://` address.
+    ///
+    /// An invalid address or TLS configuration is reported by every request;
+    /// use `with_config` to check them upfront.
+    pub fn with_tls_config(address: net::Address, tls_config: TlsConfig) -> Self {
+        let config = HttpConfig {
+            tls: tls_config,
+            ..HttpConfig::default()
+        };
+
+        Self {
+            endpoint: Endpoint::new(&address, &config),
+        }
+    }
+
+    /// Create a new HTTP transport for the given address with custom settings
+    pub fn with_config(address: net::Address, config: HttpConfig) -> Result<Self, Error> {
+        Ok(Self {
+            endpoint: Ok(Endpoint::new(&address, &config)?),
+        })
+    }
+}
+
+impl Endpoint {
+    fn new(address: &net::Address, config: &HttpConfig) -> Result<Self, Error> {
+        let (uri, client) = match address {
+            net::Address::Tcp {
+                scheme, host, port, ..
+            } => {
+                let uri = format!(
+                    "{}://{}:{}/",
+                    if scheme.is_secure() { "https" } else { "http" },
+                    host,
+                    port
+                )
+                .parse()
+                .map_err(|e| Error::invalid_params(&format!("invalid RPC address: {}", e)))?;
+
+                let mut http = HttpConnector::new();
+                http.set_connect_timeout(config.connect_timeout);
+
+                let client = if scheme.is_secure() {
+                    HyperClient::Https(
+                        hyper::Client::builder().build(config.tls.https_connector(http)?),
+                    )
+                } else {
+                    HyperClient::Http(hyper::Client::builder().build(http))
+                };
+
+                (uri, client)
+            }
+            net::Address::Unix { path } => (
+                hyperlocal::Uri::new(path, "/").into(),
+                HyperClient::Unix(hyper::Client::builder().build(UnixConnector)),
+            ),
+        };
+
+        Ok(Self { uri, client })
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn request(&self, request_body: String) -> Result<String, Error> {
+        let endpoint = self.endpoint.as_ref().map_err(Clone::clone)?;
+
+        let mut request = hyper::Request::builder()
+            .method("POST")
+            .uri(endpoint.uri.clone())
+            .body(hyper::Body::from(request_body.into_bytes()))?;
+
+        {
+            let headers = request.headers_mut();
+            headers.insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
+            headers.insert(
+                header::USER_AGENT,
+                format!("tendermint.rs/{}", env!("CARGO_PKG_VERSION"))
+                    .parse()
+                    .unwrap(),
+            );
+        }
+
+        let response = match &endpoint.client {
+            HyperClient::Http(client) => client.request(request).await?,
+            HyperClient::Https(client) => client.request(request).await?,
+            HyperClient::Unix(client) => client.request(request).await?,
+        };
+
+        let response_body = hyper::body::to_bytes(response.into_body()).await?;
+        String::from_utf8(response_body.to_vec()).map_err(Error::parse_error)
+    }
+}
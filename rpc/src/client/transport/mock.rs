@@ -0,0 +1,107 @@
+//! In-memory transport replaying canned JSONRPC responses
+
+use async_trait::async_trait;
+use std::{collections::HashMap, fs, path::Path, str::FromStr};
+
+use super::Transport;
+use crate::{Error, Method};
+
+/// Transport answering requests with canned responses, keyed by method.
+///
+/// Meant for testing code built on top of the `Client` without a running
+/// node. The `id` of each canned response is rewritten to match the request
+/// it answers, and batch requests are answered with a batch of responses.
+#[derive(Clone, Debug, Default)]
+pub struct MockTransport {
+    responses: HashMap<Method, String>,
+}
+
+impl MockTransport {
+    /// Create a mock transport without any canned response
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load canned responses from a directory of JSON fixtures.
+    ///
+    /// Every `<method>.json` file (e.g. `status.json`) is used to answer the
+    /// requests for that method; other files are ignored.
+    pub fn from_fixtures(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut transport = Self::new();
+        let io_error = |e: std::io::Error| Error::invalid_params(&e.to_string());
+
+        for entry in fs::read_dir(dir.as_ref()).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+
+            if path.extension().map_or(true, |ext| ext != "json") {
+                continue;
+            }
+
+            let method = match path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| Method::from_str(stem).ok())
+            {
+                Some(method) => method,
+                None => continue,
+            };
+
+            transport =
+                transport.with_response(method, fs::read_to_string(&path).map_err(io_error)?);
+        }
+
+        Ok(transport)
+    }
+
+    /// Answer requests for `method` with the given JSONRPC response
+    pub fn with_response(mut self, method: Method, response: impl Into<String>) -> Self {
+        self.responses.insert(method, response.into());
+        self
+    }
+
+    /// Compute the canned response to a single JSONRPC request object
+    fn respond(&self, request: &serde_json::Value) -> Result<serde_json::Value, Error> {
+        let method = request
+            .get("method")
+            .and_then(|method| method.as_str())
+            .ok_or_else(|| Error::invalid_params("request has no method"))?;
+
+        let canned = self
+            .responses
+            .get(&Method::from_str(method)?)
+            .ok_or_else(|| Error::method_not_found(method))?;
+
+        let mut response: serde_json::Value =
+            serde_json::from_str(canned).map_err(Error::parse_error)?;
+
+        if let Some(fields) = response.as_object_mut() {
+            let id = request
+                .get("id")
+                .cloned()
+                .unwrap_or(serde_json::Value::Null);
+            fields.insert("id".to_owned(), id);
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn request(&self, request: String) -> Result<String, Error> {
+        let request: serde_json::Value =
+            serde_json::from_str(&request).map_err(Error::parse_error)?;
+
+        let response = match request {
+            serde_json::Value::Array(batch) => serde_json::Value::Array(
+                batch
+                    .iter()
+                    .map(|request| self.respond(request))
+                    .collect::<Result<_, _>>()?,
+            ),
+            request => self.respond(&request)?,
+        };
+
+        Ok(response.to_string())
+    }
+}
@@ -0,0 +1,133 @@
+//! JSONRPC transport over a Tendermint websocket connection
+
+use async_trait::async_trait;
+use async_tungstenite::{
+    tokio::{client_async, TokioAdapter},
+    tungstenite::Message,
+    WebSocketStream,
+};
+use futures::{lock::Mutex, prelude::*};
+use std::fmt;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpStream, UnixStream};
+
+use tendermint::net;
+
+use super::Transport;
+use crate::{Error, TlsConfig};
+
+/// Byte stream the websocket runs over (plain TCP, TLS or UNIX socket)
+pub(crate) trait ByteStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> ByteStream for T {}
+
+/// Websocket connection to a Tendermint node
+pub(crate) type WebSocket = WebSocketStream<TokioAdapter<Box<dyn ByteStream>>>;
+
+/// Open a websocket connection to the `/websocket` endpoint of the node at
+/// the given address
+pub(crate) async fn connect(
+    address: net::Address,
+    tls_config: &TlsConfig,
+) -> Result<WebSocket, Error> {
+    let (stream, url): (Box<dyn ByteStream>, _) = match address {
+        net::Address::Tcp {
+            scheme, host, port, ..
+        } => {
+            let tcp_stream = TcpStream::connect((host.as_str(), port))
+                .await
+                .map_err(|e| Error::websocket_error(e.to_string()))?;
+
+            if scheme.is_secure() {
+                (
+                    Box::new(tls_config.connect(&host, tcp_stream).await?),
+                    format!("wss://{}:{}/websocket", host, port),
+                )
+            } else {
+                (
+                    Box::new(tcp_stream),
+                    format!("ws://{}:{}/websocket", host, port),
+                )
+            }
+        }
+        net::Address::Unix { path } => {
+            let unix_stream = UnixStream::connect(&path)
+                .await
+                .map_err(|e| Error::websocket_error(e.to_string()))?;
+
+            // The host is only used for the `Host` header of the
+            // handshake request, which Tendermint ignores
+            (Box::new(unix_stream), "ws://localhost/websocket".to_owned())
+        }
+    };
+
+    let (ws_stream, _response) = client_async(url, stream).await?;
+    Ok(ws_stream)
+}
+
+/// JSONRPC over a single, long-lived websocket connection.
+///
+/// Requests are sent one at a time; messages received while waiting for a
+/// response which do not carry the request's ID (e.g. events) are dropped.
+pub struct WebSocketTransport {
+    socket: Mutex<WebSocket>,
+}
+
+impl WebSocketTransport {
+    /// Connect to the websocket endpoint of the node at the given address
+    pub async fn connect(address: net::Address) -> Result<Self, Error> {
+        Self::connect_with_tls_config(address, &TlsConfig::default()).await
+    }
+
+    /// Connect to the websocket endpoint of the node at the given address,
+    /// using custom TLS settings if it is a `wss://` or `https://` address
+    pub async fn connect_with_tls_config(
+        address: net::Address,
+        tls_config: &TlsConfig,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            socket: Mutex::new(connect(address, tls_config).await?),
+        })
+    }
+}
+
+impl fmt::Debug for WebSocketTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketTransport").finish()
+    }
+}
+
+#[async_trait]
+impl Transport for WebSocketTransport {
+    async fn request(&self, request: String) -> Result<String, Error> {
+        let request_id = serde_json::from_str::<serde_json::Value>(&request)
+            .map_err(Error::parse_error)?
+            .get("id")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+
+        let mut socket = self.socket.lock().await;
+        socket.send(Message::text(request)).await?;
+
+        loop {
+            let msg = socket
+                .next()
+                .await
+                .ok_or_else(|| Error::websocket_error("web socket closed"))??;
+
+            let text = match msg {
+                Message::Text(text) => text,
+                Message::Close(_) => return Err(Error::websocket_error("web socket closed")),
+                _ => continue,
+            };
+
+            let response_id = serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|response| response.get("id").cloned());
+
+            if response_id.as_ref() == Some(&request_id) {
+                return Ok(text);
+            }
+        }
+    }
+}
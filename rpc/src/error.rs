@@ -52,6 +52,11 @@ impl Error {
         Error::new(Code::WebSocketError, Some(cause.into()))
     }
 
+    /// Create a new timeout error
+    pub fn timeout(cause: impl Into<String>) -> Error {
+        Error::new(Code::Timeout, Some(cause.into()))
+    }
+
     /// Create a new method-not-found error
     pub fn method_not_found(name: &str) -> Error {
         Error::new(Code::MethodNotFound, Some(name.to_string()))
@@ -87,6 +92,23 @@ impl Error {
     pub fn data(&self) -> Option<&str> {
         self.data.as_ref().map(AsRef::as_ref)
     }
+
+    /// Is this error transient, i.e. may the same request succeed if retried?
+    ///
+    /// Transport-level failures (connection errors, timeouts, dropped
+    /// websockets) are transient, whereas errors reported by the node itself
+    /// or malformed responses are considered permanent.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self.code,
+            Code::HttpError | Code::WebSocketError | Code::Timeout
+        )
+    }
+
+    /// Is this error permanent, i.e. is retrying the same request pointless?
+    pub fn is_permanent(&self) -> bool {
+        !self.is_transient()
+    }
 }
 
 impl Display for Error {
@@ -139,6 +161,10 @@ pub enum Code {
     #[error("Websocket Error")]
     WebSocketError,
 
+    /// Request or connection timeout
+    #[error("Timeout")]
+    Timeout,
+
     /// Parse error i.e. invalid JSON (-32700)
     #[error("Parse error. Invalid JSON")]
     ParseError,
@@ -180,6 +206,7 @@ impl From<i32> for Code {
         match value {
             0 => Code::HttpError,
             1 => Code::WebSocketError,
+            2 => Code::Timeout,
             -32700 => Code::ParseError,
             -32600 => Code::InvalidRequest,
             -32601 => Code::MethodNotFound,
@@ -196,6 +223,7 @@ impl From<Code> for i32 {
         match code {
             Code::HttpError => 0,
             Code::WebSocketError => 1,
+            Code::Timeout => 2,
             Code::ParseError => -32700,
             Code::InvalidRequest => -32600,
             Code::MethodNotFound => -32601,
@@ -236,4 +264,12 @@ mod tests {
         assert_eq!(res.code.value(), -32700);
         assert_eq!(res.data, Some("hello world".to_string()));
     }
+
+    #[test]
+    fn classification() {
+        assert!(Error::http_error("connection refused").is_transient());
+        assert!(Error::timeout("request timed out").is_transient());
+        assert!(Error::parse_error("hello world").is_permanent());
+        assert!(Error::new(Code::InternalError, None).is_permanent());
+    }
 }
@@ -3,8 +3,9 @@
 #[cfg(feature = "client")]
 mod client;
 #[cfg(feature = "client")]
-pub use client::{event_listener, Client};
+pub use client::{event_listener, retry::RetryPolicy, tls::TlsConfig, transport, Client};
 
+pub mod batch;
 pub mod endpoint;
 pub mod error;
 mod id;
@@ -14,5 +15,6 @@ pub mod response;
 mod version;
 
 pub use self::{
-    error::Error, id::Id, method::Method, request::Request, response::Response, version::Version,
+    batch::Batch, error::Error, id::Id, method::Method, request::Request, response::Response,
+    version::Version,
 };
@@ -83,6 +83,32 @@ impl Method {
             Method::BroadcastEvidence => "broadcast_evidence",
         }
     }
+
+    /// Can requests for this method safely be sent more than once?
+    ///
+    /// Queries are idempotent, whereas broadcasting transactions and subscribing
+    /// to events have side effects on the node. Broadcasting evidence is
+    /// idempotent, as nodes ignore the evidence they already know of.
+    pub fn is_idempotent(self) -> bool {
+        match self {
+            Method::BroadcastTxAsync
+            | Method::BroadcastTxSync
+            | Method::BroadcastTxCommit
+            | Method::Subscribe => false,
+            Method::AbciInfo
+            | Method::BroadcastEvidence
+            | Method::AbciQuery
+            | Method::Block
+            | Method::BlockResults
+            | Method::Blockchain
+            | Method::Commit
+            | Method::Genesis
+            | Method::Health
+            | Method::NetInfo
+            | Method::Status
+            | Method::Validators => true,
+        }
+    }
 }
 
 impl FromStr for Method {
@@ -20,7 +20,7 @@ pub trait Request: Debug + DeserializeOwned + Serialize + Sized {
 
 /// JSONRPC request wrapper (i.e. message envelope)
 #[derive(Debug, Deserialize, Serialize)]
-struct Wrapper<R> {
+pub(crate) struct Wrapper<R> {
     /// JSONRPC version
     jsonrpc: Version,
 
@@ -47,4 +47,9 @@ where
             params: request,
         }
     }
+
+    /// Get JSONRPC ID
+    pub fn id(&self) -> &Id {
+        &self.id
+    }
 }
@@ -300,3 +300,189 @@ mod endpoints {
         }
     }
 }
+
+#[cfg(feature = "client")]
+mod unix_socket {
+    use std::{env, fs, process};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+
+    use tendermint_rpc as rpc;
+
+    #[tokio::test]
+    async fn health_over_unix_socket() {
+        let path = env::temp_dir().join(format!("tendermint-rpc-{}.sock", process::id()));
+        let _ = fs::remove_file(&path);
+        let mut listener = UnixListener::bind(&path).unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            stream.read(&mut request).await.unwrap();
+
+            let body = fs::read_to_string("./tests/support/health.json").unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let address = format!("unix://{}", path.display()).parse().unwrap();
+        rpc::Client::new(address).health().await.unwrap();
+
+        server.await.unwrap();
+        fs::remove_file(&path).unwrap();
+    }
+}
+
+#[cfg(feature = "client")]
+mod mock_transport {
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use tendermint_rpc::{
+        self as rpc, endpoint,
+        transport::{MockTransport, Transport},
+        Method, Response, RetryPolicy,
+    };
+
+    fn client() -> rpc::Client {
+        rpc::Client::with_transport(MockTransport::from_fixtures("./tests/support").unwrap())
+    }
+
+    #[tokio::test]
+    async fn replays_fixtures() {
+        let client = client();
+
+        let status = client.status().await.unwrap();
+        assert_eq!(status.sync_info.latest_block_height.value(), 410_744);
+
+        let abci_info = client.abci_info().await.unwrap();
+        assert_eq!(abci_info.data.as_str(), "GaiaApp");
+
+        client.health().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn batch() {
+        let client = client();
+
+        let mut batch = rpc::Batch::new();
+        let commit = batch.add(endpoint::commit::Request::new(10u64.into()));
+        let validators = batch.add(endpoint::validators::Request::new(10u64.into()));
+        let status = batch.add(endpoint::status::Request);
+
+        let mut responses = client.perform_batch(batch).await.unwrap();
+
+        assert_eq!(responses.take(&validators).unwrap().validators.len(), 65);
+        assert_eq!(
+            responses
+                .take(&status)
+                .unwrap()
+                .validator_info
+                .voting_power
+                .value(),
+            0
+        );
+        assert!(responses.take(&commit).is_ok());
+    }
+
+    #[tokio::test]
+    async fn unknown_method() {
+        let client = rpc::Client::with_transport(MockTransport::new());
+        let err = client.health().await.unwrap_err();
+        assert_eq!(err.code(), rpc::error::Code::MethodNotFound);
+    }
+
+    #[tokio::test]
+    async fn canned_error() {
+        let transport = MockTransport::new().with_response(
+            Method::Blockchain,
+            std::fs::read_to_string("./tests/support/error.json").unwrap(),
+        );
+        let client = rpc::Client::with_transport(transport);
+
+        let err = client.blockchain(321u64, 123u64).await.unwrap_err();
+        assert_eq!(err.code(), rpc::error::Code::InternalError);
+    }
+
+    /// Transport failing the first `failures` requests with a transient error
+    #[derive(Debug)]
+    struct FlakyTransport {
+        failures: AtomicU32,
+        inner: MockTransport,
+    }
+
+    #[async_trait]
+    impl Transport for FlakyTransport {
+        async fn request(&self, request: String) -> Result<String, rpc::Error> {
+            if self.failures.load(Ordering::SeqCst) > 0 {
+                self.failures.fetch_sub(1, Ordering::SeqCst);
+                return Err(rpc::Error::http_error("connection reset"));
+            }
+
+            self.inner.request(request).await
+        }
+    }
+
+    fn flaky_client(failures: u32) -> rpc::Client {
+        let transport = FlakyTransport {
+            failures: AtomicU32::new(failures),
+            inner: MockTransport::from_fixtures("./tests/support").unwrap(),
+        };
+
+        rpc::Client::with_transport(transport)
+            .with_retry_policy(RetryPolicy::exponential(2, Duration::from_millis(1)))
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors() {
+        flaky_client(2).health().await.unwrap();
+
+        let err = flaky_client(3).health().await.unwrap_err();
+        assert!(err.is_transient());
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_non_idempotent_requests() {
+        let tx = tendermint::abci::Transaction::new(Vec::<u8>::new());
+        let err = flaky_client(1).broadcast_tx_async(tx).await.unwrap_err();
+        assert_eq!(err.code(), rpc::error::Code::HttpError);
+    }
+
+    #[tokio::test]
+    async fn retries_broadcasting_evidence() {
+        let block = endpoint::block::Response::from_string(
+            std::fs::read_to_string("./tests/support/block_with_evidences.json").unwrap(),
+        )
+        .unwrap()
+        .block;
+        let evidence = block.evidence.iter().next().unwrap().clone();
+
+        flaky_client(1).broadcast_evidence(evidence).await.unwrap();
+    }
+
+    /// Transport which never answers
+    #[derive(Debug)]
+    struct SilentTransport;
+
+    #[async_trait]
+    impl Transport for SilentTransport {
+        async fn request(&self, _request: String) -> Result<String, rpc::Error> {
+            tokio::time::delay_for(Duration::from_secs(3600)).await;
+            unreachable!()
+        }
+    }
+
+    #[tokio::test]
+    async fn request_timeout() {
+        let client = rpc::Client::with_transport(SilentTransport)
+            .with_request_timeout(Duration::from_millis(10));
+
+        let err = client.status().await.unwrap_err();
+        assert_eq!(err.code(), rpc::error::Code::Timeout);
+    }
+}
@@ -1,29 +1,39 @@
 //! Application BlockChain Interface (ABCI)
 //!
-//! NOTE: This module contains types for ABCI responses as consumed from RPC
-//! endpoints. It does not contain an ABCI protocol implementation.
+//! This module contains:
 //!
-//! For that, see:
+//! - the requests and responses of the ABCI protocol, along with an
+//!   `Application` trait and a socket `Server` to write ABCI applications
+//! - the types of ABCI responses as consumed from RPC endpoints
 //!
-//! <https://github.com/tendermint/rust-abci>
+//! <https://github.com/tendermint/tendermint/blob/v0.34.0/spec/abci/abci.md>
 
+mod application;
 mod code;
 mod data;
 mod gas;
 mod info;
 mod log;
 mod path;
+pub mod request;
+pub mod response;
 pub mod responses;
+mod server;
 pub mod tag;
 pub mod transaction;
+pub mod types;
 
 pub use self::{
+    application::Application,
     code::Code,
     data::Data,
     gas::Gas,
     info::Info,
     log::Log,
     path::Path,
+    request::Request,
+    response::Response,
     responses::{DeliverTx, Event, Responses},
+    server::Server,
     transaction::Transaction,
 };
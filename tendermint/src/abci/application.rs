@@ -0,0 +1,96 @@
+//! ABCI applications
+
+use super::{request, response};
+
+/// An ABCI application, driven by Tendermint through a `Server`.
+///
+/// Each method handles the request of the same name. All of them have a
+/// default implementation, which does nothing and returns an empty response,
+/// so applications only need to implement the methods they care about.
+///
+/// Tendermint opens several connections to the application (consensus,
+/// mempool, info and snapshot), whose requests may be interleaved. The server
+/// handles them one at a time.
+pub trait Application: Send + 'static {
+    /// Echo back the given message
+    fn echo(&mut self, request: request::Echo) -> response::Echo {
+        response::Echo {
+            message: request.message,
+        }
+    }
+
+    /// Return information about the application state, used by Tendermint to
+    /// sync up with the application on startup
+    fn info(&mut self, _request: request::Info) -> response::Info {
+        Default::default()
+    }
+
+    /// Set a non-consensus critical application option
+    fn set_option(&mut self, _request: request::SetOption) -> response::SetOption {
+        Default::default()
+    }
+
+    /// Initialize the application state at genesis
+    fn init_chain(&mut self, _request: request::InitChain) -> response::InitChain {
+        Default::default()
+    }
+
+    /// Query the application state
+    fn query(&mut self, _request: request::Query) -> response::Query {
+        Default::default()
+    }
+
+    /// Check a transaction before it enters the mempool.
+    ///
+    /// A non-zero code rejects the transaction.
+    fn check_tx(&mut self, _request: request::CheckTx) -> response::CheckTx {
+        Default::default()
+    }
+
+    /// Signal the beginning of a new block, before its transactions are delivered
+    fn begin_block(&mut self, _request: request::BeginBlock) -> response::BeginBlock {
+        Default::default()
+    }
+
+    /// Execute a transaction of the current block
+    fn deliver_tx(&mut self, _request: request::DeliverTx) -> response::DeliverTx {
+        Default::default()
+    }
+
+    /// Signal the end of the current block, and return the validator set and
+    /// consensus params updates, if any
+    fn end_block(&mut self, _request: request::EndBlock) -> response::EndBlock {
+        Default::default()
+    }
+
+    /// Persist the application state, and return its hash
+    fn commit(&mut self, _request: request::Commit) -> response::Commit {
+        Default::default()
+    }
+
+    /// List the available snapshots of the application state
+    fn list_snapshots(&mut self, _request: request::ListSnapshots) -> response::ListSnapshots {
+        Default::default()
+    }
+
+    /// Offer a snapshot to restore the application state from
+    fn offer_snapshot(&mut self, _request: request::OfferSnapshot) -> response::OfferSnapshot {
+        Default::default()
+    }
+
+    /// Load a chunk of a snapshot, to send it to a peer
+    fn load_snapshot_chunk(
+        &mut self,
+        _request: request::LoadSnapshotChunk,
+    ) -> response::LoadSnapshotChunk {
+        Default::default()
+    }
+
+    /// Apply a chunk of the snapshot being restored
+    fn apply_snapshot_chunk(
+        &mut self,
+        _request: request::ApplySnapshotChunk,
+    ) -> response::ApplySnapshotChunk {
+        Default::default()
+    }
+}
@@ -0,0 +1,203 @@
+//! Requests sent by Tendermint to an ABCI application
+//!
+//! <https://github.com/tendermint/tendermint/blob/v0.34.0/proto/tendermint/abci/types.proto>
+
+#![allow(missing_docs)]
+
+use super::types::{ConsensusParams, Evidence, LastCommitInfo, Snapshot, ValidatorUpdate};
+use crate::proto_types::{Header, Timestamp};
+use prost::{Message, Oneof};
+
+/// `tendermint.abci.Request`
+#[derive(Clone, PartialEq, Message)]
+pub struct Request {
+    #[prost(
+        oneof = "Value",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15"
+    )]
+    pub value: Option<Value>,
+}
+
+impl From<Value> for Request {
+    fn from(value: Value) -> Self {
+        Request { value: Some(value) }
+    }
+}
+
+/// The possible requests
+#[derive(Clone, PartialEq, Oneof)]
+pub enum Value {
+    #[prost(message, tag = "1")]
+    Echo(Echo),
+    #[prost(message, tag = "2")]
+    Flush(Flush),
+    #[prost(message, tag = "3")]
+    Info(Info),
+    #[prost(message, tag = "4")]
+    SetOption(SetOption),
+    #[prost(message, tag = "5")]
+    InitChain(InitChain),
+    #[prost(message, tag = "6")]
+    Query(Query),
+    #[prost(message, tag = "7")]
+    BeginBlock(BeginBlock),
+    #[prost(message, tag = "8")]
+    CheckTx(CheckTx),
+    #[prost(message, tag = "9")]
+    DeliverTx(DeliverTx),
+    #[prost(message, tag = "10")]
+    EndBlock(EndBlock),
+    #[prost(message, tag = "11")]
+    Commit(Commit),
+    #[prost(message, tag = "12")]
+    ListSnapshots(ListSnapshots),
+    #[prost(message, tag = "13")]
+    OfferSnapshot(OfferSnapshot),
+    #[prost(message, tag = "14")]
+    LoadSnapshotChunk(LoadSnapshotChunk),
+    #[prost(message, tag = "15")]
+    ApplySnapshotChunk(ApplySnapshotChunk),
+}
+
+/// `tendermint.abci.RequestEcho`
+#[derive(Clone, PartialEq, Message)]
+pub struct Echo {
+    #[prost(string, tag = "1")]
+    pub message: String,
+}
+
+/// `tendermint.abci.RequestFlush`
+#[derive(Clone, PartialEq, Message)]
+pub struct Flush {}
+
+/// `tendermint.abci.RequestInfo`
+#[derive(Clone, PartialEq, Message)]
+pub struct Info {
+    /// Tendermint software version
+    #[prost(string, tag = "1")]
+    pub version: String,
+    #[prost(uint64, tag = "2")]
+    pub block_version: u64,
+    #[prost(uint64, tag = "3")]
+    pub p2p_version: u64,
+}
+
+/// `tendermint.abci.RequestSetOption`
+#[derive(Clone, PartialEq, Message)]
+pub struct SetOption {
+    #[prost(string, tag = "1")]
+    pub key: String,
+    #[prost(string, tag = "2")]
+    pub value: String,
+}
+
+/// `tendermint.abci.RequestInitChain`, sent once at genesis
+#[derive(Clone, PartialEq, Message)]
+pub struct InitChain {
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "1")]
+    pub time: Option<Timestamp>,
+    #[prost(string, tag = "2")]
+    pub chain_id: String,
+    #[prost(message, tag = "3")]
+    pub consensus_params: Option<ConsensusParams>,
+    #[prost(message, repeated, tag = "4")]
+    pub validators: Vec<ValidatorUpdate>,
+    #[prost(bytes, tag = "5")]
+    pub app_state_bytes: Vec<u8>,
+    #[prost(int64, tag = "6")]
+    pub initial_height: i64,
+}
+
+/// `tendermint.abci.RequestQuery`
+#[derive(Clone, PartialEq, Message)]
+pub struct Query {
+    #[prost(bytes, tag = "1")]
+    pub data: Vec<u8>,
+    #[prost(string, tag = "2")]
+    pub path: String,
+    #[prost(int64, tag = "3")]
+    pub height: i64,
+    #[prost(bool, tag = "4")]
+    pub prove: bool,
+}
+
+/// `tendermint.abci.RequestBeginBlock`
+#[derive(Clone, PartialEq, Message)]
+pub struct BeginBlock {
+    #[prost(bytes, tag = "1")]
+    pub hash: Vec<u8>,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "2")]
+    pub header: Option<Header>,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "3")]
+    pub last_commit_info: Option<LastCommitInfo>,
+    #[prost(message, repeated, tag = "4")]
+    pub byzantine_validators: Vec<Evidence>,
+}
+
+/// `tendermint.abci.RequestCheckTx`
+#[derive(Clone, PartialEq, Message)]
+pub struct CheckTx {
+    #[prost(bytes, tag = "1")]
+    pub tx: Vec<u8>,
+    /// See `CheckTxType`
+    #[prost(int32, tag = "2")]
+    pub r#type: i32,
+}
+
+/// `tendermint.abci.RequestDeliverTx`
+#[derive(Clone, PartialEq, Message)]
+pub struct DeliverTx {
+    #[prost(bytes, tag = "1")]
+    pub tx: Vec<u8>,
+}
+
+/// `tendermint.abci.RequestEndBlock`
+#[derive(Clone, PartialEq, Message)]
+pub struct EndBlock {
+    #[prost(int64, tag = "1")]
+    pub height: i64,
+}
+
+/// `tendermint.abci.RequestCommit`
+#[derive(Clone, PartialEq, Message)]
+pub struct Commit {}
+
+/// `tendermint.abci.RequestListSnapshots`
+#[derive(Clone, PartialEq, Message)]
+pub struct ListSnapshots {}
+
+/// `tendermint.abci.RequestOfferSnapshot`
+#[derive(Clone, PartialEq, Message)]
+pub struct OfferSnapshot {
+    #[prost(message, tag = "1")]
+    pub snapshot: Option<Snapshot>,
+    /// Light client-verified app hash for the height of the snapshot
+    #[prost(bytes, tag = "2")]
+    pub app_hash: Vec<u8>,
+}
+
+/// `tendermint.abci.RequestLoadSnapshotChunk`
+#[derive(Clone, PartialEq, Message)]
+pub struct LoadSnapshotChunk {
+    #[prost(uint64, tag = "1")]
+    pub height: u64,
+    #[prost(uint32, tag = "2")]
+    pub format: u32,
+    #[prost(uint32, tag = "3")]
+    pub chunk: u32,
+}
+
+/// `tendermint.abci.RequestApplySnapshotChunk`
+#[derive(Clone, PartialEq, Message)]
+pub struct ApplySnapshotChunk {
+    #[prost(uint32, tag = "1")]
+    pub index: u32,
+    #[prost(bytes, tag = "2")]
+    pub chunk: Vec<u8>,
+    /// ID of the peer the chunk was received from
+    #[prost(string, tag = "3")]
+    pub sender: String,
+}
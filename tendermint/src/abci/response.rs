@@ -0,0 +1,281 @@
+//! Responses of an ABCI application to the requests of Tendermint
+//!
+//! <https://github.com/tendermint/tendermint/blob/v0.34.0/proto/tendermint/abci/types.proto>
+
+#![allow(missing_docs)]
+
+use super::types::{ConsensusParams, Event, ProofOps, Snapshot, ValidatorUpdate};
+use prost::{Message, Oneof};
+
+/// `tendermint.abci.Response`
+#[derive(Clone, PartialEq, Message)]
+pub struct Response {
+    #[prost(
+        oneof = "Value",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16"
+    )]
+    pub value: Option<Value>,
+}
+
+impl From<Value> for Response {
+    fn from(value: Value) -> Self {
+        Response { value: Some(value) }
+    }
+}
+
+/// The possible responses
+#[derive(Clone, PartialEq, Oneof)]
+pub enum Value {
+    #[prost(message, tag = "1")]
+    Exception(Exception),
+    #[prost(message, tag = "2")]
+    Echo(Echo),
+    #[prost(message, tag = "3")]
+    Flush(Flush),
+    #[prost(message, tag = "4")]
+    Info(Info),
+    #[prost(message, tag = "5")]
+    SetOption(SetOption),
+    #[prost(message, tag = "6")]
+    InitChain(InitChain),
+    #[prost(message, tag = "7")]
+    Query(Query),
+    #[prost(message, tag = "8")]
+    BeginBlock(BeginBlock),
+    #[prost(message, tag = "9")]
+    CheckTx(CheckTx),
+    #[prost(message, tag = "10")]
+    DeliverTx(DeliverTx),
+    #[prost(message, tag = "11")]
+    EndBlock(EndBlock),
+    #[prost(message, tag = "12")]
+    Commit(Commit),
+    #[prost(message, tag = "13")]
+    ListSnapshots(ListSnapshots),
+    #[prost(message, tag = "14")]
+    OfferSnapshot(OfferSnapshot),
+    #[prost(message, tag = "15")]
+    LoadSnapshotChunk(LoadSnapshotChunk),
+    #[prost(message, tag = "16")]
+    ApplySnapshotChunk(ApplySnapshotChunk),
+}
+
+/// `tendermint.abci.ResponseOfferSnapshot.Result`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OfferSnapshotResult {
+    /// Unknown result, abort all snapshot restoration
+    Unknown = 0,
+    /// Snapshot accepted, apply chunks
+    Accept = 1,
+    /// Abort all snapshot restoration
+    Abort = 2,
+    /// Reject this specific snapshot, try others
+    Reject = 3,
+    /// Reject all snapshots of this format, try others
+    RejectFormat = 4,
+    /// Reject all snapshots from the sender(s), try others
+    RejectSender = 5,
+}
+
+/// `tendermint.abci.ResponseApplySnapshotChunk.Result`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ApplySnapshotChunkResult {
+    /// Unknown result, abort all snapshot restoration
+    Unknown = 0,
+    /// Chunk successfully accepted
+    Accept = 1,
+    /// Abort all snapshot restoration
+    Abort = 2,
+    /// Retry chunk (combine with refetch and reject)
+    Retry = 3,
+    /// Retry snapshot (combine with refetch and reject)
+    RetrySnapshot = 4,
+    /// Reject this snapshot, try others
+    RejectSnapshot = 5,
+}
+
+/// `tendermint.abci.ResponseException`, sent when a request could not be handled
+#[derive(Clone, PartialEq, Message)]
+pub struct Exception {
+    #[prost(string, tag = "1")]
+    pub error: String,
+}
+
+/// `tendermint.abci.ResponseEcho`
+#[derive(Clone, PartialEq, Message)]
+pub struct Echo {
+    #[prost(string, tag = "1")]
+    pub message: String,
+}
+
+/// `tendermint.abci.ResponseFlush`
+#[derive(Clone, PartialEq, Message)]
+pub struct Flush {}
+
+/// `tendermint.abci.ResponseInfo`
+#[derive(Clone, PartialEq, Message)]
+pub struct Info {
+    #[prost(string, tag = "1")]
+    pub data: String,
+    #[prost(string, tag = "2")]
+    pub version: String,
+    #[prost(uint64, tag = "3")]
+    pub app_version: u64,
+    #[prost(int64, tag = "4")]
+    pub last_block_height: i64,
+    #[prost(bytes, tag = "5")]
+    pub last_block_app_hash: Vec<u8>,
+}
+
+/// `tendermint.abci.ResponseSetOption`
+#[derive(Clone, PartialEq, Message)]
+pub struct SetOption {
+    #[prost(uint32, tag = "1")]
+    pub code: u32,
+    #[prost(string, tag = "3")]
+    pub log: String,
+    #[prost(string, tag = "4")]
+    pub info: String,
+}
+
+/// `tendermint.abci.ResponseInitChain`
+#[derive(Clone, PartialEq, Message)]
+pub struct InitChain {
+    #[prost(message, tag = "1")]
+    pub consensus_params: Option<ConsensusParams>,
+    /// Initial validator set, if different from the genesis one
+    #[prost(message, repeated, tag = "2")]
+    pub validators: Vec<ValidatorUpdate>,
+    #[prost(bytes, tag = "3")]
+    pub app_hash: Vec<u8>,
+}
+
+/// `tendermint.abci.ResponseQuery`
+#[derive(Clone, PartialEq, Message)]
+pub struct Query {
+    #[prost(uint32, tag = "1")]
+    pub code: u32,
+    #[prost(string, tag = "3")]
+    pub log: String,
+    #[prost(string, tag = "4")]
+    pub info: String,
+    #[prost(int64, tag = "5")]
+    pub index: i64,
+    #[prost(bytes, tag = "6")]
+    pub key: Vec<u8>,
+    #[prost(bytes, tag = "7")]
+    pub value: Vec<u8>,
+    #[prost(message, tag = "8")]
+    pub proof_ops: Option<ProofOps>,
+    #[prost(int64, tag = "9")]
+    pub height: i64,
+    #[prost(string, tag = "10")]
+    pub codespace: String,
+}
+
+/// `tendermint.abci.ResponseBeginBlock`
+#[derive(Clone, PartialEq, Message)]
+pub struct BeginBlock {
+    #[prost(message, repeated, tag = "1")]
+    pub events: Vec<Event>,
+}
+
+/// `tendermint.abci.ResponseCheckTx`
+#[derive(Clone, PartialEq, Message)]
+pub struct CheckTx {
+    #[prost(uint32, tag = "1")]
+    pub code: u32,
+    #[prost(bytes, tag = "2")]
+    pub data: Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub log: String,
+    #[prost(string, tag = "4")]
+    pub info: String,
+    #[prost(int64, tag = "5")]
+    pub gas_wanted: i64,
+    #[prost(int64, tag = "6")]
+    pub gas_used: i64,
+    #[prost(message, repeated, tag = "7")]
+    pub events: Vec<Event>,
+    #[prost(string, tag = "8")]
+    pub codespace: String,
+}
+
+/// `tendermint.abci.ResponseDeliverTx`
+#[derive(Clone, PartialEq, Message)]
+pub struct DeliverTx {
+    #[prost(uint32, tag = "1")]
+    pub code: u32,
+    #[prost(bytes, tag = "2")]
+    pub data: Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub log: String,
+    #[prost(string, tag = "4")]
+    pub info: String,
+    #[prost(int64, tag = "5")]
+    pub gas_wanted: i64,
+    #[prost(int64, tag = "6")]
+    pub gas_used: i64,
+    #[prost(message, repeated, tag = "7")]
+    pub events: Vec<Event>,
+    #[prost(string, tag = "8")]
+    pub codespace: String,
+}
+
+/// `tendermint.abci.ResponseEndBlock`
+#[derive(Clone, PartialEq, Message)]
+pub struct EndBlock {
+    #[prost(message, repeated, tag = "1")]
+    pub validator_updates: Vec<ValidatorUpdate>,
+    #[prost(message, tag = "2")]
+    pub consensus_param_updates: Option<ConsensusParams>,
+    #[prost(message, repeated, tag = "3")]
+    pub events: Vec<Event>,
+}
+
+/// `tendermint.abci.ResponseCommit`
+#[derive(Clone, PartialEq, Message)]
+pub struct Commit {
+    /// Application state hash, included in the header of the next block
+    #[prost(bytes, tag = "2")]
+    pub data: Vec<u8>,
+    /// Blocks below this height may be pruned by Tendermint
+    #[prost(int64, tag = "3")]
+    pub retain_height: i64,
+}
+
+/// `tendermint.abci.ResponseListSnapshots`
+#[derive(Clone, PartialEq, Message)]
+pub struct ListSnapshots {
+    #[prost(message, repeated, tag = "1")]
+    pub snapshots: Vec<Snapshot>,
+}
+
+/// `tendermint.abci.ResponseOfferSnapshot`
+#[derive(Clone, PartialEq, Message)]
+pub struct OfferSnapshot {
+    /// See `OfferSnapshotResult`
+    #[prost(int32, tag = "1")]
+    pub result: i32,
+}
+
+/// `tendermint.abci.ResponseLoadSnapshotChunk`
+#[derive(Clone, PartialEq, Message)]
+pub struct LoadSnapshotChunk {
+    #[prost(bytes, tag = "1")]
+    pub chunk: Vec<u8>,
+}
+
+/// `tendermint.abci.ResponseApplySnapshotChunk`
+#[derive(Clone, PartialEq, Message)]
+pub struct ApplySnapshotChunk {
+    /// See `ApplySnapshotChunkResult`
+    #[prost(int32, tag = "1")]
+    pub result: i32,
+    /// Chunks to refetch and reapply
+    #[prost(uint32, repeated, tag = "2")]
+    pub refetch_chunks: Vec<u32>,
+    /// Senders to reject, and ban
+    #[prost(string, repeated, tag = "3")]
+    pub reject_senders: Vec<String>,
+}
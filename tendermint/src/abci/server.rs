@@ -0,0 +1,366 @@
+//! ABCI socket server, through which Tendermint drives an application
+
+use super::{
+    request::{self, Request},
+    response::{self, Response},
+    Application,
+};
+use crate::error::{Error, Kind};
+use crate::net;
+use anomaly::{fail, format_err};
+use prost::Message;
+use std::convert::TryFrom;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Maximum length of the varint length prefix of a message
+const MAX_VARINT_LEN: usize = 10;
+
+/// Maximum length of a message, as in Tendermint
+const MAX_MSG_LEN: usize = 100 * 1024 * 1024;
+
+/// Server listening for connections from Tendermint, and dispatching their
+/// requests to an `Application`.
+///
+/// Messages are prefixed with their length as an unsigned varint, as of
+/// Tendermint v0.34.
+pub struct Server<A> {
+    listener: Listener,
+    app: Arc<Mutex<A>>,
+}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl<A: Application> Server<A> {
+    /// Listen on the given address (Tendermint's `proxy_app`), serving the given application
+    pub fn bind(address: &net::Address, app: A) -> Result<Self, Error> {
+        let listener = match address {
+            net::Address::Tcp { host, port, .. } => {
+                TcpListener::bind((host.as_str(), *port)).map(Listener::Tcp)
+            }
+            #[cfg(unix)]
+            net::Address::Unix { path } => UnixListener::bind(path).map(Listener::Unix),
+            #[cfg(not(unix))]
+            net::Address::Unix { .. } => fail!(
+                Kind::Io,
+                "UNIX domain sockets are not supported on this platform"
+            ),
+        }
+        .map_err(|e| format_err!(Kind::Io, "couldn't listen on {}: {}", address, e))?;
+
+        Ok(Server {
+            listener,
+            app: Arc::new(Mutex::new(app)),
+        })
+    }
+
+    /// Local address of the server, if listening on TCP
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match &self.listener {
+            Listener::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Listener::Unix(_) => None,
+        }
+    }
+
+    /// Accept connections, and serve each of them in its own thread.
+    ///
+    /// Only returns if accepting a connection fails.
+    pub fn listen(self) -> Result<(), Error> {
+        loop {
+            match &self.listener {
+                Listener::Tcp(listener) => {
+                    let (stream, _) = listener.accept()?;
+                    self.spawn(stream.try_clone()?, stream);
+                }
+                #[cfg(unix)]
+                Listener::Unix(listener) => {
+                    let (stream, _) = listener.accept()?;
+                    self.spawn(stream.try_clone()?, stream);
+                }
+            }
+        }
+    }
+
+    fn spawn<R, W>(&self, reader: R, writer: W)
+    where
+        R: Read + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        let app = Arc::clone(&self.app);
+
+        thread::spawn(move || {
+            // Errors close the connection, which Tendermint treats as fatal
+            let _ = serve(reader, writer, &app);
+        });
+    }
+}
+
+/// Handle the requests of a connection until it is closed.
+///
+/// Like in Tendermint, responses are buffered until a `Flush` request.
+fn serve<A: Application>(
+    reader: impl Read,
+    writer: impl Write,
+    app: &Mutex<A>,
+) -> Result<(), Error> {
+    let mut reader = BufReader::new(reader);
+    let mut writer = BufWriter::new(writer);
+
+    while let Some(request) = read_message::<Request>(&mut reader)? {
+        let flush = matches!(request.value, Some(request::Value::Flush(_)));
+
+        let response = {
+            let mut app = app
+                .lock()
+                .map_err(|_| format_err!(Kind::Protocol, "application panicked"))?;
+
+            handle(&mut *app, request)
+        };
+
+        write_message(&mut writer, &response)?;
+
+        if flush {
+            writer.flush()?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Dispatch a request to the application
+fn handle<A: Application>(app: &mut A, request: Request) -> Response {
+    use request::Value as Req;
+    use response::Value as Res;
+
+    let value = match request.value {
+        Some(Req::Echo(request)) => Res::Echo(app.echo(request)),
+        Some(Req::Flush(_)) => Res::Flush(response::Flush {}),
+        Some(Req::Info(request)) => Res::Info(app.info(request)),
+        Some(Req::SetOption(request)) => Res::SetOption(app.set_option(request)),
+        Some(Req::InitChain(request)) => Res::InitChain(app.init_chain(request)),
+        Some(Req::Query(request)) => Res::Query(app.query(request)),
+        Some(Req::BeginBlock(request)) => Res::BeginBlock(app.begin_block(request)),
+        Some(Req::CheckTx(request)) => Res::CheckTx(app.check_tx(request)),
+        Some(Req::DeliverTx(request)) => Res::DeliverTx(app.deliver_tx(request)),
+        Some(Req::EndBlock(request)) => Res::EndBlock(app.end_block(request)),
+        Some(Req::Commit(request)) => Res::Commit(app.commit(request)),
+        Some(Req::ListSnapshots(request)) => Res::ListSnapshots(app.list_snapshots(request)),
+        Some(Req::OfferSnapshot(request)) => Res::OfferSnapshot(app.offer_snapshot(request)),
+        Some(Req::LoadSnapshotChunk(request)) => {
+            Res::LoadSnapshotChunk(app.load_snapshot_chunk(request))
+        }
+        Some(Req::ApplySnapshotChunk(request)) => {
+            Res::ApplySnapshotChunk(app.apply_snapshot_chunk(request))
+        }
+        None => Res::Exception(response::Exception {
+            error: "empty request".to_owned(),
+        }),
+    };
+
+    Response::from(value)
+}
+
+/// Read a message prefixed with its length as an unsigned varint.
+///
+/// Returns `None` if the connection was closed before the message started.
+fn read_message<M: Message + Default>(reader: &mut impl Read) -> Result<Option<M>, Error> {
+    let mut len = 0u64;
+
+    for i in 0..MAX_VARINT_LEN {
+        let mut byte = [0u8; 1];
+
+        if reader.read(&mut byte)? == 0 {
+            if i == 0 {
+                return Ok(None);
+            }
+
+            fail!(Kind::Io, "connection closed while reading a message length");
+        }
+
+        len |= u64::from(byte[0] & 0x7f) << (7 * i);
+
+        if byte[0] & 0x80 == 0 {
+            let len = usize::try_from(len)
+                .ok()
+                .filter(|len| *len <= MAX_MSG_LEN)
+                .ok_or_else(|| {
+                    format_err!(
+                        Kind::Length,
+                        "message of {} bytes exceeds the maximum length of {} bytes",
+                        len,
+                        MAX_MSG_LEN
+                    )
+                })?;
+
+            let mut buf = vec![0u8; len];
+            reader.read_exact(&mut buf)?;
+
+            return Ok(Some(M::decode(buf.as_slice())?));
+        }
+    }
+
+    fail!(Kind::Length, "message length does not fit in a varint");
+}
+
+/// Write a message prefixed with its length as an unsigned varint
+fn write_message(writer: &mut impl Write, message: &impl Message) -> Result<(), Error> {
+    let mut buf = vec![];
+    message.encode_length_delimited(&mut buf)?;
+    writer.write_all(&buf)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpStream;
+
+    /// Application counting the transactions it was delivered
+    #[derive(Default)]
+    struct Counter {
+        count: u64,
+    }
+
+    impl Application for Counter {
+        fn deliver_tx(&mut self, _request: request::DeliverTx) -> response::DeliverTx {
+            self.count += 1;
+            Default::default()
+        }
+
+        fn commit(&mut self, _request: request::Commit) -> response::Commit {
+            response::Commit {
+                data: self.count.to_be_bytes().to_vec(),
+                retain_height: 0,
+            }
+        }
+    }
+
+    fn encode_requests(requests: Vec<request::Value>) -> Vec<u8> {
+        let mut buf = vec![];
+        for request in requests {
+            write_message(&mut buf, &Request::from(request)).unwrap();
+        }
+        buf
+    }
+
+    fn decode_responses(mut buf: &[u8]) -> Vec<response::Value> {
+        let mut responses = vec![];
+        while let Some(response) = read_message::<Response>(&mut buf).unwrap() {
+            responses.push(response.value.unwrap());
+        }
+        responses
+    }
+
+    #[test]
+    fn dispatch_requests_to_application() {
+        let requests = encode_requests(vec![
+            request::Value::DeliverTx(request::DeliverTx { tx: b"a".to_vec() }),
+            request::Value::DeliverTx(request::DeliverTx { tx: b"b".to_vec() }),
+            request::Value::Commit(request::Commit {}),
+            request::Value::Flush(request::Flush {}),
+        ]);
+
+        let mut output = vec![];
+        serve(
+            requests.as_slice(),
+            &mut output,
+            &Mutex::new(Counter::default()),
+        )
+        .unwrap();
+
+        let responses = decode_responses(&output);
+        assert_eq!(responses.len(), 4);
+        assert_eq!(
+            responses[2],
+            response::Value::Commit(response::Commit {
+                data: 2u64.to_be_bytes().to_vec(),
+                retain_height: 0,
+            })
+        );
+        assert_eq!(responses[3], response::Value::Flush(response::Flush {}));
+    }
+
+    #[test]
+    fn reply_to_empty_request_with_exception() {
+        let mut requests = vec![];
+        write_message(&mut requests, &Request { value: None }).unwrap();
+
+        let mut output = vec![];
+        serve(
+            requests.as_slice(),
+            &mut output,
+            &Mutex::new(Counter::default()),
+        )
+        .unwrap();
+
+        match &decode_responses(&output)[..] {
+            [response::Value::Exception(_)] => (),
+            responses => panic!("unexpected responses: {:?}", responses),
+        }
+    }
+
+    #[test]
+    fn reject_truncated_message() {
+        let requests = encode_requests(vec![request::Value::Echo(request::Echo {
+            message: "hello".to_owned(),
+        })]);
+
+        let mut output = vec![];
+        let truncated = &requests[..requests.len() - 1];
+        assert!(serve(truncated, &mut output, &Mutex::new(Counter::default())).is_err());
+    }
+
+    #[test]
+    fn reject_oversized_message() {
+        let mut requests = vec![];
+        prost::encoding::encode_varint(MAX_MSG_LEN as u64 + 1, &mut requests);
+
+        let err = read_message::<Request>(&mut requests.as_slice()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<anomaly::Context<Kind>>().unwrap().kind(),
+            &Kind::Length
+        );
+    }
+
+    #[test]
+    fn serve_over_tcp() {
+        let address = "tcp://127.0.0.1:0".parse().unwrap();
+        let server = Server::bind(&address, Counter::default()).unwrap();
+        let local_addr = server.local_addr().unwrap();
+        thread::spawn(move || server.listen());
+
+        let mut stream = TcpStream::connect(local_addr).unwrap();
+        stream
+            .write_all(&encode_requests(vec![
+                request::Value::Echo(request::Echo {
+                    message: "hello".to_owned(),
+                }),
+                request::Value::Flush(request::Flush {}),
+            ]))
+            .unwrap();
+
+        let echo = read_message::<Response>(&mut stream).unwrap().unwrap();
+        assert_eq!(
+            echo.value,
+            Some(response::Value::Echo(response::Echo {
+                message: "hello".to_owned(),
+            }))
+        );
+
+        let flush = read_message::<Response>(&mut stream).unwrap().unwrap();
+        assert_eq!(
+            flush.value,
+            Some(response::Value::Flush(response::Flush {}))
+        );
+    }
+}
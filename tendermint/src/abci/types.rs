@@ -0,0 +1,187 @@
+//! Types shared by the requests and responses of the ABCI protocol
+//!
+//! <https://github.com/tendermint/tendermint/blob/v0.34.0/proto/tendermint/abci/types.proto>
+
+#![allow(missing_docs)]
+
+use crate::proto_types::{Duration, PublicKey, Timestamp};
+use prost::Message;
+
+/// `tendermint.abci.CheckTxType`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CheckTxType {
+    /// Transaction new to the mempool
+    New = 0,
+    /// Transaction already in the mempool, checked again after a block was committed
+    Recheck = 1,
+}
+
+/// `tendermint.abci.EvidenceType`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EvidenceType {
+    Unknown = 0,
+    DuplicateVote = 1,
+    LightClientAttack = 2,
+}
+
+/// `tendermint.abci.ConsensusParams`
+#[derive(Clone, PartialEq, Message)]
+pub struct ConsensusParams {
+    #[prost(message, tag = "1")]
+    pub block: Option<BlockParams>,
+    #[prost(message, tag = "2")]
+    pub evidence: Option<EvidenceParams>,
+    #[prost(message, tag = "3")]
+    pub validator: Option<ValidatorParams>,
+    #[prost(message, tag = "4")]
+    pub version: Option<VersionParams>,
+}
+
+/// `tendermint.abci.BlockParams`
+#[derive(Clone, PartialEq, Message)]
+pub struct BlockParams {
+    #[prost(int64, tag = "1")]
+    pub max_bytes: i64,
+    #[prost(int64, tag = "2")]
+    pub max_gas: i64,
+}
+
+/// `tendermint.types.EvidenceParams`
+#[derive(Clone, PartialEq, Message)]
+pub struct EvidenceParams {
+    #[prost(int64, tag = "1")]
+    pub max_age_num_blocks: i64,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "2")]
+    pub max_age_duration: Option<Duration>,
+    #[prost(int64, tag = "3")]
+    pub max_bytes: i64,
+}
+
+/// `tendermint.types.ValidatorParams`
+#[derive(Clone, PartialEq, Message)]
+pub struct ValidatorParams {
+    #[prost(string, repeated, tag = "1")]
+    pub pub_key_types: Vec<String>,
+}
+
+/// `tendermint.types.VersionParams`
+#[derive(Clone, PartialEq, Message)]
+pub struct VersionParams {
+    #[prost(uint64, tag = "1")]
+    pub app_version: u64,
+}
+
+/// `tendermint.abci.LastCommitInfo`
+#[derive(Clone, PartialEq, Message)]
+pub struct LastCommitInfo {
+    #[prost(int32, tag = "1")]
+    pub round: i32,
+    #[prost(message, repeated, tag = "2")]
+    pub votes: Vec<VoteInfo>,
+}
+
+/// `tendermint.abci.Event`, emitted by the application while processing
+/// transactions and blocks, and used to index them.
+#[derive(Clone, PartialEq, Message)]
+pub struct Event {
+    #[prost(string, tag = "1")]
+    pub r#type: String,
+    #[prost(message, repeated, tag = "2")]
+    pub attributes: Vec<EventAttribute>,
+}
+
+/// `tendermint.abci.EventAttribute`
+#[derive(Clone, PartialEq, Message)]
+pub struct EventAttribute {
+    #[prost(bytes, tag = "1")]
+    pub key: Vec<u8>,
+    #[prost(bytes, tag = "2")]
+    pub value: Vec<u8>,
+    /// Whether Tendermint should index this attribute
+    #[prost(bool, tag = "3")]
+    pub index: bool,
+}
+
+/// `tendermint.abci.Validator`, identified by its address
+#[derive(Clone, PartialEq, Message)]
+pub struct Validator {
+    #[prost(bytes, tag = "1")]
+    pub address: Vec<u8>,
+    #[prost(int64, tag = "3")]
+    pub power: i64,
+}
+
+/// `tendermint.abci.ValidatorUpdate`, setting the voting power of a validator.
+///
+/// A power of zero removes the validator from the set.
+#[derive(Clone, PartialEq, Message)]
+pub struct ValidatorUpdate {
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "1")]
+    pub pub_key: Option<PublicKey>,
+    #[prost(int64, tag = "2")]
+    pub power: i64,
+}
+
+/// `tendermint.abci.VoteInfo`
+#[derive(Clone, PartialEq, Message)]
+pub struct VoteInfo {
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "1")]
+    pub validator: Option<Validator>,
+    #[prost(bool, tag = "2")]
+    pub signed_last_block: bool,
+}
+
+/// `tendermint.abci.Evidence` of the misbehavior of a validator
+#[derive(Clone, PartialEq, Message)]
+pub struct Evidence {
+    /// See `EvidenceType`
+    #[prost(int32, tag = "1")]
+    pub r#type: i32,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "2")]
+    pub validator: Option<Validator>,
+    #[prost(int64, tag = "3")]
+    pub height: i64,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "4")]
+    pub time: Option<Timestamp>,
+    #[prost(int64, tag = "5")]
+    pub total_voting_power: i64,
+}
+
+/// `tendermint.abci.Snapshot` of the application state, for state sync
+#[derive(Clone, PartialEq, Message)]
+pub struct Snapshot {
+    #[prost(uint64, tag = "1")]
+    pub height: u64,
+    /// Application-specific format of the snapshot
+    #[prost(uint32, tag = "2")]
+    pub format: u32,
+    #[prost(uint32, tag = "3")]
+    pub chunks: u32,
+    #[prost(bytes, tag = "4")]
+    pub hash: Vec<u8>,
+    #[prost(bytes, tag = "5")]
+    pub metadata: Vec<u8>,
+}
+
+/// `tendermint.crypto.ProofOps`
+#[derive(Clone, PartialEq, Message)]
+pub struct ProofOps {
+    #[prost(message, repeated, tag = "1")]
+    pub ops: Vec<ProofOp>,
+}
+
+/// `tendermint.crypto.ProofOp`
+#[derive(Clone, PartialEq, Message)]
+pub struct ProofOp {
+    #[prost(string, tag = "1")]
+    pub r#type: String,
+    #[prost(bytes, tag = "2")]
+    pub key: Vec<u8>,
+    #[prost(bytes, tag = "3")]
+    pub data: Vec<u8>,
+}
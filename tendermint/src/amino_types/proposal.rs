@@ -56,20 +56,20 @@ pub struct SignProposalRequest {
 }
 
 #[derive(Clone, PartialEq, Message)]
-struct CanonicalProposal {
+pub struct CanonicalProposal {
     #[prost_amino(uint32, tag = "1")]
-    msg_type: u32, /* this is a byte in golang, which is a varint encoded UInt8 (using amino's
-                    * EncodeUvarint) */
+    pub msg_type: u32, /* this is a byte in golang, which is a varint encoded UInt8 (using amino's
+                        * EncodeUvarint) */
     #[prost_amino(sfixed64)]
-    height: i64,
+    pub height: i64,
     #[prost_amino(sfixed64)]
-    round: i64,
+    pub round: i64,
     #[prost_amino(sfixed64)]
-    pol_round: i64,
+    pub pol_round: i64,
     #[prost_amino(message)]
-    block_id: Option<CanonicalBlockId>,
+    pub block_id: Option<CanonicalBlockId>,
     #[prost_amino(message)]
-    timestamp: Option<TimeMsg>,
+    pub timestamp: Option<TimeMsg>,
     #[prost_amino(string)]
     pub chain_id: String,
 }
@@ -135,6 +135,11 @@ impl SignableMsg for SignProposalRequest {
             prop.signature = sig.as_ref().to_vec();
         }
     }
+    fn set_timestamp(&mut self, timestamp: TimeMsg) {
+        if let Some(ref mut prop) = self.proposal {
+            prop.timestamp = Some(timestamp);
+        }
+    }
     fn validate(&self) -> Result<(), validate::Error> {
         match self.proposal {
             Some(ref p) => p.validate_basic(),
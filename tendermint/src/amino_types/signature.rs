@@ -1,4 +1,4 @@
-use super::validate;
+use super::{time::TimeMsg, validate};
 use crate::{chain, consensus};
 use bytes::BufMut;
 use prost_amino::{DecodeError, EncodeError};
@@ -15,6 +15,8 @@ pub trait SignableMsg {
 
     /// Set the Ed25519 signature on the underlying message
     fn set_signature(&mut self, sig: &ed25519::Signature);
+    /// Set the timestamp of the underlying message
+    fn set_timestamp(&mut self, timestamp: TimeMsg);
     fn validate(&self) -> Result<(), validate::Error>;
     fn consensus_state(&self) -> Option<consensus::State>;
     fn height(&self) -> Option<i64>;
@@ -179,6 +179,11 @@ impl SignableMsg for SignVoteRequest {
             vt.signature = sig.as_ref().to_vec();
         }
     }
+    fn set_timestamp(&mut self, timestamp: TimeMsg) {
+        if let Some(ref mut vt) = self.vote {
+            vt.timestamp = Some(timestamp);
+        }
+    }
     fn validate(&self) -> Result<(), validate::Error> {
         match self.vote {
             Some(ref v) => v.validate_basic(),
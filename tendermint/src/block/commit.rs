@@ -2,8 +2,13 @@
 
 use crate::block::commit_sig::CommitSig;
 use crate::block::{Height, Id};
-use crate::serializers;
+use crate::error::{Error, Kind};
+use crate::merkle::simple_hash_from_byte_vectors;
+use crate::proto_types::{self, ProtoMessage};
+use crate::{account, serializers, validator, Hash, Vote};
+use anomaly::fail;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::{ops::Deref, slice};
 
 /// Commit contains the justification (ie. a set of signatures) that a block was committed by a set
@@ -27,6 +32,117 @@ pub struct Commit {
     pub signatures: CommitSigs,
 }
 
+impl Commit {
+    /// Assemble the commit for the given block at the given height and round from the
+    /// given precommits, with one signature per validator of the given set, in order.
+    /// Validators without a precommit among `votes` are marked as absent.
+    ///
+    /// Note that the signatures of the votes are not verified.
+    ///
+    /// ## Errors
+    /// - If a vote is not a precommit, or is not for the given height and round
+    /// - If a vote is neither for the given block nor for nil
+    /// - If a vote is from a validator which is not in the set, or if there are
+    ///   several votes from the same validator
+    pub fn from_votes(
+        height: Height,
+        round: u64,
+        block_id: Id,
+        votes: &[Vote],
+        validators: &validator::Set,
+    ) -> Result<Commit, Error> {
+        let mut votes_by_validator: HashMap<account::Id, &Vote> = HashMap::new();
+
+        for vote in votes {
+            let validator_address = vote.validator_address;
+
+            if !vote.is_precommit() {
+                fail!(
+                    Kind::Protocol,
+                    "vote from {} is not a precommit",
+                    validator_address
+                );
+            }
+
+            if vote.height != height || vote.round != round {
+                fail!(
+                    Kind::Protocol,
+                    "vote from {} is for height {} and round {}, expected height {} and round {}",
+                    validator_address,
+                    vote.height,
+                    vote.round,
+                    height,
+                    round
+                );
+            }
+
+            if let Some(vote_block_id) = &vote.block_id {
+                if vote_block_id != &block_id {
+                    fail!(
+                        Kind::Protocol,
+                        "vote from {} is for block {}, expected {}",
+                        validator_address,
+                        vote_block_id,
+                        block_id
+                    );
+                }
+            }
+
+            if validators.validator(validator_address).is_none() {
+                fail!(
+                    Kind::Protocol,
+                    "vote from {} which is not in the validator set",
+                    validator_address
+                );
+            }
+
+            if votes_by_validator.insert(validator_address, vote).is_some() {
+                fail!(Kind::Protocol, "several votes from {}", validator_address);
+            }
+        }
+
+        let signatures = validators
+            .validators()
+            .iter()
+            .map(
+                |validator| match votes_by_validator.get(&validator.address) {
+                    None => CommitSig::BlockIDFlagAbsent,
+                    Some(vote) if vote.block_id.is_some() => CommitSig::BlockIDFlagCommit {
+                        validator_address: vote.validator_address,
+                        timestamp: vote.timestamp,
+                        signature: vote.signature.clone(),
+                    },
+                    Some(vote) => CommitSig::BlockIDFlagNil {
+                        validator_address: vote.validator_address,
+                        timestamp: vote.timestamp,
+                        signature: vote.signature.clone(),
+                    },
+                },
+            )
+            .collect::<Vec<_>>();
+
+        Ok(Commit {
+            height,
+            round,
+            block_id,
+            signatures: CommitSigs::new(signatures),
+        })
+    }
+
+    /// Compute the hash of this commit, ie. the `last_commit_hash` of the header
+    /// of the next block: the Merkle root of the protobuf encodings of its
+    /// signatures, as of Tendermint v0.34.
+    pub fn hash(&self) -> Hash {
+        let signatures_bytes = self
+            .signatures
+            .iter()
+            .map(|commit_sig| ProtoMessage::bytes_vec(&proto_types::CommitSig::from(commit_sig)))
+            .collect();
+
+        Hash::Sha256(simple_hash_from_byte_vectors(signatures_bytes))
+    }
+}
+
 /// CommitSigs which certify that a block is valid
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct CommitSigs(Vec<CommitSig>);
@@ -71,3 +187,119 @@ impl PartialEq for CommitSigs {
         self.0.clone().into_iter().eq(other.0.clone().into_iter())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{signed_header::SignedHeader, Header};
+    use crate::encoding::Encoding;
+    use crate::lite::Commit as _;
+    use crate::private_key::Ed25519Keypair;
+    use crate::{vote, PrivateKey, Signature, Time};
+    use signatory::ed25519;
+    use std::str::FromStr;
+
+    fn make_keys(count: u8) -> Vec<PrivateKey> {
+        (0..count)
+            .map(|i| PrivateKey::from(Ed25519Keypair::from(&ed25519::Seed::new([i; 32]))))
+            .collect()
+    }
+
+    fn make_vote(height: Height, block_id: Option<Id>) -> Vote {
+        Vote {
+            vote_type: vote::Type::Precommit,
+            height,
+            round: 0,
+            block_id,
+            timestamp: Time::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap(),
+            validator_address: account::Id::new([0; 20]),
+            validator_index: 0,
+            signature: Signature::Ed25519(ed25519::Signature::new([0; 64])),
+        }
+    }
+
+    #[test]
+    fn commit_from_signed_votes() {
+        let header: Header = serde_json::from_str(include_str!(
+            "../../tests/support/serialization/block/header.json"
+        ))
+        .unwrap();
+        let block_id =
+            Id::from_str("26C0A41F3243C6BCD7AD2DFF8A8D83A71D29D307B5326C227F734A1A512FE47D")
+                .unwrap();
+
+        let keys = make_keys(4);
+        let validators = validator::Set::new(
+            keys.iter()
+                .map(|key| validator::Info::new(key.public_key(), vote::Power::new(10)))
+                .collect(),
+        );
+
+        // Two votes for the block, one for nil, and one validator is absent
+        let votes = vec![
+            make_vote(header.height, Some(block_id.clone())),
+            make_vote(header.height, Some(block_id.clone())),
+            make_vote(header.height, None),
+        ]
+        .into_iter()
+        .zip(&keys)
+        .map(|(vote, key)| vote.sign(key, &header.chain_id, Encoding::Amino))
+        .collect::<Vec<_>>();
+
+        let commit = Commit::from_votes(header.height, 0, block_id, &votes, &validators).unwrap();
+
+        assert_eq!(commit.signatures.len(), 4);
+        assert_eq!(
+            commit.signatures.iter().filter(|s| s.is_commit()).count(),
+            2
+        );
+        assert_eq!(commit.signatures.iter().filter(|s| s.is_nil()).count(), 1);
+        assert_eq!(
+            commit.signatures.iter().filter(|s| s.is_absent()).count(),
+            1
+        );
+
+        for (commit_sig, validator) in commit.signatures.iter().zip(validators.validators()) {
+            if let Some(address) = commit_sig.validator_address() {
+                assert_eq!(address, validator.address);
+            }
+        }
+
+        let signed_header = SignedHeader { header, commit };
+        assert_eq!(signed_header.voting_power_in(&validators).unwrap(), 30);
+    }
+
+    #[test]
+    fn commit_hash_matches_last_commit_hash() {
+        // Last commit of the block at height 22 of a Tendermint v0.33 chain, whose
+        // signatures encode the same with Amino as with protobuf
+        let commit: Commit = serde_json::from_str(include_str!(
+            "../../tests/support/serialization/block/last_commit.json"
+        ))
+        .unwrap();
+
+        let last_commit_hash =
+            Hash::from_str("6494B45CF9B18F150FE20D77DBFC598592E4B2B559071FA1E7346274A5AA544F")
+                .unwrap();
+
+        assert_eq!(commit.hash(), last_commit_hash);
+    }
+
+    #[test]
+    fn commit_from_votes_for_another_height() {
+        let keys = make_keys(1);
+        let validators = validator::Set::new(vec![validator::Info::new(
+            keys[0].public_key(),
+            vote::Power::new(10),
+        )]);
+        let chain_id = "test-chain".parse().unwrap();
+
+        let vote = make_vote(Height::from(2_u64), None).sign(&keys[0], &chain_id, Encoding::Amino);
+        let block_id =
+            Id::from_str("26C0A41F3243C6BCD7AD2DFF8A8D83A71D29D307B5326C227F734A1A512FE47D")
+                .unwrap();
+
+        let result = Commit::from_votes(Height::from(1_u64), 0, block_id, &[vote], &validators);
+        assert!(result.is_err());
+    }
+}
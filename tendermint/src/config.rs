@@ -5,11 +5,16 @@
 //! - `config.toml`: `config::TendermintConfig`
 //! - `node_key.rs`: `config::node_key::NodeKey`
 //! - `priv_validator_key.rs`: `config::priv_validator_key::PrivValidatorKey`
+//! - `priv_validator_state.rs`: `config::priv_validator_state::PrivValidatorState`
 
 mod node_key;
 mod priv_validator_key;
+mod priv_validator_state;
 
-pub use self::{node_key::NodeKey, priv_validator_key::PrivValidatorKey};
+pub use self::{
+    node_key::NodeKey, priv_validator_key::PrivValidatorKey,
+    priv_validator_state::PrivValidatorState,
+};
 
 use crate::{
     abci::tag,
@@ -139,6 +144,15 @@ impl TendermintConfig {
         let path = home.as_ref().join(&self.node_key_file);
         NodeKey::load_json_file(&path)
     }
+
+    /// Load `priv_validator_state.json` file from the configured location
+    pub fn load_priv_validator_state(
+        &self,
+        home: impl AsRef<Path>,
+    ) -> Result<PrivValidatorState, Error> {
+        let path = home.as_ref().join(&self.priv_validator_state_file);
+        PrivValidatorState::load_json_file(&path)
+    }
 }
 
 /// Database backend
@@ -0,0 +1,454 @@
+//! Validator signing state
+
+use crate::{
+    amino_types::{self, TimeMsg},
+    block, consensus,
+    consensus::state::STEP_PROPOSE,
+    encoding::Encoding,
+    error::{Error, Kind},
+    proto_types::{self, ProtoMessage},
+    serializers,
+    time::ParseTimestamp,
+    PrivateKey, Signature, Time,
+};
+use anomaly::{fail, format_err};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, fs, path::Path};
+
+/// Last height, round and step signed by a validator, along with what it signed,
+/// as stored in `priv_validator_state.json`.
+///
+/// Validators must never sign for a height, round and step lower than the last
+/// signed one, nor sign different data for the same height, round and step,
+/// as this would amount to a double sign.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PrivValidatorState {
+    /// Last signed height
+    pub height: block::Height,
+
+    /// Last signed round
+    #[serde(with = "serializers::from_str")]
+    pub round: i64,
+
+    /// Last signed step
+    pub step: i8,
+
+    /// Last signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+
+    /// Last signed bytes
+    #[serde(
+        rename = "signbytes",
+        default,
+        with = "serializers::bytes::hexstring",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub sign_bytes: Vec<u8>,
+}
+
+impl Default for PrivValidatorState {
+    /// State of a validator which has not signed anything yet
+    fn default() -> Self {
+        Self {
+            height: block::Height::from(0u64),
+            round: 0,
+            step: 0,
+            signature: None,
+            sign_bytes: vec![],
+        }
+    }
+}
+
+impl PrivValidatorState {
+    /// Parse `priv_validator_state.json`
+    pub fn parse_json<T: AsRef<str>>(json_string: T) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json_string.as_ref())?)
+    }
+
+    /// Load `priv_validator_state.json` from a file
+    pub fn load_json_file<P>(path: &P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let json_string = fs::read_to_string(path).map_err(|e| {
+            format_err!(
+                Kind::Parse,
+                "couldn't open {}: {}",
+                path.as_ref().display(),
+                e
+            )
+        })?;
+
+        Self::parse_json(json_string)
+    }
+
+    /// Save `priv_validator_state.json` to a file.
+    ///
+    /// The state is first written to a temporary file next to the given path,
+    /// which is then renamed, so that the file is never left half-written.
+    pub fn save_json_file<P>(&self, path: &P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let tmp_path = path.with_extension("json.tmp");
+        let json_string = serde_json::to_string_pretty(self)?;
+
+        fs::write(&tmp_path, json_string)
+            .and_then(|_| fs::rename(&tmp_path, path))
+            .map_err(|e| format_err!(Kind::Io, "couldn't write {}: {}", path.display(), e))?;
+
+        Ok(())
+    }
+
+    /// Last signed height, round and step
+    pub fn consensus_state(&self) -> consensus::State {
+        consensus::State {
+            height: self.height,
+            round: self.round,
+            step: self.step,
+            block_id: None,
+        }
+    }
+
+    /// Check whether the given sign bytes, encoded with the given encoding, may be
+    /// signed at the requested height, round and step.
+    ///
+    /// Returns `None` if the request is above the last signed height, round and step,
+    /// in which case it may be signed. If the request is for the same message as the
+    /// last signed one, or for one which only differs by its timestamp, returns the
+    /// last signature along with the timestamp of the last signed message: this
+    /// signature must be reused, and the timestamp of the message reset to this one.
+    ///
+    /// ## Errors
+    /// - If the request is below the last signed height, round and step
+    /// - If the request is for the last signed height, round and step, but for
+    ///   a different message
+    pub fn check(
+        &self,
+        requested: &consensus::State,
+        sign_bytes: &[u8],
+        encoding: Encoding,
+    ) -> Result<Option<(Signature, Time)>, Error> {
+        let last = self.consensus_state();
+
+        match requested.cmp(&last) {
+            Ordering::Greater => Ok(None),
+            Ordering::Less => fail!(
+                Kind::DoubleSign,
+                "request for {} is below the last signed state {}",
+                requested,
+                last
+            ),
+            Ordering::Equal => {
+                let signature = self.signature.as_ref().ok_or_else(|| {
+                    format_err!(
+                        Kind::DoubleSign,
+                        "no signature recorded for the last signed state {}",
+                        last
+                    )
+                })?;
+
+                let (last_message, last_timestamp) =
+                    split_timestamp(&self.sign_bytes, self.step, encoding)?;
+                let (message, _) = split_timestamp(sign_bytes, requested.step, encoding)?;
+
+                if message != last_message {
+                    fail!(
+                        Kind::DoubleSign,
+                        "conflicting sign bytes for the last signed state {}",
+                        last
+                    );
+                }
+
+                Ok(Some((signature.clone(), last_timestamp)))
+            }
+        }
+    }
+
+    /// Sign the given sign bytes at the requested height, round and step with
+    /// the given private key, unless this would be a double sign, and record
+    /// them as the last signed ones.
+    ///
+    /// If the last signature is reused (see `check`), it is returned along with
+    /// the timestamp of the last signed message, which must be set on the message.
+    ///
+    /// The updated state must be saved before the signature is handed out.
+    pub fn sign(
+        &mut self,
+        requested: &consensus::State,
+        sign_bytes: Vec<u8>,
+        encoding: Encoding,
+        private_key: &PrivateKey,
+    ) -> Result<(Signature, Option<Time>), Error> {
+        if let Some((signature, timestamp)) = self.check(requested, &sign_bytes, encoding)? {
+            return Ok((signature, Some(timestamp)));
+        }
+
+        let signature = private_key.sign(&sign_bytes);
+
+        self.height = requested.height;
+        self.round = requested.round;
+        self.step = requested.step;
+        self.signature = Some(signature.clone());
+        self.sign_bytes = sign_bytes;
+
+        Ok((signature, None))
+    }
+}
+
+/// Decode the canonical vote or proposal (depending on the step) encoded by the
+/// given sign bytes, and split it into the encoding of the message without its
+/// timestamp, and this timestamp.
+fn split_timestamp(
+    sign_bytes: &[u8],
+    step: i8,
+    encoding: Encoding,
+) -> Result<(Vec<u8>, Time), Error> {
+    let (message, timestamp) = match (encoding, step == STEP_PROPOSE) {
+        (Encoding::Amino, false) => {
+            split_amino(sign_bytes, |vote: &mut amino_types::vote::CanonicalVote| {
+                vote.timestamp.take()
+            })?
+        }
+        (Encoding::Amino, true) => split_amino(
+            sign_bytes,
+            |proposal: &mut amino_types::proposal::CanonicalProposal| proposal.timestamp.take(),
+        )?,
+        (Encoding::Protobuf, false) => {
+            split_protobuf(sign_bytes, |vote: &mut proto_types::CanonicalVote| {
+                vote.timestamp.take()
+            })?
+        }
+        (Encoding::Protobuf, true) => split_protobuf(
+            sign_bytes,
+            |proposal: &mut proto_types::CanonicalProposal| proposal.timestamp.take(),
+        )?,
+    };
+
+    match timestamp {
+        Some(timestamp) => Ok((message, timestamp)),
+        None => fail!(Kind::Parse, "sign bytes without a timestamp"),
+    }
+}
+
+fn split_amino<M, F>(sign_bytes: &[u8], take_timestamp: F) -> Result<(Vec<u8>, Option<Time>), Error>
+where
+    M: prost_amino::Message + Default,
+    F: FnOnce(&mut M) -> Option<TimeMsg>,
+{
+    let mut message = M::decode_length_delimited(sign_bytes)
+        .map_err(|e| format_err!(Kind::Parse, "invalid sign bytes: {}", e))?;
+
+    let timestamp = match take_timestamp(&mut message) {
+        Some(timestamp) => Some(timestamp.parse_timestamp()?),
+        None => None,
+    };
+
+    let mut bytes = vec![];
+    message
+        .encode(&mut bytes)
+        .expect("encoding into a vector cannot fail");
+
+    Ok((bytes, timestamp))
+}
+
+fn split_protobuf<M, F>(
+    sign_bytes: &[u8],
+    take_timestamp: F,
+) -> Result<(Vec<u8>, Option<Time>), Error>
+where
+    M: prost::Message + Default,
+    F: FnOnce(&mut M) -> Option<proto_types::Timestamp>,
+{
+    let mut message = M::decode_length_delimited(sign_bytes)
+        .map_err(|e| format_err!(Kind::Parse, "invalid sign bytes: {}", e))?;
+
+    let timestamp = match take_timestamp(&mut message) {
+        Some(timestamp) => Some(timestamp.parse_timestamp()?),
+        None => None,
+    };
+
+    Ok((ProtoMessage::bytes_vec(&message), timestamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::state::{STEP_PRECOMMIT, STEP_PREVOTE, STEP_PROPOSE};
+    use crate::hash::{Algorithm, Hash};
+    use crate::private_key::Ed25519Keypair;
+    use crate::{account, vote, Vote};
+    use signatory::ed25519;
+
+    fn make_key() -> PrivateKey {
+        PrivateKey::from(Ed25519Keypair::from(&ed25519::Seed::new([1; 32])))
+    }
+
+    fn make_state(height: u64, round: i64, step: i8) -> consensus::State {
+        consensus::State {
+            height: block::Height::from(height),
+            round,
+            step,
+            block_id: None,
+        }
+    }
+
+    // sign bytes of a precommit at height 5 and round 0
+    fn precommit_sign_bytes(
+        block_id: Option<block::Id>,
+        timestamp: &str,
+        encoding: Encoding,
+    ) -> Vec<u8> {
+        let vote = Vote {
+            vote_type: vote::Type::Precommit,
+            height: block::Height::from(5u64),
+            round: 0,
+            block_id,
+            timestamp: Time::parse_from_rfc3339(timestamp).unwrap(),
+            validator_address: account::Id::new([0; account::LENGTH]),
+            validator_index: 0,
+            signature: Signature::Ed25519(ed25519::Signature::new([0; 64])),
+        };
+
+        vote.sign_bytes(&"test-chain".parse().unwrap(), encoding)
+    }
+
+    #[test]
+    fn sign_increasing_states() {
+        let key = make_key();
+        let mut state = PrivValidatorState::default();
+
+        for requested in &[
+            make_state(1, 0, STEP_PROPOSE),
+            make_state(1, 0, STEP_PREVOTE),
+            make_state(1, 0, STEP_PRECOMMIT),
+            make_state(1, 1, STEP_PREVOTE),
+            make_state(2, 0, STEP_PREVOTE),
+        ] {
+            let (signature, timestamp) = state
+                .sign(requested, b"sign bytes".to_vec(), Encoding::Amino, &key)
+                .unwrap();
+
+            assert_eq!(&state.consensus_state(), requested);
+            assert_eq!(state.signature, Some(signature));
+            assert_eq!(timestamp, None);
+        }
+    }
+
+    #[test]
+    fn reuse_signature_of_same_request() {
+        let key = make_key();
+        let mut state = PrivValidatorState::default();
+        let requested = make_state(5, 0, STEP_PRECOMMIT);
+        let vote = precommit_sign_bytes(None, "2020-01-01T00:00:00Z", Encoding::Amino);
+
+        let (signature, _) = state
+            .sign(&requested, vote.clone(), Encoding::Amino, &key)
+            .unwrap();
+        let saved = state.clone();
+
+        assert_eq!(
+            state.sign(&requested, vote, Encoding::Amino, &key).unwrap(),
+            (
+                signature,
+                Some(Time::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap())
+            )
+        );
+        assert_eq!(state, saved);
+    }
+
+    #[test]
+    fn reuse_signature_of_request_differing_by_timestamp() {
+        let key = make_key();
+        let requested = make_state(5, 0, STEP_PRECOMMIT);
+
+        for &encoding in &[Encoding::Amino, Encoding::Protobuf] {
+            let mut state = PrivValidatorState::default();
+            let vote = precommit_sign_bytes(None, "2020-01-01T00:00:00Z", encoding);
+            let later_vote = precommit_sign_bytes(None, "2020-01-01T00:00:05Z", encoding);
+
+            let (signature, _) = state.sign(&requested, vote, encoding, &key).unwrap();
+            let saved = state.clone();
+
+            // The signature of the first vote is reused, along with its timestamp
+            assert_eq!(
+                state.sign(&requested, later_vote, encoding, &key).unwrap(),
+                (
+                    signature,
+                    Some(Time::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap())
+                )
+            );
+            assert_eq!(state, saved);
+        }
+    }
+
+    #[test]
+    fn reject_regressions() {
+        let key = make_key();
+        let mut state = PrivValidatorState::default();
+        state
+            .sign(
+                &make_state(5, 1, STEP_PREVOTE),
+                b"vote".to_vec(),
+                Encoding::Amino,
+                &key,
+            )
+            .unwrap();
+        let saved = state.clone();
+
+        for requested in &[
+            make_state(4, 2, STEP_PRECOMMIT),
+            make_state(5, 0, STEP_PRECOMMIT),
+            make_state(5, 1, STEP_PROPOSE),
+        ] {
+            assert!(state
+                .sign(requested, b"vote".to_vec(), Encoding::Amino, &key)
+                .is_err());
+        }
+
+        assert_eq!(state, saved);
+    }
+
+    #[test]
+    fn reject_conflicting_sign_bytes() {
+        let key = make_key();
+        let requested = make_state(5, 0, STEP_PRECOMMIT);
+        let block_id = block::Id::new(Hash::new(Algorithm::Sha256, &[1; 32]).unwrap(), None);
+
+        for &encoding in &[Encoding::Amino, Encoding::Protobuf] {
+            let mut state = PrivValidatorState::default();
+            let vote = precommit_sign_bytes(None, "2020-01-01T00:00:00Z", encoding);
+            let other_vote =
+                precommit_sign_bytes(Some(block_id.clone()), "2020-01-01T00:00:00Z", encoding);
+
+            state.sign(&requested, vote, encoding, &key).unwrap();
+
+            assert!(state.check(&requested, &other_vote, encoding).is_err());
+        }
+    }
+
+    #[test]
+    fn save_and_load() {
+        let key = make_key();
+        let mut state = PrivValidatorState::default();
+        state
+            .sign(
+                &make_state(5, 0, STEP_PREVOTE),
+                b"vote".to_vec(),
+                Encoding::Amino,
+                &key,
+            )
+            .unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("priv_validator_state_{}.json", std::process::id()));
+
+        state.save_json_file(&path).unwrap();
+        let loaded = PrivValidatorState::load_json_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, state);
+    }
+}
@@ -11,6 +11,15 @@ use {
 /// <https://tendermint.com/docs/spec/consensus/consensus.html>
 pub const NIL_PLACEHOLDER: &str = "<nil>";
 
+/// Step at which a proposal is signed, as recorded by validators to prevent double signing
+pub const STEP_PROPOSE: i8 = 1;
+
+/// Step at which a prevote is signed, as recorded by validators to prevent double signing
+pub const STEP_PREVOTE: i8 = 2;
+
+/// Step at which a precommit is signed, as recorded by validators to prevent double signing
+pub const STEP_PRECOMMIT: i8 = 3;
+
 /// Tendermint consensus state
 #[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
 pub struct State {
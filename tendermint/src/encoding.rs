@@ -0,0 +1,55 @@
+//! Canonical encodings used to compute hashes and sign bytes
+
+use crate::block::header::Version;
+use serde::{Deserialize, Serialize};
+
+/// Block protocol version introduced by Tendermint v0.34, as of which
+/// canonical messages are encoded with Protobuf instead of Amino.
+pub const PROTOBUF_BLOCK_VERSION: u64 = 11;
+
+/// Encoding of the canonical messages from which header hashes, validator set
+/// hashes and vote sign bytes are computed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Encoding {
+    /// Amino, used up to Tendermint v0.33
+    #[serde(rename = "amino")]
+    Amino,
+
+    /// Protobuf, used as of Tendermint v0.34
+    #[serde(rename = "protobuf")]
+    Protobuf,
+}
+
+impl Encoding {
+    /// Get the encoding used by chains running the given block protocol version
+    pub fn for_block_version(block: u64) -> Self {
+        if block >= PROTOBUF_BLOCK_VERSION {
+            Encoding::Protobuf
+        } else {
+            Encoding::Amino
+        }
+    }
+}
+
+impl Default for Encoding {
+    fn default() -> Self {
+        Encoding::Amino
+    }
+}
+
+impl From<&Version> for Encoding {
+    fn from(version: &Version) -> Self {
+        Encoding::for_block_version(version.block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_encoding_from_block_version() {
+        assert_eq!(Encoding::for_block_version(10), Encoding::Amino);
+        assert_eq!(Encoding::for_block_version(11), Encoding::Protobuf);
+    }
+}
@@ -40,6 +40,14 @@ pub enum Kind {
     /// Signature invalid
     #[error("bad signature")]
     SignatureInvalid,
+
+    /// Signing request which would regress or equivocate
+    #[error("attempted double sign")]
+    DoubleSign,
+
+    /// Validator set update which cannot be applied
+    #[error("invalid validator set update")]
+    InvalidValidatorUpdate,
 }
 
 impl Kind {
@@ -1,8 +1,11 @@
 //! Evidence of malfeasance by validators (i.e. signing conflicting votes).
 
-use std::slice;
+use std::{collections::HashSet, slice};
 use {
-    crate::{block::signed_header::SignedHeader, serializers, PublicKey, Vote},
+    crate::{
+        block::{self, signed_header::SignedHeader, Header},
+        serializers, validator, vote, PublicKey, Time, Vote,
+    },
     serde::{Deserialize, Serialize},
 };
 
@@ -21,6 +24,10 @@ pub enum Evidence {
     /// Conflicting headers evidence
     #[serde(rename = "tendermint/ConflictingHeadersEvidence")]
     ConflictingHeaders(Box<ConflictingHeadersEvidence>),
+
+    /// Light client attack evidence
+    #[serde(rename = "tendermint/LightClientAttackEvidence")]
+    LightClientAttack(Box<LightClientAttackEvidence>),
 }
 
 /// Duplicate vote evidence
@@ -50,6 +57,167 @@ impl ConflictingHeadersEvidence {
     }
 }
 
+/// Evidence of an attack on a light client, ie. of a set of validators having
+/// signed a block which conflicts with the block committed on the main chain
+/// at the same height.
+///
+/// <https://github.com/tendermint/spec/blob/master/spec/light-client/detection/detection_003_reviewed.md>
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LightClientAttackEvidence {
+    conflicting_block: ConflictingBlock,
+    common_height: block::Height,
+    byzantine_validators: Vec<validator::Info>,
+    total_voting_power: vote::Power,
+    timestamp: Time,
+}
+
+impl LightClientAttackEvidence {
+    /// Create a new evidence of a light client attack
+    pub fn new(
+        conflicting_block: ConflictingBlock,
+        common_height: block::Height,
+        byzantine_validators: Vec<validator::Info>,
+        total_voting_power: vote::Power,
+        timestamp: Time,
+    ) -> Self {
+        Self {
+            conflicting_block,
+            common_height,
+            byzantine_validators,
+            total_voting_power,
+            timestamp,
+        }
+    }
+
+    /// The block which conflicts with the one committed on the main chain
+    pub fn conflicting_block(&self) -> &ConflictingBlock {
+        &self.conflicting_block
+    }
+
+    /// Height of the last block on which both chains agree for lunatic attacks,
+    /// or height of the conflicting block otherwise
+    pub fn common_height(&self) -> block::Height {
+        self.common_height
+    }
+
+    /// Validators which took part in the attack
+    pub fn byzantine_validators(&self) -> &[validator::Info] {
+        &self.byzantine_validators
+    }
+
+    /// Total voting power of the validator set at the common height
+    pub fn total_voting_power(&self) -> vote::Power {
+        self.total_voting_power
+    }
+
+    /// Time of the block at the common height
+    pub fn timestamp(&self) -> Time {
+        self.timestamp
+    }
+}
+
+/// Signed header and validator set of the block conflicting with the main chain
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConflictingBlock {
+    /// Signed header of the conflicting block
+    pub signed_header: SignedHeader,
+
+    /// Validator set which signed the conflicting block
+    pub validator_set: validator::Set,
+}
+
+/// Types of attack on a light client
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AttackType {
+    /// The conflicting header could not have been produced by a correct
+    /// execution of the state machine, eg. it carries a forged validator set
+    Lunatic,
+
+    /// Validators signed both blocks in the same round
+    Equivocation,
+
+    /// Validators signed both blocks in different rounds
+    Amnesia,
+}
+
+impl AttackType {
+    /// Classify the attack which produced the `conflicting` signed header,
+    /// given the `trusted` signed header at the same height.
+    pub fn classify(conflicting: &SignedHeader, trusted: &SignedHeader) -> Self {
+        if is_invalid_header(&conflicting.header, &trusted.header) {
+            AttackType::Lunatic
+        } else if conflicting.commit.round == trusted.commit.round {
+            AttackType::Equivocation
+        } else {
+            AttackType::Amnesia
+        }
+    }
+
+    /// Compute the validators responsible for the attack which produced the
+    /// `conflicting` block, given the `trusted` signed header at the same height
+    /// and the validator set at the last height on which both chains agree.
+    ///
+    /// - Lunatic: validators of the common validator set which signed the conflicting block
+    /// - Equivocation: validators which signed both blocks
+    /// - Amnesia: none, as the faulty validators cannot be told apart from the correct ones
+    ///
+    /// The validators are sorted by decreasing voting power, then by address.
+    pub fn byzantine_validators(
+        self,
+        conflicting: &ConflictingBlock,
+        trusted: &SignedHeader,
+        common_validators: &validator::Set,
+    ) -> Vec<validator::Info> {
+        let conflicting_signers = conflicting
+            .signed_header
+            .commit
+            .signatures
+            .iter()
+            .filter(|sig| sig.is_commit())
+            .filter_map(|sig| sig.validator_address());
+
+        let mut validators: Vec<validator::Info> = match self {
+            AttackType::Lunatic => conflicting_signers
+                .filter_map(|address| common_validators.validator(address))
+                .collect(),
+            AttackType::Equivocation => {
+                let trusted_signers = trusted
+                    .commit
+                    .signatures
+                    .iter()
+                    .filter(|sig| !sig.is_absent())
+                    .filter_map(|sig| sig.validator_address())
+                    .collect::<HashSet<_>>();
+
+                conflicting_signers
+                    .filter(|address| trusted_signers.contains(address))
+                    .filter_map(|address| conflicting.validator_set.validator(address))
+                    .collect()
+            }
+            AttackType::Amnesia => vec![],
+        };
+
+        validators.sort_by(|a, b| {
+            b.voting_power
+                .value()
+                .cmp(&a.voting_power.value())
+                .then(a.address.cmp(&b.address))
+        });
+
+        validators
+    }
+}
+
+/// Whether the `conflicting` header contains fields which are deterministically
+/// derived from the previous state, and which differ from the `trusted` header.
+fn is_invalid_header(conflicting: &Header, trusted: &Header) -> bool {
+    conflicting.validators_hash != trusted.validators_hash
+        || conflicting.next_validators_hash != trusted.next_validators_hash
+        || conflicting.consensus_hash != trusted.consensus_hash
+        || conflicting.app_hash != trusted.app_hash
+        || conflicting.last_results_hash != trusted.last_results_hash
+}
+
 /// Evidence data is a wrapper for a list of `Evidence`.
 ///
 /// <https://github.com/tendermint/tendermint/blob/master/docs/spec/blockchain/blockchain.md#evidencedata>
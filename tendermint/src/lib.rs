@@ -29,6 +29,7 @@ pub mod chain;
 pub mod channel;
 pub mod config;
 pub mod consensus;
+pub mod encoding;
 pub mod evidence;
 pub mod genesis;
 pub mod hash;
@@ -40,7 +41,11 @@ mod moniker;
 pub mod net;
 pub mod node;
 pub mod private_key;
+pub mod privval;
+pub mod proposal;
+pub mod proto_types;
 pub mod public_key;
+pub mod secret_connection;
 pub mod serializers;
 pub mod signature;
 pub mod time;
@@ -58,6 +63,7 @@ pub use crate::{
     error::{Error, Kind},
     hash::Hash,
     moniker::Moniker,
+    proposal::Proposal,
     public_key::{PublicKey, TendermintKey},
     signature::Signature,
     time::Time,
@@ -8,6 +8,7 @@ use crate::serializers;
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+use crate::encoding::Encoding;
 use crate::lite::error::{Error, Kind};
 use crate::Hash;
 
@@ -27,6 +28,10 @@ pub trait Header: Clone + Debug + Serialize + DeserializeOwned {
     fn validators_hash(&self) -> Hash;
     fn next_validators_hash(&self) -> Hash;
 
+    /// Encoding of the canonical messages of the block, from which the hashes
+    /// of the header and of its validator sets are computed.
+    fn encoding(&self) -> Encoding;
+
     /// Hash of the header (ie. the hash of the block).
     fn hash(&self) -> Hash;
 }
@@ -34,8 +39,13 @@ pub trait Header: Clone + Debug + Serialize + DeserializeOwned {
 /// ValidatorSet is the full validator set.
 /// It exposes its hash and its total power.
 pub trait ValidatorSet: Clone + Debug + Serialize + DeserializeOwned {
-    /// Hash of the validator set.
-    fn hash(&self) -> Hash;
+    /// Hash of the validator set, using the given encoding.
+    fn hash_with_encoding(&self, encoding: Encoding) -> Hash;
+
+    /// Hash of the validator set, using the default encoding.
+    fn hash(&self) -> Hash {
+        self.hash_with_encoding(Encoding::default())
+    }
 
     /// Total voting power of the set
     fn total_power(&self) -> u64;
@@ -273,6 +283,9 @@ pub(super) mod mocks {
         fn next_validators_hash(&self) -> Hash {
             self.next_vals
         }
+        fn encoding(&self) -> Encoding {
+            Encoding::default()
+        }
         fn hash(&self) -> Hash {
             json_hash(self)
         }
@@ -298,7 +311,7 @@ pub(super) mod mocks {
     }
 
     impl ValidatorSet for MockValSet {
-        fn hash(&self) -> Hash {
+        fn hash_with_encoding(&self, _encoding: Encoding) -> Hash {
             json_hash(&self)
         }
         fn total_power(&self) -> u64 {
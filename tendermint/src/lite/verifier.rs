@@ -65,17 +65,19 @@ where
     let commit = signed_header.commit();
 
     // ensure the header validator hashes match the given validators
-    if header.validators_hash() != vals.hash() {
+    let val_hash = vals.hash_with_encoding(header.encoding());
+    if header.validators_hash() != val_hash {
         return Err(Kind::InvalidValidatorSet {
             header_val_hash: header.validators_hash(),
-            val_hash: vals.hash(),
+            val_hash,
         }
         .into());
     }
-    if header.next_validators_hash() != next_vals.hash() {
+    let next_val_hash = next_vals.hash_with_encoding(header.encoding());
+    if header.next_validators_hash() != next_val_hash {
         return Err(Kind::InvalidNextValidatorSet {
             header_next_val_hash: header.next_validators_hash(),
-            next_val_hash: next_vals.hash(),
+            next_val_hash,
         }
         .into());
     }
@@ -1,10 +1,13 @@
 //! [`lite::Header`] implementation for [`block::Header`].
 
 use crate::amino_types::{message::AminoMessage, BlockId, ConsensusVersion, TimeMsg};
+use crate::encoding::Encoding;
 use crate::lite::Height;
 use crate::merkle::simple_hash_from_byte_vectors;
+use crate::proto_types::{self, BytesValue, Consensus, Int64Value, ProtoMessage, StringValue};
 use crate::Hash;
 use crate::{block, lite, Time};
+use std::convert::TryFrom;
 
 impl lite::Header for block::Header {
     type Time = Time;
@@ -25,38 +28,88 @@ impl lite::Header for block::Header {
         self.next_validators_hash
     }
 
+    /// Encoding of the Tendermint version given by the block protocol version
+    fn encoding(&self) -> Encoding {
+        Encoding::from(&self.version)
+    }
+
+    /// Compute the hash of the header, using the encoding of the
+    /// Tendermint version given by its block protocol version.
     fn hash(&self) -> Hash {
-        // Note that if there is an encoding problem this will
-        // panic (as the golang code would):
-        // https://github.com/tendermint/tendermint/blob/134fe2896275bb926b49743c1e25493f6b24cc31/types/block.go#L393
-        // https://github.com/tendermint/tendermint/blob/134fe2896275bb926b49743c1e25493f6b24cc31/types/encoding_helper.go#L9:6
-
-        let mut fields_bytes: Vec<Vec<u8>> = Vec::with_capacity(16);
-        fields_bytes.push(AminoMessage::bytes_vec(&ConsensusVersion::from(
-            &self.version,
-        )));
-        fields_bytes.push(encode_bytes(self.chain_id.as_bytes()));
-        fields_bytes.push(encode_varint(self.height.value()));
-        fields_bytes.push(AminoMessage::bytes_vec(&TimeMsg::from(self.time)));
-        fields_bytes.push(
-            self.last_block_id
-                .as_ref()
-                .map_or(vec![], |id| AminoMessage::bytes_vec(&BlockId::from(id))),
-        );
-        fields_bytes.push(self.last_commit_hash.as_ref().map_or(vec![], encode_hash));
-        fields_bytes.push(self.data_hash.as_ref().map_or(vec![], encode_hash));
-        fields_bytes.push(encode_hash(&self.validators_hash));
-        fields_bytes.push(encode_hash(&self.next_validators_hash));
-        fields_bytes.push(encode_hash(&self.consensus_hash));
-        fields_bytes.push(encode_bytes(&self.app_hash));
-        fields_bytes.push(self.last_results_hash.as_ref().map_or(vec![], encode_hash));
-        fields_bytes.push(self.evidence_hash.as_ref().map_or(vec![], encode_hash));
-        fields_bytes.push(encode_bytes(self.proposer_address.as_bytes()));
-
-        Hash::Sha256(simple_hash_from_byte_vectors(fields_bytes))
+        match self.encoding() {
+            Encoding::Amino => amino_hash(self),
+            Encoding::Protobuf => protobuf_hash(self),
+        }
     }
 }
 
+fn amino_hash(header: &block::Header) -> Hash {
+    // Note that if there is an encoding problem this will
+    // panic (as the golang code would):
+    // https://github.com/tendermint/tendermint/blob/134fe2896275bb926b49743c1e25493f6b24cc31/types/block.go#L393
+    // https://github.com/tendermint/tendermint/blob/134fe2896275bb926b49743c1e25493f6b24cc31/types/encoding_helper.go#L9:6
+
+    let mut fields_bytes: Vec<Vec<u8>> = Vec::with_capacity(16);
+    fields_bytes.push(AminoMessage::bytes_vec(&ConsensusVersion::from(
+        &header.version,
+    )));
+    fields_bytes.push(encode_bytes(header.chain_id.as_bytes()));
+    fields_bytes.push(encode_varint(header.height.value()));
+    fields_bytes.push(AminoMessage::bytes_vec(&TimeMsg::from(header.time)));
+    fields_bytes.push(
+        header
+            .last_block_id
+            .as_ref()
+            .map_or(vec![], |id| AminoMessage::bytes_vec(&BlockId::from(id))),
+    );
+    fields_bytes.push(header.last_commit_hash.as_ref().map_or(vec![], encode_hash));
+    fields_bytes.push(header.data_hash.as_ref().map_or(vec![], encode_hash));
+    fields_bytes.push(encode_hash(&header.validators_hash));
+    fields_bytes.push(encode_hash(&header.next_validators_hash));
+    fields_bytes.push(encode_hash(&header.consensus_hash));
+    fields_bytes.push(encode_bytes(&header.app_hash));
+    fields_bytes.push(
+        header
+            .last_results_hash
+            .as_ref()
+            .map_or(vec![], encode_hash),
+    );
+    fields_bytes.push(header.evidence_hash.as_ref().map_or(vec![], encode_hash));
+    fields_bytes.push(encode_bytes(header.proposer_address.as_bytes()));
+
+    Hash::Sha256(simple_hash_from_byte_vectors(fields_bytes))
+}
+
+fn protobuf_hash(header: &block::Header) -> Hash {
+    // Note that if there is an encoding problem this will
+    // panic (as the golang code would):
+    // https://github.com/tendermint/tendermint/blob/v0.34.0/types/block.go#L433
+    // https://github.com/tendermint/tendermint/blob/v0.34.0/types/encoding_helper.go
+
+    let height = i64::try_from(header.height.value()).expect("height overflow");
+
+    let fields_bytes: Vec<Vec<u8>> = vec![
+        ProtoMessage::bytes_vec(&Consensus::from(&header.version)),
+        ProtoMessage::bytes_vec(&StringValue {
+            value: header.chain_id.to_string(),
+        }),
+        ProtoMessage::bytes_vec(&Int64Value { value: height }),
+        ProtoMessage::bytes_vec(&proto_types::Timestamp::from(header.time)),
+        ProtoMessage::bytes_vec(&proto_types::BlockId::from(header.last_block_id.as_ref())),
+        proto_encode_hash(header.last_commit_hash.as_ref()),
+        proto_encode_hash(header.data_hash.as_ref()),
+        proto_encode_hash(Some(&header.validators_hash)),
+        proto_encode_hash(Some(&header.next_validators_hash)),
+        proto_encode_hash(Some(&header.consensus_hash)),
+        proto_encode_bytes(&header.app_hash),
+        proto_encode_hash(header.last_results_hash.as_ref()),
+        proto_encode_hash(header.evidence_hash.as_ref()),
+        proto_encode_bytes(header.proposer_address.as_bytes()),
+    ];
+
+    Hash::Sha256(simple_hash_from_byte_vectors(fields_bytes))
+}
+
 fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
     let bytes_len = bytes.len();
     if bytes_len > 0 {
@@ -79,11 +132,24 @@ fn encode_varint(val: u64) -> Vec<u8> {
     val_enc
 }
 
+fn proto_encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    ProtoMessage::bytes_vec(&BytesValue {
+        value: bytes.to_vec(),
+    })
+}
+
+fn proto_encode_hash(hash: Option<&Hash>) -> Vec<u8> {
+    hash.map_or(vec![], |hash| proto_encode_bytes(hash.as_bytes()))
+}
+
 #[cfg(test)]
 mod test {
-    use crate::block::Header;
+    use super::{proto_encode_bytes, protobuf_hash};
+    use crate::block::{self, header::Version, parts, Header};
+    use crate::hash::Algorithm;
     use crate::lite::Header as _;
-    use crate::Hash;
+    use crate::{account, Hash};
+    use sha2::{Digest, Sha256};
     use std::str::FromStr;
 
     #[test]
@@ -175,4 +241,51 @@ mod test {
 
         assert_eq!(got_hash, want_hash);
     }
+
+    #[test]
+    fn test_proto_encode_bytes() {
+        // Empty values are left out, as by `cdcEncode` in Go
+        assert_eq!(proto_encode_bytes(&[]), Vec::<u8>::new());
+        assert_eq!(
+            proto_encode_bytes(&[0xab, 0xcd]),
+            vec![0xa, 0x2, 0xab, 0xcd]
+        );
+    }
+
+    #[test]
+    fn test_protobuf_hash() {
+        // Test vector of `TestHeaderHash` in Tendermint v0.34:
+        // https://github.com/tendermint/tendermint/blob/v0.34.0/types/block_test.go
+        let sum = |bytes: &[u8]| Hash::new(Algorithm::Sha256, &Sha256::digest(bytes)).unwrap();
+        let zero_hash = Hash::new(Algorithm::Sha256, &[0; 32]).unwrap();
+
+        let mut proposer_address = [0u8; account::LENGTH];
+        proposer_address.copy_from_slice(&sum(b"proposer_address").as_bytes()[..account::LENGTH]);
+
+        let header = Header {
+            version: Version { block: 1, app: 2 },
+            chain_id: "chainId".parse().unwrap(),
+            height: block::Height::from(3u64),
+            time: "2019-10-13T16:14:44Z".parse().unwrap(),
+            last_block_id: Some(block::Id::new(
+                zero_hash,
+                Some(parts::Header::new(6, zero_hash)),
+            )),
+            last_commit_hash: Some(sum(b"last_commit_hash")),
+            data_hash: Some(sum(b"data_hash")),
+            validators_hash: sum(b"validators_hash"),
+            next_validators_hash: sum(b"next_validators_hash"),
+            consensus_hash: sum(b"consensus_hash"),
+            app_hash: sum(b"app_hash").as_bytes().to_vec(),
+            last_results_hash: Some(sum(b"last_results_hash")),
+            evidence_hash: Some(sum(b"evidence_hash")),
+            proposer_address: account::Id::new(proposer_address),
+        };
+
+        let want_hash =
+            Hash::from_str("F740121F553B5418C3EFBD343C2DBFE9E007BB67B0D020A0741374BAB65242A4")
+                .unwrap();
+
+        assert_eq!(protobuf_hash(&header), want_hash);
+    }
 }
@@ -1,6 +1,7 @@
 //! [`lite::SignedHeader`] implementation for [`block::signed_header::SignedHeader`].
 
 use crate::block::CommitSig;
+use crate::encoding::Encoding;
 use crate::lite::error::{Error, Kind};
 use crate::lite::types::ValidatorSet as _;
 use crate::validator::Set;
@@ -140,19 +141,14 @@ fn non_absent_votes(commit: &block::Commit) -> Vec<vote::Vote> {
 impl block::signed_header::SignedHeader {
     /// This is a private helper method to iterate over the underlying
     /// votes to compute the voting power (see `voting_power_in` below).
+    /// The votes are canonicalized with the encoding of the Tendermint
+    /// version given by the block protocol version of the header.
     pub fn signed_votes(&self) -> Vec<vote::SignedVote> {
         let chain_id = self.header.chain_id.to_string();
-        let mut votes = non_absent_votes(&self.commit);
-        votes
-            .drain(..)
-            .map(|vote| {
-                vote::SignedVote::new(
-                    (&vote).into(),
-                    &chain_id,
-                    vote.validator_address,
-                    vote.signature,
-                )
-            })
+        let encoding = Encoding::from(&self.header.version);
+        non_absent_votes(&self.commit)
+            .iter()
+            .map(|vote| vote::SignedVote::from_vote(vote, &chain_id, encoding))
             .collect()
     }
 }
@@ -176,3 +172,159 @@ impl From<&block::signed_header::SignedHeader>
         Self::new(sh.clone(), sh.clone().header)
     }
 }
+
+#[cfg(all(test, feature = "secp256k1"))]
+mod tests {
+    use super::*;
+    use crate::block::{header::Version, parts, Commit, CommitSigs, Header, Height, Id};
+    use crate::hash::{Algorithm, Hash};
+    use crate::lite::Commit as _;
+    use crate::{validator, PublicKey, Signature, Time};
+    use signatory::ecdsa::curve::secp256k1;
+    use signatory::ed25519;
+    use signatory::signature::Signature as _;
+    use subtle_encoding::hex;
+
+    // The following vectors were generated by signing the amino encoded canonical
+    // precommit for `BLOCK_HASH` and `PARTS_HASH` at height 1, round 0, on
+    // `test-chain`, at `2020-01-01T00:00:00Z`.
+    const SIGN_BYTES: &str = "69080211010000000000000022480A20496ACA80E4D8F29FB8E8CD816C3AFB48\
+                              D3F103970B3A2EE1600C08CA67326DEE12240A20D887DB09649DAB0D83951D8D\
+                              5D69B2E7D8BB70E79DAA2A3A279B4FD6B8346CEA10012A060880C2AFF005320A\
+                              746573742D636861696E";
+    const BLOCK_HASH: &str = "496ACA80E4D8F29FB8E8CD816C3AFB48D3F103970B3A2EE1600C08CA67326DEE";
+    const PARTS_HASH: &str = "D887DB09649DAB0D83951D8D5D69B2E7D8BB70E79DAA2A3A279B4FD6B8346CEA";
+
+    const ED25519_PUBKEY: &str = "03A107BFF3CE10BE1D70DD18E74BC09967E4D6309BA50D5F1DDC8664125531B8";
+    const ED25519_SIGNATURE: &str = "2365CDDFFEC3E743CE9930D9DB4A261742BF08E4AD063AB0334E4B28A3D5AF53\
+                                     75AF3E6DB34104A18D8CED9D6BD50A93F12BD6A8F01975ABF1AF3E8999F44503";
+
+    const SECP256K1_PUBKEY: &str =
+        "02BB50E2D89A4ED70663D080659FE0AD4B9BC3E06C17A227433966CB59CEEE020D";
+    const SECP256K1_SIGNATURE: &str = "2F45577CDA3E67C2554B53FFEAA6265098ECE99C4AE049E2C508824610DB282A\
+                                       3F806C955BF9841CA7178A28D31EC54433BDFC522054B61130BCD59C71647FE1";
+
+    fn hash(hash: &str) -> Hash {
+        Hash::from_hex_upper(Algorithm::Sha256, hash).unwrap()
+    }
+
+    fn ed25519_signature(signature: &str) -> Signature {
+        let bytes = hex::decode_upper(signature).unwrap();
+        Signature::Ed25519(ed25519::Signature::from_bytes(&bytes).unwrap())
+    }
+
+    fn secp256k1_signature(signature: &str) -> Signature {
+        let bytes = hex::decode_upper(signature).unwrap();
+        Signature::Secp256k1(secp256k1::FixedSignature::from_bytes(&bytes).unwrap())
+    }
+
+    fn make_validators() -> (validator::Info, validator::Info) {
+        let ed25519_pk =
+            PublicKey::from_raw_ed25519(&hex::decode_upper(ED25519_PUBKEY).unwrap()).unwrap();
+        let secp256k1_pk =
+            PublicKey::from_raw_secp256k1(&hex::decode_upper(SECP256K1_PUBKEY).unwrap()).unwrap();
+
+        (
+            validator::Info::new(ed25519_pk, vote::Power::new(10)),
+            validator::Info::new(secp256k1_pk, vote::Power::new(20)),
+        )
+    }
+
+    fn make_signed_header(
+        ed25519_validator: &validator::Info,
+        ed25519_signature: Signature,
+        secp256k1_validator: &validator::Info,
+        secp256k1_signature: Signature,
+    ) -> block::signed_header::SignedHeader {
+        let time = Time::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap();
+
+        let header = Header {
+            version: Version { block: 10, app: 0 },
+            chain_id: "test-chain".parse().unwrap(),
+            height: Height::from(1_u64),
+            time,
+            last_block_id: None,
+            last_commit_hash: None,
+            data_hash: None,
+            validators_hash: hash(BLOCK_HASH),
+            next_validators_hash: hash(BLOCK_HASH),
+            consensus_hash: hash(BLOCK_HASH),
+            app_hash: vec![],
+            last_results_hash: None,
+            evidence_hash: None,
+            proposer_address: ed25519_validator.address,
+        };
+
+        let commit = Commit {
+            height: Height::from(1_u64),
+            round: 0,
+            block_id: Id::new(
+                hash(BLOCK_HASH),
+                Some(parts::Header::new(1, hash(PARTS_HASH))),
+            ),
+            signatures: CommitSigs::new(vec![
+                CommitSig::BlockIDFlagCommit {
+                    validator_address: ed25519_validator.address,
+                    timestamp: time,
+                    signature: ed25519_signature,
+                },
+                CommitSig::BlockIDFlagCommit {
+                    validator_address: secp256k1_validator.address,
+                    timestamp: time,
+                    signature: secp256k1_signature,
+                },
+            ]),
+        };
+
+        block::signed_header::SignedHeader { header, commit }
+    }
+
+    #[test]
+    fn sign_bytes_of_mixed_key_commit() {
+        let (ed25519_validator, secp256k1_validator) = make_validators();
+        let signed_header = make_signed_header(
+            &ed25519_validator,
+            ed25519_signature(ED25519_SIGNATURE),
+            &secp256k1_validator,
+            secp256k1_signature(SECP256K1_SIGNATURE),
+        );
+
+        let want = hex::decode_upper(SIGN_BYTES).unwrap();
+        for vote in signed_header.signed_votes() {
+            assert_eq!(vote.sign_bytes(), want);
+        }
+    }
+
+    #[test]
+    fn verify_commit_signed_by_mixed_key_validators() {
+        let (ed25519_validator, secp256k1_validator) = make_validators();
+        let signed_header = make_signed_header(
+            &ed25519_validator,
+            ed25519_signature(ED25519_SIGNATURE),
+            &secp256k1_validator,
+            secp256k1_signature(SECP256K1_SIGNATURE),
+        );
+
+        let validators = Set::new(vec![ed25519_validator, secp256k1_validator]);
+
+        assert!(signed_header.validate(&validators).is_ok());
+        assert_eq!(signed_header.voting_power_in(&validators).unwrap(), 30);
+    }
+
+    #[test]
+    fn reject_commit_with_signatures_of_the_wrong_key_type() {
+        let (ed25519_validator, secp256k1_validator) = make_validators();
+
+        // Signatures are interpreted according to the validator's key type
+        let signed_header = make_signed_header(
+            &ed25519_validator,
+            ed25519_signature(SECP256K1_SIGNATURE),
+            &secp256k1_validator,
+            ed25519_signature(ED25519_SIGNATURE),
+        );
+
+        let validators = Set::new(vec![ed25519_validator, secp256k1_validator]);
+
+        assert!(signed_header.voting_power_in(&validators).is_err());
+    }
+}
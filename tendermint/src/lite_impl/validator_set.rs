@@ -1,15 +1,29 @@
 //! [`lite::ValidatorSet`] implementation for [`validator::Set`].
 
+use crate::encoding::Encoding;
 use crate::validator;
 use crate::{lite, merkle, Hash};
 
 impl lite::ValidatorSet for validator::Set {
-    /// Compute the Merkle root of the validator set
-    fn hash(&self) -> Hash {
-        let validator_bytes: Vec<Vec<u8>> = self
-            .validators()
-            .iter()
-            .map(|validator| validator.hash_bytes())
+    /// Compute the Merkle root of the validator set, using the given encoding
+    /// for its validators.
+    ///
+    /// As of Tendermint v0.34, the validators are hashed by decreasing voting
+    /// power (then by address), rather than by address.
+    fn hash_with_encoding(&self, encoding: Encoding) -> Hash {
+        let mut validators: Vec<&validator::Info> = self.validators().iter().collect();
+
+        if encoding == Encoding::Protobuf {
+            validators.sort_by(|a, b| {
+                b.voting_power
+                    .cmp(&a.voting_power)
+                    .then_with(|| a.address.cmp(&b.address))
+            });
+        }
+
+        let validator_bytes: Vec<Vec<u8>> = validators
+            .into_iter()
+            .map(|validator| validator.hash_bytes_with_encoding(encoding))
             .collect();
         Hash::Sha256(merkle::simple_hash_from_byte_vectors(validator_bytes))
     }
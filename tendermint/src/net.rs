@@ -1,4 +1,4 @@
-//! Remote addresses (`tcp://` or `unix://`)
+//! Remote addresses (`tcp://`, `http(s)://`, `ws(s)://` or `unix://`)
 
 use crate::{
     error::{Error, Kind},
@@ -16,14 +16,100 @@ use std::{
 /// URI prefix for TCP connections
 pub const TCP_PREFIX: &str = "tcp://";
 
+/// URI prefix for HTTP connections
+pub const HTTP_PREFIX: &str = "http://";
+
+/// URI prefix for HTTP connections secured with TLS
+pub const HTTPS_PREFIX: &str = "https://";
+
+/// URI prefix for WebSocket connections
+pub const WS_PREFIX: &str = "ws://";
+
+/// URI prefix for WebSocket connections secured with TLS
+pub const WSS_PREFIX: &str = "wss://";
+
 /// URI prefix for Unix socket connections
 pub const UNIX_PREFIX: &str = "unix://";
 
+/// Scheme of a TCP-based address
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Scheme {
+    /// Plain TCP (`tcp://`)
+    Tcp,
+
+    /// HTTP (`http://`)
+    Http,
+
+    /// HTTP over TLS (`https://`)
+    Https,
+
+    /// WebSocket (`ws://`)
+    Ws,
+
+    /// WebSocket over TLS (`wss://`)
+    Wss,
+}
+
+impl Scheme {
+    /// All TCP-based schemes, in the order they are matched when parsing
+    const ALL: [Scheme; 5] = [
+        Scheme::Tcp,
+        Scheme::Http,
+        Scheme::Https,
+        Scheme::Ws,
+        Scheme::Wss,
+    ];
+
+    /// URI prefix for this scheme
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Scheme::Tcp => TCP_PREFIX,
+            Scheme::Http => HTTP_PREFIX,
+            Scheme::Https => HTTPS_PREFIX,
+            Scheme::Ws => WS_PREFIX,
+            Scheme::Wss => WSS_PREFIX,
+        }
+    }
+
+    /// Is this scheme secured with TLS?
+    pub fn is_secure(self) -> bool {
+        match self {
+            Scheme::Https | Scheme::Wss => true,
+            Scheme::Tcp | Scheme::Http | Scheme::Ws => false,
+        }
+    }
+
+    /// Port implied by this scheme when none is given explicitly
+    pub fn default_port(self) -> Option<u16> {
+        match self {
+            Scheme::Http | Scheme::Ws => Some(80),
+            Scheme::Https | Scheme::Wss => Some(443),
+            Scheme::Tcp => None,
+        }
+    }
+}
+
+impl Default for Scheme {
+    fn default() -> Self {
+        Scheme::Tcp
+    }
+}
+
+impl Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = self.prefix();
+        write!(f, "{}", &prefix[..prefix.len() - "://".len()])
+    }
+}
+
 /// Remote address (TCP or UNIX socket)
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Address {
     /// TCP connections
     Tcp {
+        /// URI scheme the address was given with
+        scheme: Scheme,
+
         /// Remote peer ID
         peer_id: Option<node::Id>,
 
@@ -51,7 +137,9 @@ impl<'de> Deserialize<'de> for Address {
 impl Display for Address {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Address::Tcp { host, port, .. } => write!(f, "{}{}:{}", TCP_PREFIX, host, port),
+            Address::Tcp {
+                scheme, host, port, ..
+            } => write!(f, "{}{}:{}", scheme.prefix(), host, port),
             Address::Unix { path } => write!(f, "{}{}", UNIX_PREFIX, path.display()),
         }
     }
@@ -61,27 +149,41 @@ impl FromStr for Address {
     type Err = Error;
 
     fn from_str(addr: &str) -> Result<Self, Error> {
-        if addr.starts_with(TCP_PREFIX) {
-            Self::parse_tcp_addr(&addr[TCP_PREFIX.len()..])
-        } else if addr.starts_with(UNIX_PREFIX) {
-            Ok(Address::Unix {
+        if addr.starts_with(UNIX_PREFIX) {
+            return Ok(Address::Unix {
                 path: PathBuf::from(&addr[UNIX_PREFIX.len()..]),
-            })
-        } else if addr.contains("://") {
-            // The only supported URI prefixes are `tcp://` and `unix://`
+            });
+        }
+
+        for &scheme in Scheme::ALL.iter() {
+            if addr.starts_with(scheme.prefix()) {
+                return Self::parse_tcp_addr(scheme, &addr[scheme.prefix().len()..]);
+            }
+        }
+
+        if addr.contains("://") {
+            // The only supported URI prefixes are the TCP-based schemes and `unix://`
             fail!(Kind::Parse, "invalid address prefix: {:?}", addr)
         } else {
             // If the address has no URI prefix, assume TCP
-            Self::parse_tcp_addr(addr)
+            Self::parse_tcp_addr(Scheme::Tcp, addr)
         }
     }
 }
 
 impl Address {
-    /// Parse a TCP address (without a `tcp://` prefix).
+    /// Get the URI scheme of this address (`None` for UNIX sockets)
+    pub fn scheme(&self) -> Option<Scheme> {
+        match self {
+            Address::Tcp { scheme, .. } => Some(*scheme),
+            Address::Unix { .. } => None,
+        }
+    }
+
+    /// Parse a TCP address (without its URI prefix).
     ///
     /// This is used internally by `Address::from_str`.
-    fn parse_tcp_addr(addr: &str) -> Result<Self, Error> {
+    fn parse_tcp_addr(scheme: Scheme, addr: &str) -> Result<Self, Error> {
         // TODO(tarcieri): use the `uri` (or other) crate for this
         let authority_parts = addr.split('@').collect::<Vec<_>>();
 
@@ -91,35 +193,38 @@ impl Address {
             _ => fail!(
                 Kind::Parse,
                 "invalid {} address (bad authority): {}",
-                TCP_PREFIX,
+                scheme.prefix(),
                 addr
             ),
         };
 
+        // Tolerate a trailing slash, e.g. `https://rpc.example.com/`
+        let authority = authority.trim_end_matches('/');
         let host_and_port: Vec<&str> = authority.split(':').collect();
 
-        if host_and_port.len() != 2 {
-            fail!(
-                Kind::Parse,
-                "invalid {} address (missing port): {}",
-                TCP_PREFIX,
-                addr
-            );
-        }
-
         // TODO(tarcieri): default for missing hostname?
         let host = host_and_port[0].to_owned();
 
-        let port = host_and_port[1].parse::<u16>().map_err(|_| {
-            format_err!(
+        let port = match (host_and_port.len(), scheme.default_port()) {
+            (1, Some(default_port)) => default_port,
+            (2, _) => host_and_port[1].parse::<u16>().map_err(|_| {
+                format_err!(
+                    Kind::Parse,
+                    "invalid {} address (bad port): {}",
+                    scheme.prefix(),
+                    addr
+                )
+            })?,
+            _ => fail!(
                 Kind::Parse,
-                "invalid {} address (bad port): {}",
-                TCP_PREFIX,
+                "invalid {} address (missing port): {}",
+                scheme.prefix(),
                 addr
-            )
-        })?;
+            ),
+        };
 
         Ok(Address::Tcp {
+            scheme,
             peer_id,
             host,
             port,
@@ -149,10 +254,12 @@ mod tests {
         for tcp_addr in &[EXAMPLE_TCP_ADDR, tcp_addr_without_prefix] {
             match tcp_addr.parse::<Address>().unwrap() {
                 Address::Tcp {
+                    scheme,
                     peer_id,
                     host,
                     port,
                 } => {
+                    assert_eq!(scheme, Scheme::Tcp);
                     assert_eq!(
                         peer_id.unwrap(),
                         "abd636b766dcefb5322d8ca40011ec2cb35efbc2"
@@ -166,4 +273,29 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn parse_tls_addrs() {
+        let https = "https://rpc.example.com:8443".parse::<Address>().unwrap();
+        assert_eq!(https.scheme(), Some(Scheme::Https));
+        assert_eq!(https.to_string(), "https://rpc.example.com:8443");
+
+        match "wss://rpc.example.com/".parse::<Address>().unwrap() {
+            Address::Tcp {
+                scheme, host, port, ..
+            } => {
+                assert_eq!(scheme, Scheme::Wss);
+                assert!(scheme.is_secure());
+                assert_eq!(host, "rpc.example.com");
+                assert_eq!(port, 443);
+            }
+            other => panic!("unexpected address type: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn tcp_addr_requires_port() {
+        assert!("tcp://127.0.0.1".parse::<Address>().is_err());
+        assert!("ftp://127.0.0.1:21".parse::<Address>().is_err());
+    }
 }
@@ -1,9 +1,11 @@
 //! Cryptographic private keys
 
 use crate::public_key::PublicKey;
+use crate::signature::Signature;
 use serde::{de, de::Error as _, ser, Deserialize, Serialize};
 use signatory::ed25519;
 use signatory::public_key::PublicKeyed;
+use signatory::signature::Signer;
 use signatory_dalek::Ed25519Signer;
 use subtle_encoding::{Base64, Encoding};
 use zeroize::{Zeroize, Zeroizing};
@@ -34,6 +36,19 @@ impl PrivateKey {
             PrivateKey::Ed25519(keypair) => Some(keypair),
         }
     }
+
+    /// Sign the given message with this private key
+    pub fn sign(&self, msg: &[u8]) -> Signature {
+        match self {
+            PrivateKey::Ed25519(keypair) => Signature::Ed25519(keypair.to_signer().sign(msg)),
+        }
+    }
+}
+
+impl From<Ed25519Keypair> for PrivateKey {
+    fn from(keypair: Ed25519Keypair) -> PrivateKey {
+        PrivateKey::Ed25519(keypair)
+    }
 }
 
 /// Ed25519 keypairs
@@ -63,6 +78,18 @@ impl Ed25519Keypair {
     }
 }
 
+impl<'a> From<&'a ed25519::Seed> for Ed25519Keypair {
+    fn from(seed: &'a ed25519::Seed) -> Ed25519Keypair {
+        let public_key = Ed25519Signer::from(seed).public_key().unwrap();
+
+        let mut keypair_bytes = [0u8; ED25519_KEYPAIR_SIZE];
+        keypair_bytes[..32].copy_from_slice(seed.as_secret_slice());
+        keypair_bytes[32..].copy_from_slice(public_key.as_bytes());
+
+        Ed25519Keypair(keypair_bytes)
+    }
+}
+
 impl<'a> From<&'a Ed25519Keypair> for ed25519::Seed {
     fn from(keypair: &'a Ed25519Keypair) -> ed25519::Seed {
         ed25519::Seed::from_keypair(&keypair.0[..]).unwrap()
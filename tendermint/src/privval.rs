@@ -0,0 +1,30 @@
+//! Remote signer (privval) protocol
+//!
+//! Validators may delegate the signing of their votes and proposals to a remote
+//! signer. The validator node sends requests to the signer over a connection, as
+//! amino messages prefixed with their length, and the signer replies to each request
+//! in turn:
+//!
+//! - [`Signer`] answers the requests using a [`PrivateKey`], and keeps track of the last
+//!   signed height, round and step in order to never double sign
+//! - [`SignerClient`] sends requests on behalf of the validator node
+//!
+//! Both work over any bidirectional byte stream, such as the [`Connection`]s
+//! established over UNIX domain sockets, or over TCP with a secret connection.
+//!
+//! [`PrivateKey`]: ../private_key/enum.PrivateKey.html
+//! [`Signer`]: struct.Signer.html
+//! [`SignerClient`]: struct.SignerClient.html
+//! [`Connection`]: enum.Connection.html
+
+mod client;
+mod connection;
+mod message;
+mod signer;
+
+pub use self::{
+    client::SignerClient,
+    connection::Connection,
+    message::{Request, Response, MAX_MSG_LEN},
+    signer::Signer,
+};
@@ -0,0 +1,115 @@
+//! Client of a remote signer, for use by a validator node
+
+use super::message::{Request, Response};
+use crate::amino_types::{
+    self, remote_error::RemoteErrorCode, PingRequest, PubKeyRequest, RemoteError,
+    SignProposalRequest, SignVoteRequest,
+};
+use crate::error::{Error, Kind};
+use crate::{Proposal, PublicKey, Signature, Vote};
+use anomaly::{fail, format_err};
+use signatory::ed25519;
+use std::io::{Read, Write};
+
+/// Client sending the requests of a validator node to its remote signer,
+/// and awaiting each response in turn.
+pub struct SignerClient<C> {
+    conn: C,
+}
+
+impl<C: Read + Write> SignerClient<C> {
+    /// Create a client sending requests over the given connection
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    /// Get the public key of the remote signer
+    pub fn public_key(&mut self) -> Result<PublicKey, Error> {
+        match self.request(Request::ShowPublicKey(PubKeyRequest {}))? {
+            Response::PublicKey(response) => {
+                if response.pub_key_ed25519.len() != ed25519::PUBLIC_KEY_SIZE {
+                    fail!(
+                        Kind::InvalidKey,
+                        "remote signer returned a public key of {} bytes",
+                        response.pub_key_ed25519.len()
+                    );
+                }
+
+                Ok(PublicKey::from(response))
+            }
+            response => fail!(Kind::Protocol, "unexpected response: {:?}", response),
+        }
+    }
+
+    /// Check that the remote signer is alive
+    pub fn ping(&mut self) -> Result<(), Error> {
+        match self.request(Request::Ping(PingRequest {}))? {
+            Response::Ping(_) => Ok(()),
+            response => fail!(Kind::Protocol, "unexpected response: {:?}", response),
+        }
+    }
+
+    /// Have the remote signer sign the given vote, and return it along with its signature
+    pub fn sign_vote(&mut self, vote: &Vote) -> Result<Vote, Error> {
+        let request = Request::SignVote(SignVoteRequest {
+            vote: Some(amino_types::vote::Vote::from(vote)),
+        });
+
+        let signature = match self.request(request)? {
+            Response::SignedVote(response) => match (response.vote, response.err) {
+                (_, Some(err)) => return Err(remote_error(err)),
+                (Some(signed), None) => parse_signature(&signed.signature)?,
+                (None, None) => fail!(Kind::Protocol, "remote signer returned no vote"),
+            },
+            response => fail!(Kind::Protocol, "unexpected response: {:?}", response),
+        };
+
+        Ok(Vote {
+            signature,
+            ..vote.clone()
+        })
+    }
+
+    /// Have the remote signer sign the given proposal, and return it along with its signature
+    pub fn sign_proposal(&mut self, proposal: &Proposal) -> Result<Proposal, Error> {
+        let request = Request::SignProposal(SignProposalRequest {
+            proposal: Some(amino_types::proposal::Proposal::from(proposal)),
+        });
+
+        let signature = match self.request(request)? {
+            Response::SignedProposal(response) => match (response.proposal, response.err) {
+                (_, Some(err)) => return Err(remote_error(err)),
+                (Some(signed), None) => parse_signature(&signed.signature)?,
+                (None, None) => fail!(Kind::Protocol, "remote signer returned no proposal"),
+            },
+            response => fail!(Kind::Protocol, "unexpected response: {:?}", response),
+        };
+
+        Ok(Proposal {
+            signature,
+            ..proposal.clone()
+        })
+    }
+
+    fn request(&mut self, request: Request) -> Result<Response, Error> {
+        request.write(&mut self.conn)?;
+        Response::read(&mut self.conn)
+    }
+}
+
+fn parse_signature(bytes: &[u8]) -> Result<Signature, Error> {
+    let signature = ed25519::Signature::from_bytes(bytes)
+        .map_err(|e| format_err!(Kind::SignatureInvalid, "{}", e))?;
+
+    Ok(Signature::Ed25519(signature))
+}
+
+fn remote_error(err: RemoteError) -> Error {
+    let kind = if err.code == RemoteErrorCode::DoubleSignError as i32 {
+        Kind::DoubleSign
+    } else {
+        Kind::Protocol
+    };
+
+    format_err!(kind, "remote signer error: {}", err.description).into()
+}
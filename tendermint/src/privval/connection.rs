@@ -0,0 +1,178 @@
+//! Connections between a validator node and its remote signer
+
+use crate::error::{Error, Kind};
+use crate::net;
+use crate::private_key::Ed25519Keypair;
+use crate::secret_connection::SecretConnection;
+use anomaly::{fail, format_err};
+use futures::executor::block_on;
+use std::fmt::{self, Debug};
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+/// Connection to a validator node or to a remote signer.
+///
+/// As in Tendermint, TCP connections are authenticated and encrypted with a
+/// [`SecretConnection`], while UNIX domain socket connections are not.
+///
+/// [`SecretConnection`]: ../secret_connection/struct.SecretConnection.html
+pub enum Connection {
+    /// Secret connection over TCP
+    Tcp(Box<SecretConnection<Blocking<TcpStream>>>),
+
+    /// UNIX domain socket connection
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Connection {
+    /// Connect to the given address, authenticating ourselves with the given key
+    /// if it is a TCP address.
+    ///
+    /// In Tendermint, the validator node listens on its `priv_validator_laddr`,
+    /// and the remote signer connects to it. TCP addresses must use the `tcp://`
+    /// scheme, and if they include the ID of the node (`tcp://<id>@<host>:<port>`),
+    /// the remote peer must be authenticated by its key.
+    pub fn connect(address: &net::Address, identity: &Ed25519Keypair) -> Result<Self, Error> {
+        match address {
+            net::Address::Tcp {
+                scheme,
+                peer_id,
+                host,
+                port,
+            } => {
+                if *scheme != net::Scheme::Tcp {
+                    fail!(
+                        Kind::Io,
+                        "unsupported scheme for a remote signer connection: {}",
+                        address
+                    );
+                }
+
+                let stream = TcpStream::connect((host.as_str(), *port))
+                    .map_err(|e| format_err!(Kind::Io, "couldn't connect to {}: {}", address, e))?;
+
+                let conn = block_on(SecretConnection::new(Blocking(stream), identity))?;
+
+                if let Some(peer_id) = peer_id {
+                    conn.verify_remote_node_id(*peer_id)?;
+                }
+
+                Ok(Connection::Tcp(Box::new(conn)))
+            }
+            #[cfg(unix)]
+            net::Address::Unix { path } => {
+                UnixStream::connect(path)
+                    .map(Connection::Unix)
+                    .map_err(|e| {
+                        format_err!(Kind::Io, "couldn't connect to {}: {}", address, e).into()
+                    })
+            }
+            #[cfg(not(unix))]
+            net::Address::Unix { .. } => Err(format_err!(
+                Kind::Io,
+                "UNIX domain sockets are not supported on this platform"
+            )
+            .into()),
+        }
+    }
+
+    /// Establish a secret connection over the given TCP stream, on either side
+    /// of it, authenticating ourselves with the given key.
+    pub fn secret(stream: TcpStream, identity: &Ed25519Keypair) -> Result<Self, Error> {
+        let conn = block_on(SecretConnection::new(Blocking(stream), identity))?;
+        Ok(Connection::Tcp(Box::new(conn)))
+    }
+}
+
+#[cfg(unix)]
+impl From<UnixStream> for Connection {
+    fn from(stream: UnixStream) -> Self {
+        Connection::Unix(stream)
+    }
+}
+
+impl Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Connection::Tcp(conn) => f.debug_tuple("Tcp").field(&conn.remote_node_id()).finish(),
+            #[cfg(unix)]
+            Connection::Unix(stream) => f.debug_tuple("Unix").field(stream).finish(),
+        }
+    }
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Connection::Tcp(conn) => block_on(conn.read(buf)).map_err(io_error),
+            #[cfg(unix)]
+            Connection::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Connection::Tcp(conn) => block_on(conn.write_all(buf))
+                .map(|_| buf.len())
+                .map_err(io_error),
+            #[cfg(unix)]
+            Connection::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            // Secret connections flush the frames they write
+            Connection::Tcp(_) => Ok(()),
+            #[cfg(unix)]
+            Connection::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+fn io_error(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e)
+}
+
+/// Adapter of a blocking stream to the asynchronous I/O traits expected by
+/// [`SecretConnection`], whose operations are thus always ready.
+///
+/// [`SecretConnection`]: ../secret_connection/struct.SecretConnection.html
+#[derive(Debug)]
+pub struct Blocking<S>(S);
+
+impl<S: Read + Unpin> AsyncRead for Blocking<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().0.read(buf))
+    }
+}
+
+impl<S: Write + Unpin> AsyncWrite for Blocking<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().0.write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().0.flush())
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,247 @@
+//! Requests and responses of the remote signer protocol
+
+use crate::amino_types::{
+    compute_prefix, PingRequest, PingResponse, PubKeyRequest, PubKeyResponse, SignProposalRequest,
+    SignVoteRequest, SignedProposalResponse, SignedVoteResponse, PING_PREFIX, PROPOSAL_PREFIX,
+    PUBKEY_PREFIX, VOTE_PREFIX,
+};
+use crate::error::{Error, Kind};
+use anomaly::fail;
+use once_cell::sync::Lazy;
+use prost_amino::Message;
+use std::io::{Read, Write};
+
+/// Maximum length of a message, as in Tendermint
+pub const MAX_MSG_LEN: usize = 1024 * 10;
+
+static SIGNED_VOTE_PREFIX: Lazy<Vec<u8>> =
+    Lazy::new(|| compute_prefix("tendermint/remotesigner/SignedVoteResponse"));
+static SIGNED_PROPOSAL_PREFIX: Lazy<Vec<u8>> =
+    Lazy::new(|| compute_prefix("tendermint/remotesigner/SignedProposalResponse"));
+static PUBKEY_RESPONSE_PREFIX: Lazy<Vec<u8>> =
+    Lazy::new(|| compute_prefix("tendermint/remotesigner/PubKeyResponse"));
+static PING_RESPONSE_PREFIX: Lazy<Vec<u8>> =
+    Lazy::new(|| compute_prefix("tendermint/remotesigner/PingResponse"));
+
+/// Requests sent by a validator node to its remote signer
+#[derive(Clone, Debug, PartialEq)]
+pub enum Request {
+    /// Sign a vote
+    SignVote(SignVoteRequest),
+    /// Sign a proposal
+    SignProposal(SignProposalRequest),
+    /// Get the public key of the signer
+    ShowPublicKey(PubKeyRequest),
+    /// Check that the signer is alive
+    Ping(PingRequest),
+}
+
+impl Request {
+    /// Read the next request from the given connection.
+    ///
+    /// Returns `None` if the connection was closed before a new request started.
+    pub fn read(conn: &mut impl Read) -> Result<Option<Self>, Error> {
+        let (frame, prefix) = match read_frame(conn)? {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+
+        let request = if prefix == *VOTE_PREFIX {
+            Request::SignVote(SignVoteRequest::decode(frame.as_ref())?)
+        } else if prefix == *PROPOSAL_PREFIX {
+            Request::SignProposal(SignProposalRequest::decode(frame.as_ref())?)
+        } else if prefix == *PUBKEY_PREFIX {
+            Request::ShowPublicKey(PubKeyRequest::decode(frame.as_ref())?)
+        } else if prefix == *PING_PREFIX {
+            Request::Ping(PingRequest::decode(frame.as_ref())?)
+        } else {
+            fail!(Kind::Protocol, "unknown request prefix: {:X?}", prefix);
+        };
+
+        Ok(Some(request))
+    }
+
+    /// Write this request to the given connection
+    pub fn write(&self, conn: &mut impl Write) -> Result<(), Error> {
+        let mut buf = vec![];
+
+        match self {
+            Request::SignVote(request) => request.encode(&mut buf)?,
+            Request::SignProposal(request) => request.encode(&mut buf)?,
+            Request::ShowPublicKey(request) => request.encode(&mut buf)?,
+            Request::Ping(request) => request.encode(&mut buf)?,
+        }
+
+        write_frame(conn, &buf)
+    }
+}
+
+/// Responses of a remote signer to the requests of a validator node
+#[derive(Clone, Debug, PartialEq)]
+pub enum Response {
+    /// Signed vote, or the reason why it was not signed
+    SignedVote(SignedVoteResponse),
+    /// Signed proposal, or the reason why it was not signed
+    SignedProposal(SignedProposalResponse),
+    /// Public key of the signer
+    PublicKey(PubKeyResponse),
+    /// Reply to a ping
+    Ping(PingResponse),
+}
+
+impl Response {
+    /// Read the next response from the given connection
+    pub fn read(conn: &mut impl Read) -> Result<Self, Error> {
+        let (frame, prefix) = match read_frame(conn)? {
+            Some(frame) => frame,
+            None => fail!(Kind::Io, "connection closed while awaiting a response"),
+        };
+
+        let response = if prefix == *SIGNED_VOTE_PREFIX {
+            Response::SignedVote(SignedVoteResponse::decode(frame.as_ref())?)
+        } else if prefix == *SIGNED_PROPOSAL_PREFIX {
+            Response::SignedProposal(SignedProposalResponse::decode(frame.as_ref())?)
+        } else if prefix == *PUBKEY_RESPONSE_PREFIX {
+            Response::PublicKey(PubKeyResponse::decode(frame.as_ref())?)
+        } else if prefix == *PING_RESPONSE_PREFIX {
+            Response::Ping(PingResponse::decode(frame.as_ref())?)
+        } else {
+            fail!(Kind::Protocol, "unknown response prefix: {:X?}", prefix);
+        };
+
+        Ok(response)
+    }
+
+    /// Write this response to the given connection
+    pub fn write(&self, conn: &mut impl Write) -> Result<(), Error> {
+        let mut buf = vec![];
+
+        match self {
+            Response::SignedVote(response) => response.encode(&mut buf)?,
+            Response::SignedProposal(response) => response.encode(&mut buf)?,
+            Response::PublicKey(response) => response.encode(&mut buf)?,
+            Response::Ping(response) => response.encode(&mut buf)?,
+        }
+
+        write_frame(conn, &buf)
+    }
+}
+
+/// Read a message prefixed with its length as an unsigned varint, and return it
+/// whole, along with its amino prefix.
+///
+/// Returns `None` if the connection was closed before the message started.
+fn read_frame(conn: &mut impl Read) -> Result<Option<(Vec<u8>, Vec<u8>)>, Error> {
+    let mut frame = vec![];
+    let mut len = 0usize;
+
+    loop {
+        let mut byte = [0u8; 1];
+
+        if conn.read(&mut byte)? == 0 {
+            if frame.is_empty() {
+                return Ok(None);
+            }
+
+            fail!(Kind::Io, "connection closed while reading a message length");
+        }
+
+        frame.push(byte[0]);
+        len |= usize::from(byte[0] & 0x7f) << (7 * (frame.len() - 1));
+
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+
+        // Lengths of more than 3 bytes are way above the maximum message length
+        if frame.len() == 3 {
+            fail!(Kind::Length, "message length does not fit in 3 bytes");
+        }
+    }
+
+    if len > MAX_MSG_LEN {
+        fail!(
+            Kind::Length,
+            "message of {} bytes exceeds the maximum length of {} bytes",
+            len,
+            MAX_MSG_LEN
+        );
+    }
+
+    if len < 4 {
+        fail!(Kind::Length, "message of {} bytes is too short", len);
+    }
+
+    let header_len = frame.len();
+    frame.resize(header_len + len, 0);
+    conn.read_exact(&mut frame[header_len..])?;
+
+    let prefix = frame[header_len..header_len + 4].to_vec();
+    Ok(Some((frame, prefix)))
+}
+
+fn write_frame(conn: &mut impl Write, frame: &[u8]) -> Result<(), Error> {
+    if frame.len() > MAX_MSG_LEN {
+        fail!(
+            Kind::Length,
+            "message of {} bytes exceeds the maximum length of {} bytes",
+            frame.len(),
+            MAX_MSG_LEN
+        );
+    }
+
+    conn.write_all(frame)?;
+    conn.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_written_requests() {
+        let requests = vec![
+            Request::Ping(PingRequest {}),
+            Request::ShowPublicKey(PubKeyRequest {}),
+        ];
+
+        let mut buf = vec![];
+        for request in &requests {
+            request.write(&mut buf).unwrap();
+        }
+
+        // Test vector from `amino_types::ed25519`
+        assert_eq!(&buf[buf.len() - 5..], &[0x4, 0xcb, 0x94, 0xd6, 0x20]);
+
+        let mut conn = buf.as_slice();
+        for request in requests {
+            assert_eq!(Request::read(&mut conn).unwrap(), Some(request));
+        }
+        assert_eq!(Request::read(&mut conn).unwrap(), None);
+    }
+
+    #[test]
+    fn read_written_response() {
+        let response = Response::PublicKey(PubKeyResponse {
+            pub_key_ed25519: vec![0x79; 32],
+        });
+
+        let mut buf = vec![];
+        response.write(&mut buf).unwrap();
+
+        assert_eq!(Response::read(&mut buf.as_slice()).unwrap(), response);
+    }
+
+    #[test]
+    fn reject_unknown_prefix() {
+        let buf = [0x4, 0xde, 0xad, 0xbe, 0xef];
+        assert!(Request::read(&mut buf.as_ref()).is_err());
+    }
+
+    #[test]
+    fn reject_truncated_message() {
+        let buf = [0x5, 0xcb, 0x94, 0xd6, 0x20];
+        assert!(Request::read(&mut buf.as_ref()).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! Remote signer answering the requests of a validator node
+
+use super::message::{Request, Response};
+use crate::amino_types::{
+    remote_error::RemoteErrorCode, PingResponse, PubKeyResponse, RemoteError, SignableMsg,
+    SignedMsgType, SignedProposalResponse, SignedVoteResponse, TimeMsg,
+};
+use crate::config::PrivValidatorState;
+use crate::consensus::state::{STEP_PRECOMMIT, STEP_PREVOTE, STEP_PROPOSE};
+use crate::encoding::Encoding;
+use crate::error::{Error, Kind};
+use crate::{chain, PrivateKey, Signature};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Remote signer for a validator on a given chain.
+///
+/// The signer refuses to sign anything which would regress from, or conflict with,
+/// the last height, round and step it signed. When given a state file, the signer
+/// saves its state there before replying with any new signature.
+pub struct Signer {
+    chain_id: chain::Id,
+    private_key: PrivateKey,
+    state: PrivValidatorState,
+    state_file: Option<PathBuf>,
+}
+
+impl Signer {
+    /// Create a signer for the given chain, with the given key and last signed state
+    pub fn new(chain_id: chain::Id, private_key: PrivateKey, state: PrivValidatorState) -> Self {
+        Self {
+            chain_id,
+            private_key,
+            state,
+            state_file: None,
+        }
+    }
+
+    /// Save the state of this signer to the given file whenever it signs something
+    pub fn with_state_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.state_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Last state signed by this signer
+    pub fn state(&self) -> &PrivValidatorState {
+        &self.state
+    }
+
+    /// Answer the requests read from the given connection, until it is closed
+    pub fn serve<C: Read + Write>(&mut self, conn: &mut C) -> Result<(), Error> {
+        while let Some(request) = Request::read(conn)? {
+            self.handle(request).write(conn)?;
+        }
+
+        Ok(())
+    }
+
+    /// Answer the given request.
+    ///
+    /// Signing requests which are invalid, which would lead to a double sign, or
+    /// whose signature cannot be recorded in the state file, are answered with
+    /// a remote error.
+    pub fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::SignVote(mut request) => {
+                let err = self.sign(&mut request).err();
+                let vote = if err.is_none() { request.vote } else { None };
+                Response::SignedVote(SignedVoteResponse { vote, err })
+            }
+            Request::SignProposal(mut request) => {
+                let err = self.sign(&mut request).err();
+                let proposal = if err.is_none() {
+                    request.proposal
+                } else {
+                    None
+                };
+                Response::SignedProposal(SignedProposalResponse { proposal, err })
+            }
+            Request::ShowPublicKey(_) => {
+                Response::PublicKey(PubKeyResponse::from(self.private_key.public_key()))
+            }
+            Request::Ping(_) => Response::Ping(PingResponse {}),
+        }
+    }
+
+    /// Sign the given message, unless it is invalid or would lead to a double sign
+    fn sign(&mut self, msg: &mut impl SignableMsg) -> Result<(), RemoteError> {
+        msg.validate()
+            .map_err(|e| remote_signer_error(format!("invalid message: {}", e)))?;
+
+        let mut requested = msg
+            .consensus_state()
+            .ok_or_else(|| remote_signer_error("invalid message height".to_string()))?;
+
+        // The steps recorded in the signer state differ from the ones of the consensus
+        requested.step = match msg.msg_type() {
+            Some(SignedMsgType::Proposal) => STEP_PROPOSE,
+            Some(SignedMsgType::PreVote) => STEP_PREVOTE,
+            Some(SignedMsgType::PreCommit) => STEP_PRECOMMIT,
+            None => return Err(remote_signer_error("invalid message type".to_string())),
+        };
+
+        let mut sign_bytes = vec![];
+        msg.sign_bytes(self.chain_id, &mut sign_bytes)
+            .map_err(|e| remote_signer_error(format!("cannot encode sign bytes: {}", e)))?;
+
+        let mut state = self.state.clone();
+
+        // The remote signer protocol signs Amino encoded messages
+        let (signature, timestamp) = state
+            .sign(&requested, sign_bytes, Encoding::Amino, &self.private_key)
+            .map_err(|e| match e.downcast_ref::<anomaly::Context<Kind>>() {
+                Some(context) if *context.kind() == Kind::DoubleSign => {
+                    RemoteError::double_sign(requested.height.into())
+                }
+                _ => remote_signer_error(format!("cannot check the signer state: {}", e)),
+            })?;
+
+        if state != self.state {
+            if let Some(path) = &self.state_file {
+                state.save_json_file(path).map_err(|e| {
+                    remote_signer_error(format!("cannot save the signer state: {}", e))
+                })?;
+            }
+
+            self.state = state;
+        }
+
+        // When re-signing a message which only differs by its timestamp,
+        // the timestamp of the signed message is restored along with its signature
+        if let Some(timestamp) = timestamp {
+            msg.set_timestamp(TimeMsg::from(timestamp));
+        }
+
+        match signature {
+            Signature::Ed25519(signature) => msg.set_signature(&signature),
+            #[allow(unreachable_patterns)]
+            _ => return Err(remote_signer_error("unsupported key type".to_string())),
+        }
+
+        Ok(())
+    }
+}
+
+fn remote_signer_error(description: String) -> RemoteError {
+    RemoteError {
+        code: RemoteErrorCode::RemoteSignerError as i32,
+        description,
+    }
+}
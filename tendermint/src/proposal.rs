@@ -0,0 +1,125 @@
+//! Block proposals
+
+use crate::amino_types::{self, BlockId, SignableMsg, SignedMsgType, TimeMsg};
+use crate::encoding::Encoding;
+use crate::proto_types::{self, ProtoMessage};
+use crate::{block, chain, consensus, PrivateKey, Signature, Time};
+use std::convert::TryFrom;
+
+/// Proposals are signed by the proposer of a round, and propose a block
+/// (or re-propose a block locked in a previous round) to the other validators.
+///
+/// <https://github.com/tendermint/tendermint/blob/master/docs/spec/consensus/signing.md#proposals>
+#[derive(Clone, Debug, PartialEq)]
+pub struct Proposal {
+    /// Block height
+    pub height: block::Height,
+
+    /// Round
+    pub round: u64,
+
+    /// Round in which the proposed block was locked (proof-of-lock round), if any
+    pub pol_round: Option<u64>,
+
+    /// Block ID of the proposed block
+    pub block_id: Option<block::Id>,
+
+    /// Timestamp
+    pub timestamp: Time,
+
+    /// Signature
+    pub signature: Signature,
+}
+
+impl Proposal {
+    /// Height, round and step of this proposal, used to prevent double signing
+    pub fn consensus_state(&self) -> consensus::State {
+        consensus::State {
+            height: self.height,
+            round: self.round as i64,
+            step: consensus::state::STEP_PROPOSE,
+            block_id: self.block_id.clone(),
+        }
+    }
+
+    /// Compute the bytes to be signed for this proposal on the given chain,
+    /// canonicalized with the given encoding.
+    pub fn sign_bytes(&self, chain_id: &chain::Id, encoding: Encoding) -> Vec<u8> {
+        let proposal = amino_types::proposal::Proposal::from(self);
+
+        match encoding {
+            Encoding::Amino => {
+                let request = amino_types::SignProposalRequest {
+                    proposal: Some(proposal),
+                };
+                let mut sign_bytes = vec![];
+                request
+                    .sign_bytes(*chain_id, &mut sign_bytes)
+                    .expect("encoding into a vector cannot fail");
+                sign_bytes
+            }
+            Encoding::Protobuf => proto_types::CanonicalProposal::new(&proposal, chain_id.as_str())
+                .bytes_vec_length_delimited(),
+        }
+    }
+
+    /// Sign this proposal for the given chain with the given private key.
+    pub fn sign(
+        mut self,
+        private_key: &PrivateKey,
+        chain_id: &chain::Id,
+        encoding: Encoding,
+    ) -> Self {
+        self.signature = private_key.sign(&self.sign_bytes(chain_id, encoding));
+        self
+    }
+}
+
+impl From<&Proposal> for amino_types::proposal::Proposal {
+    fn from(proposal: &Proposal) -> Self {
+        amino_types::proposal::Proposal {
+            msg_type: SignedMsgType::Proposal.to_u32(),
+            height: i64::try_from(proposal.height.value()).expect("height overflow"),
+            round: i64::try_from(proposal.round).expect("round overflow"),
+            pol_round: proposal.pol_round.map_or(-1, |pol_round| {
+                i64::try_from(pol_round).expect("round overflow")
+            }),
+            block_id: proposal.block_id.as_ref().map(BlockId::from),
+            timestamp: Some(TimeMsg::from(proposal.timestamp)),
+            signature: proposal.signature.as_bytes().to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PrivValidatorKey;
+    use crate::{validator, vote};
+
+    #[test]
+    fn sign_proposal() {
+        let key = PrivValidatorKey::parse_json(include_str!(
+            "../tests/support/config/priv_validator_key.json"
+        ))
+        .unwrap();
+        let chain_id = "test-chain".parse().unwrap();
+
+        let proposal = Proposal {
+            height: block::Height::from(10_u64),
+            round: 1,
+            pol_round: None,
+            block_id: None,
+            timestamp: Time::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap(),
+            signature: Signature::Ed25519(signatory::ed25519::Signature::new([0; 64])),
+        };
+
+        let validator = validator::Info::new(key.pub_key, vote::Power::new(1));
+
+        for encoding in &[Encoding::Amino, Encoding::Protobuf] {
+            let proposal = proposal.clone().sign(&key.priv_key, &chain_id, *encoding);
+            let sign_bytes = proposal.sign_bytes(&chain_id, *encoding);
+            assert!(validator.verify_signature(&sign_bytes, proposal.signature.as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Message types serialized using the Protobuf encoding, which replaced Amino
+//! for canonical encodings as of Tendermint v0.34
+//! <https://github.com/tendermint/tendermint/tree/v0.34.0/proto/tendermint>
+
+#![allow(missing_docs)]
+
+pub mod block_id;
+pub mod commit;
+pub mod commit_sig;
+pub mod header;
+pub mod message;
+pub mod proposal;
+pub mod time;
+pub mod validator;
+pub mod version;
+pub mod vote;
+pub mod wrappers;
+
+pub use self::{
+    block_id::{BlockId, CanonicalBlockId, CanonicalPartSetHeader, PartSetHeader},
+    commit::Commit,
+    commit_sig::{BlockIdFlag, CommitSig},
+    header::Header,
+    message::ProtoMessage,
+    proposal::CanonicalProposal,
+    time::{Duration, Timestamp},
+    validator::{PublicKey, SimpleValidator},
+    version::Consensus,
+    vote::CanonicalVote,
+    wrappers::{BytesValue, Int64Value, StringValue},
+};
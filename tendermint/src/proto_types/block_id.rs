@@ -0,0 +1,118 @@
+use crate::block::{self, parts};
+use prost::Message;
+use std::convert::TryFrom;
+
+/// `tendermint.types.BlockID`
+#[derive(Clone, PartialEq, Message)]
+pub struct BlockId {
+    #[prost(bytes, tag = "1")]
+    pub hash: Vec<u8>,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "2")]
+    pub part_set_header: Option<PartSetHeader>,
+}
+
+impl BlockId {
+    pub fn new(hash: Vec<u8>, part_set_header: PartSetHeader) -> Self {
+        BlockId {
+            hash,
+            part_set_header: Some(part_set_header),
+        }
+    }
+}
+
+impl From<&block::Id> for BlockId {
+    fn from(bid: &block::Id) -> Self {
+        BlockId::new(
+            bid.hash.as_bytes().to_vec(),
+            bid.parts
+                .as_ref()
+                .map(PartSetHeader::from)
+                .unwrap_or_default(),
+        )
+    }
+}
+
+/// A missing block id is encoded as the zero value of the (non-nullable) `BlockID`.
+impl From<Option<&block::Id>> for BlockId {
+    fn from(bid: Option<&block::Id>) -> Self {
+        bid.map_or_else(
+            || BlockId::new(vec![], PartSetHeader::default()),
+            BlockId::from,
+        )
+    }
+}
+
+/// `tendermint.types.PartSetHeader`
+#[derive(Clone, PartialEq, Message)]
+pub struct PartSetHeader {
+    #[prost(uint32, tag = "1")]
+    pub total: u32,
+    #[prost(bytes, tag = "2")]
+    pub hash: Vec<u8>,
+}
+
+impl From<&parts::Header> for PartSetHeader {
+    fn from(parts: &parts::Header) -> Self {
+        PartSetHeader {
+            total: u32::try_from(parts.total).expect("part set total overflow"),
+            hash: parts.hash.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// `tendermint.types.CanonicalBlockID`
+#[derive(Clone, PartialEq, Message)]
+pub struct CanonicalBlockId {
+    #[prost(bytes, tag = "1")]
+    pub hash: Vec<u8>,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "2")]
+    pub part_set_header: Option<CanonicalPartSetHeader>,
+}
+
+impl CanonicalBlockId {
+    /// Canonicalize the given block id, which is left out of the canonical
+    /// messages if it is the zero value.
+    pub fn canonicalize(bid: BlockId) -> Option<CanonicalBlockId> {
+        let part_set_header = bid.part_set_header.unwrap_or_default();
+
+        if bid.hash.is_empty() && part_set_header == PartSetHeader::default() {
+            return None;
+        }
+
+        Some(CanonicalBlockId {
+            hash: bid.hash,
+            part_set_header: Some(CanonicalPartSetHeader {
+                total: part_set_header.total,
+                hash: part_set_header.hash,
+            }),
+        })
+    }
+}
+
+/// `tendermint.types.CanonicalPartSetHeader`
+#[derive(Clone, PartialEq, Message)]
+pub struct CanonicalPartSetHeader {
+    #[prost(uint32, tag = "1")]
+    pub total: u32,
+    #[prost(bytes, tag = "2")]
+    pub hash: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto_types::ProtoMessage;
+
+    #[test]
+    fn missing_block_id_encodes_empty_part_set_header() {
+        let got = ProtoMessage::bytes_vec(&BlockId::from(None));
+        assert_eq!(got, vec![0x12, 0x00]);
+    }
+
+    #[test]
+    fn zero_block_id_is_not_canonicalized() {
+        assert_eq!(CanonicalBlockId::canonicalize(BlockId::from(None)), None);
+    }
+}
@@ -0,0 +1,29 @@
+use super::{block_id::BlockId, commit_sig::CommitSig};
+use crate::block;
+use prost::Message;
+use std::convert::TryFrom;
+
+/// `tendermint.types.Commit`
+#[derive(Clone, PartialEq, Message)]
+pub struct Commit {
+    #[prost(int64, tag = "1")]
+    pub height: i64,
+    #[prost(int32, tag = "2")]
+    pub round: i32,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "3")]
+    pub block_id: Option<BlockId>,
+    #[prost(message, repeated, tag = "4")]
+    pub signatures: Vec<CommitSig>,
+}
+
+impl From<&block::Commit> for Commit {
+    fn from(commit: &block::Commit) -> Self {
+        Commit {
+            height: i64::try_from(commit.height.value()).expect("height overflow"),
+            round: i32::try_from(commit.round).expect("round overflow"),
+            block_id: Some(BlockId::from(&commit.block_id)),
+            signatures: commit.signatures.iter().map(CommitSig::from).collect(),
+        }
+    }
+}
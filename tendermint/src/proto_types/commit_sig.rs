@@ -0,0 +1,78 @@
+use super::time::Timestamp;
+use crate::block;
+use prost::Message;
+
+/// `tendermint.types.BlockIDFlag`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BlockIdFlag {
+    /// No vote was received from a validator
+    Absent = 1,
+    /// Voted for the `Commit.BlockID`
+    Commit = 2,
+    /// Voted for nil
+    Nil = 3,
+}
+
+/// `tendermint.types.CommitSig`
+#[derive(Clone, PartialEq, Message)]
+pub struct CommitSig {
+    #[prost(int32, tag = "1")]
+    pub block_id_flag: i32,
+    #[prost(bytes, tag = "2")]
+    pub validator_address: Vec<u8>,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "3")]
+    pub timestamp: Option<Timestamp>,
+    #[prost(bytes, tag = "4")]
+    pub signature: Vec<u8>,
+}
+
+impl From<&block::CommitSig> for CommitSig {
+    fn from(commit_sig: &block::CommitSig) -> Self {
+        match commit_sig {
+            block::CommitSig::BlockIDFlagAbsent => CommitSig {
+                block_id_flag: BlockIdFlag::Absent as i32,
+                validator_address: vec![],
+                timestamp: Some(Timestamp::zero()),
+                signature: vec![],
+            },
+            block::CommitSig::BlockIDFlagCommit {
+                validator_address,
+                timestamp,
+                signature,
+            } => CommitSig {
+                block_id_flag: BlockIdFlag::Commit as i32,
+                validator_address: validator_address.as_bytes().to_vec(),
+                timestamp: Some(Timestamp::from(*timestamp)),
+                signature: signature.as_bytes().to_vec(),
+            },
+            block::CommitSig::BlockIDFlagNil {
+                validator_address,
+                timestamp,
+                signature,
+            } => CommitSig {
+                block_id_flag: BlockIdFlag::Nil as i32,
+                validator_address: validator_address.as_bytes().to_vec(),
+                timestamp: Some(Timestamp::from(*timestamp)),
+                signature: signature.as_bytes().to_vec(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto_types::ProtoMessage;
+
+    #[test]
+    fn absent_commit_sig_encodes_zero_timestamp() {
+        let got = ProtoMessage::bytes_vec(&CommitSig::from(&block::CommitSig::BlockIDFlagAbsent));
+        let want = vec![
+            0x8, 0x1, // block_id_flag
+            0x1a, 0xb, // timestamp
+            0x8, 0x80, 0x92, 0xb8, 0xc3, 0x98, 0xfe, 0xff, 0xff, 0xff, 0x1,
+        ];
+        assert_eq!(got, want);
+    }
+}
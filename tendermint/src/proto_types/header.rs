@@ -0,0 +1,66 @@
+use super::{block_id::BlockId, time::Timestamp, version::Consensus};
+use crate::{block, Hash};
+use prost::Message;
+use std::convert::TryFrom;
+
+/// `tendermint.types.Header`
+#[derive(Clone, PartialEq, Message)]
+pub struct Header {
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "1")]
+    pub version: Option<Consensus>,
+    #[prost(string, tag = "2")]
+    pub chain_id: String,
+    #[prost(int64, tag = "3")]
+    pub height: i64,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "4")]
+    pub time: Option<Timestamp>,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "5")]
+    pub last_block_id: Option<BlockId>,
+    #[prost(bytes, tag = "6")]
+    pub last_commit_hash: Vec<u8>,
+    #[prost(bytes, tag = "7")]
+    pub data_hash: Vec<u8>,
+    #[prost(bytes, tag = "8")]
+    pub validators_hash: Vec<u8>,
+    #[prost(bytes, tag = "9")]
+    pub next_validators_hash: Vec<u8>,
+    #[prost(bytes, tag = "10")]
+    pub consensus_hash: Vec<u8>,
+    #[prost(bytes, tag = "11")]
+    pub app_hash: Vec<u8>,
+    #[prost(bytes, tag = "12")]
+    pub last_results_hash: Vec<u8>,
+    #[prost(bytes, tag = "13")]
+    pub evidence_hash: Vec<u8>,
+    #[prost(bytes, tag = "14")]
+    pub proposer_address: Vec<u8>,
+}
+
+impl From<&block::Header> for Header {
+    fn from(header: &block::Header) -> Self {
+        Header {
+            version: Some(Consensus::from(&header.version)),
+            chain_id: header.chain_id.as_str().to_string(),
+            height: i64::try_from(header.height.value()).expect("height overflow"),
+            time: Some(Timestamp::from(header.time)),
+            last_block_id: Some(BlockId::from(header.last_block_id.as_ref())),
+            last_commit_hash: hash_bytes(header.last_commit_hash.as_ref()),
+            data_hash: hash_bytes(header.data_hash.as_ref()),
+            validators_hash: header.validators_hash.as_bytes().to_vec(),
+            next_validators_hash: header.next_validators_hash.as_bytes().to_vec(),
+            consensus_hash: header.consensus_hash.as_bytes().to_vec(),
+            app_hash: header.app_hash.clone(),
+            last_results_hash: hash_bytes(header.last_results_hash.as_ref()),
+            evidence_hash: hash_bytes(header.evidence_hash.as_ref()),
+            proposer_address: header.proposer_address.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// A missing hash is encoded as empty bytes
+fn hash_bytes(hash: Option<&Hash>) -> Vec<u8> {
+    hash.map_or_else(Vec::new, |hash| hash.as_bytes().to_vec())
+}
@@ -0,0 +1,87 @@
+use super::{
+    block_id::{BlockId, CanonicalBlockId, PartSetHeader},
+    time::Timestamp,
+};
+use crate::amino_types::{self, SignedMsgType};
+use prost::Message;
+use std::convert::TryFrom;
+
+/// `tendermint.types.CanonicalProposal`, the message whose length delimited encoding
+/// is signed by proposers as of Tendermint v0.34.
+#[derive(Clone, PartialEq, Message)]
+pub struct CanonicalProposal {
+    #[prost(int32, tag = "1")]
+    pub msg_type: i32,
+    #[prost(sfixed64, tag = "2")]
+    pub height: i64,
+    #[prost(sfixed64, tag = "3")]
+    pub round: i64,
+    #[prost(int64, tag = "4")]
+    pub pol_round: i64,
+    #[prost(message, tag = "5")]
+    pub block_id: Option<CanonicalBlockId>,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "6")]
+    pub timestamp: Option<Timestamp>,
+    #[prost(string, tag = "7")]
+    pub chain_id: String,
+}
+
+impl CanonicalProposal {
+    pub fn new(proposal: &amino_types::proposal::Proposal, chain_id: &str) -> CanonicalProposal {
+        CanonicalProposal {
+            msg_type: i32::try_from(SignedMsgType::Proposal.to_u32()).unwrap(),
+            height: proposal.height,
+            round: proposal.round,
+            pol_round: proposal.pol_round,
+            block_id: proposal.block_id.as_ref().and_then(|bid| {
+                let part_set_header =
+                    bid.parts_header
+                        .as_ref()
+                        .map_or_else(PartSetHeader::default, |psh| PartSetHeader {
+                            total: u32::try_from(psh.total).expect("part set total overflow"),
+                            hash: psh.hash.clone(),
+                        });
+                CanonicalBlockId::canonicalize(BlockId::new(bid.hash.clone(), part_set_header))
+            }),
+            timestamp: Some(
+                proposal
+                    .timestamp
+                    .as_ref()
+                    .map_or_else(Timestamp::zero, |t| Timestamp {
+                        seconds: t.seconds,
+                        nanos: t.nanos,
+                    }),
+            ),
+            chain_id: chain_id.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto_types::ProtoMessage;
+
+    #[test]
+    fn encodes_proposal_type_and_pol_round() {
+        let proposal = amino_types::proposal::Proposal {
+            msg_type: SignedMsgType::Proposal.to_u32(),
+            height: 1,
+            round: 1,
+            pol_round: -1,
+            ..Default::default()
+        };
+
+        let got = CanonicalProposal::new(&proposal, "").bytes_vec();
+        let want = vec![
+            0x8, 0x20, // ProposalType
+            0x11, 0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, // height
+            0x19, 0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, // round
+            0x20, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x1, // pol_round
+            0x32, 0xb, // timestamp
+            0x8, 0x80, 0x92, 0xb8, 0xc3, 0x98, 0xfe, 0xff, 0xff, 0xff, 0x1,
+        ];
+        assert_eq!(got, want);
+    }
+}
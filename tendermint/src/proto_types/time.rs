@@ -0,0 +1,91 @@
+//! Timestamps and durations
+
+use crate::error::Error;
+use crate::time::{ParseTimestamp, Time};
+use chrono::{DateTime, TimeZone, Utc};
+use prost::Message;
+use std::{convert::TryFrom, time};
+
+/// `google.protobuf.Timestamp`
+#[derive(Clone, PartialEq, Message)]
+pub struct Timestamp {
+    #[prost(int64, tag = "1")]
+    pub seconds: i64,
+    #[prost(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+impl Timestamp {
+    /// The zero value of Go's `time.Time` ("0001-01-01 00:00:00 UTC"), which
+    /// is encoded whenever a non-nullable timestamp is missing.
+    pub fn zero() -> Self {
+        Timestamp {
+            seconds: -62_135_596_800,
+            nanos: 0,
+        }
+    }
+}
+
+impl ParseTimestamp for Timestamp {
+    fn parse_timestamp(&self) -> Result<Time, Error> {
+        Ok(Utc.timestamp(self.seconds, self.nanos as u32).into())
+    }
+}
+
+impl From<Time> for Timestamp {
+    fn from(ts: Time) -> Timestamp {
+        let dt: DateTime<Utc> = ts.into();
+
+        Timestamp {
+            seconds: dt.timestamp(),
+            nanos: dt.timestamp_subsec_nanos() as i32,
+        }
+    }
+}
+
+/// `google.protobuf.Duration`
+#[derive(Clone, PartialEq, Message)]
+pub struct Duration {
+    #[prost(int64, tag = "1")]
+    pub seconds: i64,
+    #[prost(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+impl From<time::Duration> for Duration {
+    fn from(duration: time::Duration) -> Duration {
+        Duration {
+            seconds: i64::try_from(duration.as_secs()).expect("duration overflow"),
+            nanos: duration.subsec_nanos() as i32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_time_matches_go_zero_value() {
+        let zero = Time::parse_from_rfc3339("0001-01-01T00:00:00Z").unwrap();
+        assert_eq!(Timestamp::from(zero), Timestamp::zero());
+    }
+
+    #[test]
+    fn converts_time_after_unix_epoch() {
+        let time = Time::parse_from_rfc3339("2017-12-25T03:00:01.234Z").unwrap();
+        assert_eq!(
+            Timestamp::from(time),
+            Timestamp {
+                seconds: 1_514_170_801,
+                nanos: 234_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_timestamp_back_to_time() {
+        let time = Time::parse_from_rfc3339("2017-12-25T03:00:01.234Z").unwrap();
+        assert_eq!(Timestamp::from(time).parse_timestamp().unwrap(), time);
+    }
+}
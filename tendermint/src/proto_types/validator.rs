@@ -0,0 +1,53 @@
+use crate::{validator, PublicKey as TmPublicKey};
+use prost::Message;
+use std::convert::TryFrom;
+
+/// `tendermint.crypto.PublicKey`
+#[derive(Clone, PartialEq, Message)]
+pub struct PublicKey {
+    #[prost(oneof = "public_key::Sum", tags = "1, 2")]
+    pub sum: Option<public_key::Sum>,
+}
+
+pub mod public_key {
+    use prost::Oneof;
+
+    #[derive(Clone, PartialEq, Oneof)]
+    pub enum Sum {
+        #[prost(bytes, tag = "1")]
+        Ed25519(Vec<u8>),
+        #[prost(bytes, tag = "2")]
+        Secp256k1(Vec<u8>),
+    }
+}
+
+impl From<&TmPublicKey> for PublicKey {
+    fn from(pk: &TmPublicKey) -> Self {
+        let sum = match pk {
+            TmPublicKey::Ed25519(pk) => public_key::Sum::Ed25519(pk.as_bytes().to_vec()),
+            #[cfg(feature = "secp256k1")]
+            TmPublicKey::Secp256k1(pk) => public_key::Sum::Secp256k1(pk.as_bytes().to_vec()),
+        };
+
+        PublicKey { sum: Some(sum) }
+    }
+}
+
+/// `tendermint.types.SimpleValidator`, the form of the validator used for
+/// computing the Merkle root of a validator set as of Tendermint v0.34.
+#[derive(Clone, PartialEq, Message)]
+pub struct SimpleValidator {
+    #[prost(message, tag = "1")]
+    pub pub_key: Option<PublicKey>,
+    #[prost(int64, tag = "2")]
+    pub voting_power: i64,
+}
+
+impl From<&validator::Info> for SimpleValidator {
+    fn from(info: &validator::Info) -> Self {
+        SimpleValidator {
+            pub_key: Some(PublicKey::from(&info.pub_key)),
+            voting_power: i64::try_from(info.voting_power.value()).expect("voting power overflow"),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use super::{
+    block_id::{BlockId, CanonicalBlockId},
+    time::Timestamp,
+};
+use crate::vote;
+use prost::Message;
+use std::convert::TryFrom;
+
+/// `tendermint.types.CanonicalVote`, the message whose length delimited encoding
+/// is signed by validators as of Tendermint v0.34.
+#[derive(Clone, PartialEq, Message)]
+pub struct CanonicalVote {
+    #[prost(int32, tag = "1")]
+    pub vote_type: i32,
+    #[prost(sfixed64, tag = "2")]
+    pub height: i64,
+    #[prost(sfixed64, tag = "3")]
+    pub round: i64,
+    #[prost(message, tag = "4")]
+    pub block_id: Option<CanonicalBlockId>,
+    /// Non-nullable, hence always encoded
+    #[prost(message, tag = "5")]
+    pub timestamp: Option<Timestamp>,
+    #[prost(string, tag = "6")]
+    pub chain_id: String,
+}
+
+impl CanonicalVote {
+    pub fn new(vote: &vote::Vote, chain_id: &str) -> CanonicalVote {
+        CanonicalVote {
+            vote_type: i32::from(vote.vote_type.to_u8()),
+            height: i64::try_from(vote.height.value()).expect("height overflow"),
+            round: i64::try_from(vote.round).expect("round overflow"),
+            block_id: vote
+                .block_id
+                .as_ref()
+                .and_then(|block_id| CanonicalBlockId::canonicalize(BlockId::from(block_id))),
+            timestamp: Some(Timestamp::from(vote.timestamp)),
+            chain_id: chain_id.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto_types::ProtoMessage;
+
+    fn canonical_vote(vote_type: i32, height: i64, round: i64, chain_id: &str) -> CanonicalVote {
+        CanonicalVote {
+            vote_type,
+            height,
+            round,
+            block_id: None,
+            timestamp: Some(Timestamp::zero()),
+            chain_id: chain_id.to_string(),
+        }
+    }
+
+    // The following vectors are taken from `TestVoteSignBytesTestVectors`:
+    // https://github.com/tendermint/tendermint/blob/v0.34.0/types/vote_test.go
+    #[test]
+    fn test_sign_bytes_compatibility() {
+        // NOTE: Height and Round are skipped here
+        let got = canonical_vote(0, 0, 0, "").bytes_vec_length_delimited();
+        let want = vec![
+            0xd, 0x2a, 0xb, 0x8, 0x80, 0x92, 0xb8, 0xc3, 0x98, 0xfe, 0xff, 0xff, 0xff, 0x1,
+        ];
+        assert_eq!(got, want);
+
+        // with proper (fixed size) height and round (PreCommit):
+        let got = canonical_vote(2, 1, 1, "").bytes_vec_length_delimited();
+        let want = vec![
+            0x21, // length
+            0x8,  // (field_number << 3) | wire_type
+            0x2,  // PrecommitType
+            0x11, // (field_number << 3) | wire_type
+            0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0,  // height
+            0x19, // (field_number << 3) | wire_type
+            0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0,  // round
+            0x2a, // (field_number << 3) | wire_type
+            // remaining fields (timestamp):
+            0xb, 0x8, 0x80, 0x92, 0xb8, 0xc3, 0x98, 0xfe, 0xff, 0xff, 0xff, 0x1,
+        ];
+        assert_eq!(got, want);
+
+        // with proper (fixed size) height and round (PreVote):
+        let got = canonical_vote(1, 1, 1, "").bytes_vec_length_delimited();
+        let want = vec![
+            0x21, // length
+            0x8,  // (field_number << 3) | wire_type
+            0x1,  // PrevoteType
+            0x11, // (field_number << 3) | wire_type
+            0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0,  // height
+            0x19, // (field_number << 3) | wire_type
+            0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0,  // round
+            0x2a, // (field_number << 3) | wire_type
+            // remaining fields (timestamp):
+            0xb, 0x8, 0x80, 0x92, 0xb8, 0xc3, 0x98, 0xfe, 0xff, 0xff, 0xff, 0x1,
+        ];
+        assert_eq!(got, want);
+
+        // containing non-empty chain_id:
+        let got = canonical_vote(0, 1, 1, "test_chain_id").bytes_vec_length_delimited();
+        let want = vec![
+            0x2e, // length
+            0x11, // (field_number << 3) | wire_type
+            0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0,  // height
+            0x19, // (field_number << 3) | wire_type
+            0x1, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, // round
+            // remaining fields:
+            0x2a, // (field_number << 3) | wire_type
+            0xb, 0x8, 0x80, 0x92, 0xb8, 0xc3, 0x98, 0xfe, 0xff, 0xff, 0xff, 0x1, // timestamp
+            // (field_number << 3) | wire_type
+            0x32, 0xd, 0x74, 0x65, 0x73, 0x74, 0x5f, 0x63, 0x68, 0x61, 0x69, 0x6e, 0x5f, 0x69,
+            0x64, // chainID
+        ];
+        assert_eq!(got, want);
+    }
+}
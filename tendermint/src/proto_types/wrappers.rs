@@ -0,0 +1,27 @@
+//! Well-known wrapper types, used by Tendermint to encode the primitive
+//! fields of a header before hashing them.
+//!
+//! <https://github.com/tendermint/tendermint/blob/v0.34.0/types/encoding_helper.go>
+
+use prost::Message;
+
+/// `google.protobuf.StringValue`
+#[derive(Clone, PartialEq, Message)]
+pub struct StringValue {
+    #[prost(string, tag = "1")]
+    pub value: String,
+}
+
+/// `google.protobuf.Int64Value`
+#[derive(Clone, PartialEq, Message)]
+pub struct Int64Value {
+    #[prost(int64, tag = "1")]
+    pub value: i64,
+}
+
+/// `google.protobuf.BytesValue`
+#[derive(Clone, PartialEq, Message)]
+pub struct BytesValue {
+    #[prost(bytes, tag = "1")]
+    pub value: Vec<u8>,
+}
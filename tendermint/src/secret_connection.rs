@@ -0,0 +1,325 @@
+//! Secret connections: authenticated and encrypted connections between peers.
+//!
+//! A secret connection is established over an underlying connection with the
+//! Station-to-Station handshake of Tendermint:
+//!
+//! 1. Both peers exchange ephemeral X25519 public keys, and derive the keys of the
+//!    connection from their Diffie-Hellman shared secret
+//! 2. Both peers sign a challenge derived from the transcript of the handshake with
+//!    their long-lived Ed25519 key, and exchange their public keys and signatures
+//!    over the now encrypted connection
+//!
+//! Data is then sent in frames of at most 1024 bytes, sealed with ChaCha20-Poly1305.
+//!
+//! <https://github.com/tendermint/tendermint/blob/master/docs/spec/p2p/peer.md#authenticated-encryption-handshake>
+
+mod kdf;
+mod nonce;
+
+pub use self::{
+    kdf::Kdf,
+    nonce::{Nonce, NONCE_SIZE},
+};
+
+use crate::error::{Error, Kind};
+use crate::{node, private_key::Ed25519Keypair};
+use anomaly::{fail, format_err};
+use chacha20poly1305::{
+    aead::{generic_array::GenericArray, Aead, NewAead},
+    ChaCha20Poly1305,
+};
+use merlin::Transcript;
+use prost_amino::Message;
+use prost_amino_derive::Message;
+use rand_core::OsRng;
+use signatory::{
+    ed25519,
+    public_key::PublicKeyed,
+    signature::{Signature as _, Signer, Verifier},
+};
+use signatory_dalek::Ed25519Verifier;
+use std::{cmp, convert::TryInto, io};
+use subtle::ConstantTimeEq;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use x25519_dalek::{EphemeralSecret, PublicKey as EphemeralPublic};
+
+/// Size of the length of the data of a frame
+pub const DATA_LEN_SIZE: usize = 4;
+
+/// Maximum size of the data of a frame
+pub const DATA_MAX_SIZE: usize = 1024;
+
+/// Size of a frame, once padded
+const TOTAL_FRAME_SIZE: usize = DATA_MAX_SIZE + DATA_LEN_SIZE;
+
+/// Size of the authentication tag of a sealed frame
+const TAG_SIZE: usize = 16;
+
+/// Size of a sealed frame, as sent over the underlying connection
+const SEALED_FRAME_SIZE: usize = TOTAL_FRAME_SIZE + TAG_SIZE;
+
+/// Public key and signature of the challenge exchanged during the handshake
+#[derive(Clone, PartialEq, Message)]
+struct AuthSigMessage {
+    #[prost_amino(bytes, tag = "1", amino_name = "tendermint/PubKeyEd25519")]
+    pub_key: Vec<u8>,
+    #[prost_amino(bytes, tag = "2")]
+    sig: Vec<u8>,
+}
+
+/// Encrypted connection to a peer, authenticated by its Ed25519 key
+pub struct SecretConnection<IoHandler> {
+    io_handler: IoHandler,
+    remote_pubkey: Option<ed25519::PublicKey>,
+    send_cipher: ChaCha20Poly1305,
+    recv_cipher: ChaCha20Poly1305,
+    send_nonce: Nonce,
+    recv_nonce: Nonce,
+    recv_buffer: Vec<u8>,
+}
+
+impl<IoHandler: AsyncRead + AsyncWrite + Unpin> SecretConnection<IoHandler> {
+    /// Perform the handshake over the given connection, authenticating
+    /// ourselves with the given key.
+    ///
+    /// ## Errors
+    /// - If the connection fails, or the remote peer does not follow the protocol
+    /// - If the remote ephemeral key is of low order
+    /// - If the remote signature of the challenge is invalid
+    pub async fn new(mut io_handler: IoHandler, local_key: &Ed25519Keypair) -> Result<Self, Error> {
+        let local_eph_secret = EphemeralSecret::new(&mut OsRng);
+        let local_eph_pubkey = EphemeralPublic::from(&local_eph_secret);
+        let remote_eph_pubkey = share_eph_pubkey(&mut io_handler, &local_eph_pubkey).await?;
+
+        let (low_eph_pubkey, high_eph_pubkey) =
+            sort32(local_eph_pubkey.as_bytes(), remote_eph_pubkey.as_bytes());
+        let loc_is_least = local_eph_pubkey.as_bytes() == low_eph_pubkey;
+
+        let shared_secret = local_eph_secret.diffie_hellman(&remote_eph_pubkey);
+
+        // Low order points yield an all-zero shared secret
+        if bool::from(shared_secret.as_bytes().ct_eq(&[0u8; 32])) {
+            fail!(Kind::Crypto, "remote ephemeral public key is of low order");
+        }
+
+        let mut transcript = Transcript::new(b"TENDERMINT_SECRET_CONNECTION_TRANSCRIPT_HASH");
+        transcript.append_message(b"EPHEMERAL_LOWER_PUBLIC_KEY", low_eph_pubkey);
+        transcript.append_message(b"EPHEMERAL_UPPER_PUBLIC_KEY", high_eph_pubkey);
+        transcript.append_message(b"DH_SECRET", shared_secret.as_bytes());
+
+        let kdf = Kdf::derive_secrets_and_challenge(shared_secret.as_bytes(), loc_is_least);
+
+        let mut challenge = [0u8; 32];
+        transcript.challenge_bytes(b"SECRET_CONNECTION_MAC", &mut challenge);
+
+        let mut conn = SecretConnection {
+            io_handler,
+            remote_pubkey: None,
+            send_cipher: ChaCha20Poly1305::new(GenericArray::from_slice(&kdf.send_secret)),
+            recv_cipher: ChaCha20Poly1305::new(GenericArray::from_slice(&kdf.recv_secret)),
+            send_nonce: Nonce::default(),
+            recv_nonce: Nonce::default(),
+            recv_buffer: vec![],
+        };
+
+        let signer = local_key.to_signer();
+        let local_signature: ed25519::Signature = signer.sign(&challenge);
+        let local_pubkey = signer
+            .public_key()
+            .map_err(|e| format_err!(Kind::InvalidKey, "{}", e))?;
+
+        let remote_auth = conn
+            .share_auth_signature(&local_pubkey, &local_signature)
+            .await?;
+
+        let remote_pubkey = ed25519::PublicKey::from_bytes(&remote_auth.pub_key)
+            .ok_or_else(|| format_err!(Kind::InvalidKey, "malformed remote public key"))?;
+        let remote_signature = ed25519::Signature::from_bytes(&remote_auth.sig)
+            .map_err(|e| format_err!(Kind::SignatureInvalid, "{}", e))?;
+
+        Ed25519Verifier::from(&remote_pubkey)
+            .verify(&challenge, &remote_signature)
+            .map_err(|_| format_err!(Kind::SignatureInvalid, "invalid challenge signature"))?;
+
+        conn.remote_pubkey = Some(remote_pubkey);
+        Ok(conn)
+    }
+
+    /// Public key of the remote peer
+    pub fn remote_pubkey(&self) -> ed25519::PublicKey {
+        self.remote_pubkey
+            .expect("the remote public key is set by the handshake")
+    }
+
+    /// Node ID of the remote peer
+    pub fn remote_node_id(&self) -> node::Id {
+        node::Id::from(self.remote_pubkey())
+    }
+
+    /// Check that the remote peer is the node with the given ID
+    pub fn verify_remote_node_id(&self, expected: node::Id) -> Result<(), Error> {
+        let actual = self.remote_node_id();
+
+        if actual != expected {
+            fail!(
+                Kind::InvalidKey,
+                "remote peer is {}, expected {}",
+                actual,
+                expected
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Read decrypted data into the given buffer, and return the number of bytes read.
+    ///
+    /// Returns 0 once the underlying connection is closed.
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        while self.recv_buffer.is_empty() {
+            let mut sealed_frame = vec![0u8; SEALED_FRAME_SIZE];
+
+            match self.io_handler.read_exact(&mut sealed_frame).await {
+                Ok(_) => (),
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
+                Err(e) => return Err(e.into()),
+            }
+
+            let frame = self
+                .recv_cipher
+                .decrypt(
+                    GenericArray::from_slice(self.recv_nonce.as_bytes()),
+                    sealed_frame.as_slice(),
+                )
+                .map_err(|_| format_err!(Kind::Crypto, "cannot decrypt frame"))?;
+            self.recv_nonce.increment()?;
+
+            let data_len = u32::from_le_bytes(frame[..DATA_LEN_SIZE].try_into().unwrap()) as usize;
+
+            if data_len > DATA_MAX_SIZE {
+                fail!(
+                    Kind::Protocol,
+                    "frame data of {} bytes exceeds the maximum of {} bytes",
+                    data_len,
+                    DATA_MAX_SIZE
+                );
+            }
+
+            self.recv_buffer = frame[DATA_LEN_SIZE..DATA_LEN_SIZE + data_len].to_vec();
+        }
+
+        let n = cmp::min(buf.len(), self.recv_buffer.len());
+        buf[..n].copy_from_slice(&self.recv_buffer[..n]);
+        self.recv_buffer.drain(..n);
+
+        Ok(n)
+    }
+
+    /// Encrypt and write all the given data, in as many frames as needed
+    pub async fn write_all(&mut self, data: &[u8]) -> Result<(), Error> {
+        for chunk in data.chunks(DATA_MAX_SIZE) {
+            let mut frame = vec![0u8; TOTAL_FRAME_SIZE];
+            frame[..DATA_LEN_SIZE].copy_from_slice(&(chunk.len() as u32).to_le_bytes());
+            frame[DATA_LEN_SIZE..DATA_LEN_SIZE + chunk.len()].copy_from_slice(chunk);
+
+            let sealed_frame = self
+                .send_cipher
+                .encrypt(
+                    GenericArray::from_slice(self.send_nonce.as_bytes()),
+                    frame.as_slice(),
+                )
+                .map_err(|_| format_err!(Kind::Crypto, "cannot encrypt frame"))?;
+            self.send_nonce.increment()?;
+
+            self.io_handler.write_all(&sealed_frame).await?;
+        }
+
+        self.io_handler.flush().await?;
+        Ok(())
+    }
+
+    /// Send our public key and signature of the challenge, and receive the remote ones
+    async fn share_auth_signature(
+        &mut self,
+        pubkey: &ed25519::PublicKey,
+        signature: &ed25519::Signature,
+    ) -> Result<AuthSigMessage, Error> {
+        let local_auth = AuthSigMessage {
+            pub_key: pubkey.as_bytes().to_vec(),
+            sig: signature.as_ref().to_vec(),
+        };
+
+        let mut buf = vec![];
+        local_auth.encode_length_delimited(&mut buf)?;
+        self.write_all(&buf).await?;
+
+        let mut buf = vec![0u8; DATA_MAX_SIZE];
+        let n = self.read(&mut buf).await?;
+
+        Ok(AuthSigMessage::decode_length_delimited(&buf[..n])?)
+    }
+}
+
+/// Send our ephemeral public key and receive the remote one, encoded as length-prefixed
+/// amino byte arrays: the total length, followed by the length of the key and the key.
+async fn share_eph_pubkey<IoHandler: AsyncRead + AsyncWrite + Unpin>(
+    io_handler: &mut IoHandler,
+    local_eph_pubkey: &EphemeralPublic,
+) -> Result<EphemeralPublic, Error> {
+    let mut buf = vec![33, 32];
+    buf.extend_from_slice(local_eph_pubkey.as_bytes());
+    io_handler.write_all(&buf).await?;
+    io_handler.flush().await?;
+
+    let mut buf = [0u8; 34];
+    io_handler.read_exact(&mut buf).await?;
+
+    if buf[0] != 33 || buf[1] != 32 {
+        fail!(Kind::Protocol, "malformed remote ephemeral public key");
+    }
+
+    let mut remote_eph_pubkey = [0u8; 32];
+    remote_eph_pubkey.copy_from_slice(&buf[2..]);
+
+    Ok(EphemeralPublic::from(remote_eph_pubkey))
+}
+
+/// Return the given keys, lowest first
+fn sort32<'a>(first: &'a [u8; 32], second: &'a [u8; 32]) -> (&'a [u8; 32], &'a [u8; 32]) {
+    if second > first {
+        (first, second)
+    } else {
+        (second, first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_keys() {
+        let low = [0u8; 32];
+        let high = [1u8; 32];
+
+        assert_eq!(sort32(&low, &high), (&low, &high));
+        assert_eq!(sort32(&high, &low), (&low, &high));
+    }
+
+    #[test]
+    fn encode_auth_sig_message() {
+        let msg = AuthSigMessage {
+            pub_key: vec![0x79; 32],
+            sig: vec![0x42; 64],
+        };
+
+        let mut buf = vec![];
+        msg.encode_length_delimited(&mut buf).unwrap();
+
+        // Total length, then the public key with its amino prefix, and the signature
+        assert_eq!(buf[..7], [0x69, 0x0a, 0x25, 0x16, 0x24, 0xde, 0x64]);
+        assert_eq!(buf[7..9], [0x20, 0x79]);
+        assert_eq!(buf[39..41], [0x12, 0x40]);
+        assert_eq!(buf.len(), 105);
+    }
+}
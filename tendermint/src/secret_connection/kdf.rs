@@ -0,0 +1,87 @@
+//! Derivation of the secrets of a secret connection
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+/// Info string of the HKDF expansion, as in Tendermint
+const HKDF_INFO: &[u8] = b"TENDERMINT_SECRET_CONNECTION_KEY_AND_CHALLENGE_GEN";
+
+/// Secrets derived from the Diffie-Hellman shared secret of a handshake
+#[derive(Zeroize)]
+#[zeroize(drop)]
+pub struct Kdf {
+    /// Key for decrypting the received frames
+    pub recv_secret: [u8; 32],
+
+    /// Key for encrypting the sent frames
+    pub send_secret: [u8; 32],
+
+    /// Challenge of the earlier versions of the handshake, which now
+    /// derive the challenge from the transcript of the handshake instead
+    pub challenge: [u8; 32],
+}
+
+impl Kdf {
+    /// Derive the secrets from the given shared secret, depending on whether
+    /// the local ephemeral public key is the lowest of both.
+    pub fn derive_secrets_and_challenge(shared_secret: &[u8; 32], loc_is_least: bool) -> Self {
+        let mut key_material = [0u8; 96];
+
+        Hkdf::<Sha256>::new(None, shared_secret)
+            .expand(HKDF_INFO, &mut key_material)
+            .expect("96 bytes is a valid HKDF-SHA256 output length");
+
+        let mut recv_secret = [0u8; 32];
+        let mut send_secret = [0u8; 32];
+        let mut challenge = [0u8; 32];
+
+        if loc_is_least {
+            recv_secret.copy_from_slice(&key_material[0..32]);
+            send_secret.copy_from_slice(&key_material[32..64]);
+        } else {
+            send_secret.copy_from_slice(&key_material[0..32]);
+            recv_secret.copy_from_slice(&key_material[32..64]);
+        }
+
+        challenge.copy_from_slice(&key_material[64..96]);
+        key_material.zeroize();
+
+        Kdf {
+            recv_secret,
+            send_secret,
+            challenge,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subtle_encoding::hex;
+
+    fn decode_32(s: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(s).unwrap());
+        bytes
+    }
+
+    #[test]
+    fn derive_secrets_and_challenge_golden_test_vectors() {
+        let golden = include_str!("../../tests/support/TestDeriveSecretsAndChallenge.golden");
+
+        for line in golden.lines() {
+            let params: Vec<&str> = line.split(',').collect();
+            assert_eq!(params.len(), 5);
+
+            let shared_secret = decode_32(params[0]);
+            let loc_is_least = params[1].parse::<bool>().unwrap();
+
+            let kdf = Kdf::derive_secrets_and_challenge(&shared_secret, loc_is_least);
+
+            assert_eq!(kdf.recv_secret, decode_32(params[2]));
+            assert_eq!(kdf.send_secret, decode_32(params[3]));
+            assert_eq!(kdf.challenge, decode_32(params[4]));
+        }
+    }
+}
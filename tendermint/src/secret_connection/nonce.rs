@@ -0,0 +1,57 @@
+//! Nonces of the frames of a secret connection
+
+use crate::error::{Error, Kind};
+use anomaly::fail;
+use std::convert::TryInto;
+
+/// Size of a ChaCha20-Poly1305 nonce
+pub const NONCE_SIZE: usize = 12;
+
+/// Nonce made of 4 zero bytes followed by a little-endian 64-bit counter,
+/// which is incremented after each frame, as in Tendermint.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Nonce([u8; NONCE_SIZE]);
+
+impl Nonce {
+    /// Increment the counter of this nonce
+    pub fn increment(&mut self) -> Result<(), Error> {
+        let counter = u64::from_le_bytes(self.0[4..].try_into().unwrap());
+
+        match counter.checked_add(1) {
+            Some(next) => self.0[4..].copy_from_slice(&next.to_le_bytes()),
+            None => fail!(Kind::Crypto, "nonce overflow"),
+        }
+
+        Ok(())
+    }
+
+    /// Bytes of this nonce
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment_counter_in_little_endian() {
+        let mut nonce = Nonce::default();
+
+        for _ in 0..0x102 {
+            nonce.increment().unwrap();
+        }
+
+        assert_eq!(
+            nonce.as_bytes(),
+            &[0, 0, 0, 0, 0x02, 0x01, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn reject_counter_overflow() {
+        let mut nonce = Nonce([0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+        assert!(nonce.increment().is_err());
+    }
+}
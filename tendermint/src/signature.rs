@@ -1,14 +1,27 @@
 //! Cryptographic (a.k.a. digital) signatures
 
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "secp256k1")]
+use signatory::ecdsa::curve::secp256k1;
 use signatory::signature::Signature as _;
 use subtle_encoding::base64;
 
 /// Signatures
+///
+/// Note that the encoding of a signature does not tell the algorithm which
+/// created it, as both Ed25519 and (fixed-size) secp256k1 signatures are 64 bytes
+/// long. Signatures are thus deserialized as Ed25519 signatures, and their raw
+/// bytes are interpreted according to the public key of the signer upon
+/// verification (see `validator::Info::verify_signature`).
 #[derive(Clone, Debug, PartialEq)]
 pub enum Signature {
     /// Ed25519 block signature
     Ed25519(signatory::ed25519::Signature),
+
+    /// Secp256k1 block signature, i.e. the fixed-size `r || s` encoding
+    /// of an ECDSA signature over secp256k1 (with a low `s`)
+    #[cfg(feature = "secp256k1")]
+    Secp256k1(secp256k1::FixedSignature),
 }
 
 impl Signature {
@@ -16,6 +29,8 @@ impl Signature {
     pub fn algorithm(self) -> Algorithm {
         match self {
             Signature::Ed25519(_) => Algorithm::Ed25519,
+            #[cfg(feature = "secp256k1")]
+            Signature::Secp256k1(_) => Algorithm::EcdsaSecp256k1,
         }
     }
 
@@ -29,6 +44,8 @@ impl AsRef<[u8]> for Signature {
     fn as_ref(&self) -> &[u8] {
         match self {
             Signature::Ed25519(sig) => sig.as_ref(),
+            #[cfg(feature = "secp256k1")]
+            Signature::Secp256k1(sig) => sig.as_ref(),
         }
     }
 }
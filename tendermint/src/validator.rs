@@ -2,21 +2,40 @@
 
 use prost_amino_derive::Message;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "secp256k1")]
+use signatory::ecdsa::curve::secp256k1;
 use signatory::{
     ed25519,
     signature::{Signature, Verifier},
 };
 use signatory_dalek::Ed25519Verifier;
+#[cfg(feature = "secp256k1")]
+use signatory_secp256k1::EcdsaVerifier;
 use subtle_encoding::base64;
 
 use crate::amino_types::message::AminoMessage;
+use crate::encoding::Encoding;
+use crate::error::{Error, Kind};
+use crate::proto_types::{ProtoMessage, SimpleValidator};
 use crate::{account, vote, PublicKey};
+use anomaly::fail;
+
+/// Maximum total voting power of a validator set, which keeps the proposer
+/// priority computations from overflowing
+pub const MAX_TOTAL_VOTING_POWER: u64 = (i64::MAX / 8) as u64;
+
+/// Bound of the spread of the proposer priorities, relative to the total voting power
+const PRIORITY_WINDOW_SIZE_FACTOR: i64 = 2;
 
 /// Validator set contains a vector of validators
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Set {
     #[serde(deserialize_with = "parse_vals")]
     validators: Vec<Info>,
+
+    /// Proposer of the current round, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    proposer: Option<Info>,
 }
 
 impl Set {
@@ -24,7 +43,10 @@ impl Set {
     /// vals is mutable so it can be sorted by address.
     pub fn new(mut vals: Vec<Info>) -> Set {
         Self::sort_validators(&mut vals);
-        Set { validators: vals }
+        Set {
+            validators: vals,
+            proposer: None,
+        }
     }
 
     /// Get Info of the underlying validators.
@@ -47,6 +69,244 @@ impl Set {
             .find(|val| val.address == val_id)
             .cloned()
     }
+
+    /// Returns the proposer of the current round: the one selected by the
+    /// last call to `increment_proposer_priority`, or else the validator with
+    /// the highest proposer priority. Returns `None` if the set is empty.
+    pub fn proposer(&self) -> Option<Info> {
+        self.proposer
+            .or_else(|| self.most_priority().map(|i| self.validators[i]))
+    }
+
+    /// Increment the proposer priorities of the validators the given number of
+    /// times, i.e. advance the proposer selection by as many rounds, as in
+    /// Tendermint's `ValidatorSet.IncrementProposerPriority`.
+    ///
+    /// Panics if the set is empty or `times` is zero.
+    pub fn increment_proposer_priority(&mut self, times: usize) {
+        assert!(
+            !self.validators.is_empty(),
+            "cannot increment the proposer priority of an empty validator set"
+        );
+        assert!(times > 0, "cannot increment the proposer priority 0 times");
+
+        self.rescale_priorities(PRIORITY_WINDOW_SIZE_FACTOR * self.total_voting_power());
+        self.shift_by_avg_proposer_priority();
+
+        let total_voting_power = self.total_voting_power();
+
+        for _ in 0..times {
+            for val in &mut self.validators {
+                let priority = val.priority().saturating_add(val.power() as i64);
+                val.set_priority(priority);
+            }
+
+            let most_priority = self.most_priority().unwrap();
+            let proposer = &mut self.validators[most_priority];
+            proposer.set_priority(proposer.priority().saturating_sub(total_voting_power));
+            self.proposer = Some(*proposer);
+        }
+    }
+
+    /// Apply the given updates (e.g. from an `EndBlock` response) to the set,
+    /// as in Tendermint's `ValidatorSet.UpdateWithChangeSet`.
+    ///
+    /// Updates with a voting power of 0 remove validators. New validators start
+    /// with a low proposer priority, so they do not propose right away.
+    ///
+    /// The set is left untouched if the updates are invalid: duplicated,
+    /// removing unknown validators, leaving the set empty, or exceeding the
+    /// maximum total voting power.
+    pub fn apply_updates(&mut self, updates: &[Update]) -> Result<(), Error> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        let mut changes: Vec<Info> = updates
+            .iter()
+            .map(|update| Info::new(update.pub_key, update.power))
+            .collect();
+        Self::sort_validators(&mut changes);
+
+        for pair in changes.windows(2) {
+            if pair[0].address == pair[1].address {
+                fail!(
+                    Kind::InvalidValidatorUpdate,
+                    "duplicate update of validator {}",
+                    pair[0].address
+                );
+            }
+        }
+
+        for change in &changes {
+            if change.power() > MAX_TOTAL_VOTING_POWER {
+                fail!(
+                    Kind::InvalidValidatorUpdate,
+                    "voting power {} of validator {} exceeds the maximum of {}",
+                    change.power(),
+                    change.address,
+                    MAX_TOTAL_VOTING_POWER
+                );
+            }
+        }
+
+        let (removals, mut changes): (Vec<Info>, Vec<Info>) =
+            changes.into_iter().partition(|change| change.power() == 0);
+
+        let new_validators = changes
+            .iter()
+            .filter(|change| self.validator(change.address).is_none())
+            .count();
+
+        if new_validators == 0 && removals.len() == self.validators.len() {
+            fail!(
+                Kind::InvalidValidatorUpdate,
+                "applying the updates would result in an empty validator set"
+            );
+        }
+
+        let mut removed_power = 0i64;
+
+        for removal in &removals {
+            match self.validator(removal.address) {
+                Some(val) => removed_power += val.power() as i64,
+                None => fail!(
+                    Kind::InvalidValidatorUpdate,
+                    "cannot remove unknown validator {}",
+                    removal.address
+                ),
+            }
+        }
+
+        // The total voting power must stay below the maximum whatever the
+        // order the updates are applied in, so check the largest increases last
+        let mut deltas: Vec<i64> = changes
+            .iter()
+            .map(|change| {
+                change.power() as i64
+                    - self
+                        .validator(change.address)
+                        .map_or(0, |val| val.power() as i64)
+            })
+            .collect();
+        deltas.sort_unstable();
+
+        let mut total_voting_power = self.total_voting_power() - removed_power;
+
+        for delta in deltas {
+            total_voting_power += delta;
+
+            if total_voting_power > MAX_TOTAL_VOTING_POWER as i64 {
+                fail!(
+                    Kind::InvalidValidatorUpdate,
+                    "total voting power of the resulting validator set exceeds the maximum of {}",
+                    MAX_TOTAL_VOTING_POWER
+                );
+            }
+        }
+
+        // New validators start at -1.125 times the total voting power of the
+        // set after the updates, and before the removals
+        let total_voting_power = total_voting_power + removed_power;
+
+        for change in &mut changes {
+            let priority = match self.validator(change.address) {
+                Some(val) => val.priority(),
+                None => -(total_voting_power + (total_voting_power >> 3)),
+            };
+            change.set_priority(priority);
+        }
+
+        for change in changes {
+            match self
+                .validators
+                .iter_mut()
+                .find(|val| val.address == change.address)
+            {
+                Some(val) => *val = change,
+                None => self.validators.push(change),
+            }
+        }
+
+        self.validators.retain(|val| {
+            !removals
+                .iter()
+                .any(|removal| removal.address == val.address)
+        });
+        Self::sort_validators(&mut self.validators);
+
+        self.rescale_priorities(PRIORITY_WINDOW_SIZE_FACTOR * self.total_voting_power());
+        self.shift_by_avg_proposer_priority();
+
+        Ok(())
+    }
+
+    fn total_voting_power(&self) -> i64 {
+        self.validators.iter().map(|val| val.power() as i64).sum()
+    }
+
+    /// Index of the validator with the highest proposer priority, ties being
+    /// broken in favor of the lowest address
+    fn most_priority(&self) -> Option<usize> {
+        let mut most = None;
+
+        for (i, val) in self.validators.iter().enumerate() {
+            most = match most {
+                Some(j) => {
+                    let other = &self.validators[j];
+
+                    // Higher priority first, then lower address
+                    if (val.priority(), other.address) > (other.priority(), val.address) {
+                        Some(i)
+                    } else {
+                        Some(j)
+                    }
+                }
+                None => Some(i),
+            };
+        }
+
+        most
+    }
+
+    /// Scale the proposer priorities down, so that their spread is at most `diff_max`
+    fn rescale_priorities(&mut self, diff_max: i64) {
+        if diff_max <= 0 {
+            return;
+        }
+
+        let priorities = self.validators.iter().map(Info::priority);
+        let diff = match (priorities.clone().max(), priorities.min()) {
+            (Some(max), Some(min)) => max - min,
+            _ => return,
+        };
+
+        if diff > diff_max {
+            let ratio = (diff + diff_max - 1) / diff_max;
+
+            for val in &mut self.validators {
+                val.set_priority(val.priority() / ratio);
+            }
+        }
+    }
+
+    /// Center the proposer priorities around 0
+    fn shift_by_avg_proposer_priority(&mut self) {
+        if self.validators.is_empty() {
+            return;
+        }
+
+        let sum: i128 = self
+            .validators
+            .iter()
+            .map(|val| i128::from(val.priority()))
+            .sum();
+        let avg = sum.div_euclid(self.validators.len() as i128) as i64;
+
+        for val in &mut self.validators {
+            val.set_priority(val.priority().saturating_sub(avg));
+        }
+    }
 }
 
 // TODO: maybe add a type (with an Option<Vec<Info>> field) instead
@@ -86,14 +346,28 @@ impl Info {
 
     /// Verify the given signature against the given sign_bytes using the validators
     /// public key.
+    ///
+    /// The signature is interpreted according to the type of the public key, i.e.
+    /// either as an Ed25519 signature, or as the fixed-size encoding of a secp256k1
+    /// ECDSA signature over the SHA-256 digest of the sign_bytes.
     pub fn verify_signature(&self, sign_bytes: &[u8], signature: &[u8]) -> bool {
-        if let Some(pk) = &self.pub_key.ed25519() {
-            let verifier = Ed25519Verifier::from(pk);
-            if let Ok(sig) = ed25519::Signature::from_bytes(signature) {
-                return verifier.verify(sign_bytes, &sig).is_ok();
+        match &self.pub_key {
+            PublicKey::Ed25519(pk) => {
+                let verifier = Ed25519Verifier::from(pk);
+                match ed25519::Signature::from_bytes(signature) {
+                    Ok(sig) => verifier.verify(sign_bytes, &sig).is_ok(),
+                    Err(_) => false,
+                }
+            }
+            #[cfg(feature = "secp256k1")]
+            PublicKey::Secp256k1(pk) => {
+                let verifier = EcdsaVerifier::from(pk);
+                match secp256k1::FixedSignature::from_bytes(signature) {
+                    Ok(sig) => verifier.verify(sign_bytes, &sig).is_ok(),
+                    Err(_) => false,
+                }
             }
         }
-        false
     }
 }
 
@@ -108,6 +382,15 @@ impl From<PublicKey> for account::Id {
 }
 
 impl Info {
+    /// Return the proposer priority of the validator, 0 if unknown.
+    pub fn priority(&self) -> i64 {
+        self.proposer_priority.map_or(0, ProposerPriority::value)
+    }
+
+    fn set_priority(&mut self, priority: i64) {
+        self.proposer_priority = Some(ProposerPriority::new(priority));
+    }
+
     /// Create a new validator.
     pub fn new(pk: PublicKey, vp: vote::Power) -> Info {
         Info {
@@ -149,6 +432,16 @@ impl Info {
     pub fn hash_bytes(&self) -> Vec<u8> {
         AminoMessage::bytes_vec(&InfoHashable::from(self))
     }
+
+    /// Returns the bytes to be hashed into the Merkle tree, using the given
+    /// encoding. As of Tendermint v0.34, this is the protobuf encoding of
+    /// the pubkey and voting power.
+    pub fn hash_bytes_with_encoding(&self, encoding: Encoding) -> Vec<u8> {
+        match encoding {
+            Encoding::Amino => self.hash_bytes(),
+            Encoding::Protobuf => ProtoMessage::bytes_vec(&SimpleValidator::from(self)),
+        }
+    }
 }
 
 /// Proposer priority
@@ -156,6 +449,11 @@ impl Info {
 pub struct ProposerPriority(i64);
 
 impl ProposerPriority {
+    /// Create a new proposer priority
+    pub fn new(priority: i64) -> ProposerPriority {
+        ProposerPriority(priority)
+    }
+
     /// Get the current voting power
     pub fn value(self) -> i64 {
         self.0
@@ -273,4 +571,151 @@ mod tests {
             148_151_478_422_287_875 + 158_095_448_483_785_107 + 770_561_664_770_006_272
         );
     }
+
+    #[test]
+    fn test_protobuf_hash_bytes() {
+        let pk_string = "F349539C7E5EF7C49549B09C4BFC2335318AB0FE51FBFAA2433B4F13E816F4A7";
+        let val = make_validator(pk_string, 10);
+
+        let mut want = vec![0xa, 0x22, 0xa, 0x20];
+        want.extend(hex::decode_upper(pk_string).unwrap());
+        want.extend(vec![0x10, 0xa]);
+
+        assert_eq!(val.hash_bytes_with_encoding(Encoding::Protobuf), want);
+        assert_eq!(
+            val.hash_bytes_with_encoding(Encoding::Amino),
+            val.hash_bytes()
+        );
+    }
+
+    #[test]
+    fn test_protobuf_validator_set_hash() {
+        // The validators are sorted by address in the set, but hashed by
+        // decreasing voting power as of Tendermint v0.34. Test vector computed
+        // following `ValidatorSet.Hash` in Go:
+        // https://github.com/tendermint/tendermint/blob/v0.34.0/types/validator_set.go
+        let val_set = Set::new(vec![
+            make_validator(PK1, 10),
+            make_validator(PK2, 30),
+            make_validator(PK3, 20),
+        ]);
+
+        let hash_string = "C719201E7DE6652C86E99BF480930D34884AC94E414B2D4C59A18FF9FD6707F4";
+        let hash_expect = hex::decode_upper(hash_string).unwrap();
+
+        let hash = val_set.hash_with_encoding(Encoding::Protobuf);
+        assert_eq!(hash.as_bytes().to_vec(), hash_expect);
+        assert_eq!(val_set.hash_with_encoding(Encoding::Amino), val_set.hash());
+    }
+
+    const PK1: &str = "F349539C7E5EF7C49549B09C4BFC2335318AB0FE51FBFAA2433B4F13E816F4A7";
+    const PK2: &str = "5646AA4C706B7AF73768903E77D117487D2584B76D83EB8FF287934EE7758AFC";
+    const PK3: &str = "EB6B732C4BD86B5FA3F3BC3DB688DA0ED182A7411F81C2D405506B298FC19E52";
+
+    fn make_update(pk_string: &str, vp: u64) -> Update {
+        Update {
+            pub_key: make_validator(pk_string, vp).pub_key,
+            power: vote::Power::new(vp),
+        }
+    }
+
+    // make a validator with the given name as its address, for readable test vectors
+    fn make_named_validator(name: &str, vp: u64) -> Info {
+        let mut address = [0u8; account::LENGTH];
+        address[..name.len()].copy_from_slice(name.as_bytes());
+
+        Info {
+            address: account::Id::new(address),
+            ..make_validator(PK1, vp)
+        }
+    }
+
+    #[test]
+    fn test_proposer_selection() {
+        // test vector from `TestProposerSelection1` in Tendermint
+        let mut val_set = Set::new(vec![
+            make_named_validator("foo", 1000),
+            make_named_validator("bar", 300),
+            make_named_validator("baz", 330),
+        ]);
+        val_set.increment_proposer_priority(1);
+
+        let mut proposers = vec![];
+        for _ in 0..99 {
+            let proposer = val_set.proposer().unwrap();
+            proposers.push(String::from_utf8(proposer.address.as_bytes()[..3].to_vec()).unwrap());
+            val_set.increment_proposer_priority(1);
+        }
+
+        let expected = "foo baz foo bar foo foo baz foo bar foo foo baz foo foo bar foo baz foo foo \
+            bar foo foo baz foo bar foo foo baz foo bar foo foo baz foo foo bar foo baz foo foo bar \
+            foo baz foo foo bar foo baz foo foo bar foo baz foo foo foo baz bar foo foo foo baz foo \
+            bar foo foo baz foo bar foo foo baz foo bar foo foo baz foo bar foo foo baz foo foo bar \
+            foo baz foo foo bar foo baz foo foo bar foo baz foo foo";
+        assert_eq!(proposers.join(" "), expected);
+    }
+
+    #[test]
+    fn test_proposer_of_new_set() {
+        assert_eq!(Set::new(vec![]).proposer(), None);
+
+        // without priorities, the validator with the lowest address proposes
+        let val_set = Set::new(vec![
+            make_named_validator("foo", 10),
+            make_named_validator("bar", 10),
+        ]);
+        assert_eq!(
+            val_set.proposer().unwrap().address,
+            val_set.validators()[0].address
+        );
+    }
+
+    #[test]
+    fn test_apply_updates() {
+        let (v1, v2, v3) = (
+            make_validator(PK1, 10),
+            make_validator(PK2, 10),
+            make_validator(PK3, 20),
+        );
+        let mut val_set = Set::new(vec![v1, v2]);
+
+        // new validators start at -1.125 times the total voting power
+        val_set.apply_updates(&[make_update(PK3, 20)]).unwrap();
+        assert_eq!(val_set.validators().len(), 3);
+        assert_eq!(val_set.validator(v1.address).unwrap().priority(), 15);
+        assert_eq!(val_set.validator(v2.address).unwrap().priority(), 15);
+        assert_eq!(val_set.validator(v3.address).unwrap().priority(), -30);
+
+        // the average priority is rounded down
+        val_set.apply_updates(&[make_update(PK1, 0)]).unwrap();
+        assert_eq!(val_set.validator(v1.address), None);
+        assert_eq!(val_set.validator(v2.address).unwrap().priority(), 23);
+        assert_eq!(val_set.validator(v3.address).unwrap().priority(), -22);
+
+        // updating the power of a validator keeps its priority
+        val_set.apply_updates(&[make_update(PK2, 30)]).unwrap();
+        assert_eq!(val_set.validator(v2.address).unwrap().power(), 30);
+        assert_eq!(val_set.validator(v2.address).unwrap().priority(), 23);
+    }
+
+    #[test]
+    fn test_reject_invalid_updates() {
+        let mut val_set = Set::new(vec![make_validator(PK1, 10)]);
+        let original = val_set.clone();
+
+        for updates in vec![
+            vec![make_update(PK2, 10), make_update(PK2, 20)],
+            vec![make_update(PK2, 0)],
+            vec![make_update(PK1, 0)],
+            vec![make_update(PK2, MAX_TOTAL_VOTING_POWER + 1)],
+            vec![make_update(PK2, MAX_TOTAL_VOTING_POWER)],
+        ] {
+            let err = val_set.apply_updates(&updates).unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<anomaly::Context<Kind>>().unwrap().kind(),
+                &Kind::InvalidValidatorUpdate
+            );
+            assert_eq!(val_set, original);
+        }
+    }
 }
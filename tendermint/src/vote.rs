@@ -4,8 +4,10 @@ mod power;
 
 pub use self::power::Power;
 use crate::amino_types::message::AminoMessage;
-use crate::{account, block, Signature, Time};
-use crate::{amino_types, hash};
+use crate::encoding::Encoding;
+use crate::proto_types::ProtoMessage;
+use crate::{account, block, chain, consensus, PrivateKey, Signature, Time};
+use crate::{amino_types, hash, proto_types};
 use {
     crate::serializers,
     serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer},
@@ -70,12 +72,46 @@ impl Vote {
             None => None,
         }
     }
+
+    /// Height, round and step of this vote, used to prevent double signing
+    pub fn consensus_state(&self) -> consensus::State {
+        consensus::State {
+            height: self.height,
+            round: self.round as i64,
+            step: match self.vote_type {
+                Type::Prevote => consensus::state::STEP_PREVOTE,
+                Type::Precommit => consensus::state::STEP_PRECOMMIT,
+            },
+            block_id: self.block_id.clone(),
+        }
+    }
+
+    /// Compute the bytes to be signed for this vote on the given chain,
+    /// canonicalized with the given encoding.
+    pub fn sign_bytes(&self, chain_id: &chain::Id, encoding: Encoding) -> Vec<u8> {
+        SignedVote::from_vote(self, chain_id.as_str(), encoding).sign_bytes()
+    }
+
+    /// Sign this vote for the given chain with the given private key.
+    ///
+    /// This sets the validator address of the vote to the address of the key,
+    /// and its signature to the signature of its sign bytes.
+    pub fn sign(
+        mut self,
+        private_key: &PrivateKey,
+        chain_id: &chain::Id,
+        encoding: Encoding,
+    ) -> Self {
+        self.validator_address = account::Id::from(private_key.public_key());
+        self.signature = private_key.sign(&self.sign_bytes(chain_id, encoding));
+        self
+    }
 }
 
-/// SignedVote is the union of a canonicalized vote, the signature on
-/// the sign bytes of that vote and the id of the validator who signed it.
+/// SignedVote is the union of the sign bytes of a canonicalized vote, the signature
+/// on those sign bytes and the id of the validator who signed it.
 pub struct SignedVote {
-    vote: amino_types::vote::CanonicalVote,
+    sign_bytes: Vec<u8>,
     validator_address: account::Id,
     signature: Signature,
 }
@@ -91,12 +127,30 @@ impl SignedVote {
     ) -> SignedVote {
         let canonical_vote = amino_types::vote::CanonicalVote::new(vote, chain_id);
         SignedVote {
-            vote: canonical_vote,
+            sign_bytes: canonical_vote.bytes_vec_length_delimited(),
             signature,
             validator_address,
         }
     }
 
+    /// Create new SignedVote from the given vote, canonicalized with the given
+    /// encoding.
+    pub fn from_vote(vote: &Vote, chain_id: &str, encoding: Encoding) -> SignedVote {
+        let sign_bytes = match encoding {
+            Encoding::Amino => amino_types::vote::CanonicalVote::new(vote.into(), chain_id)
+                .bytes_vec_length_delimited(),
+            Encoding::Protobuf => {
+                proto_types::CanonicalVote::new(vote, chain_id).bytes_vec_length_delimited()
+            }
+        };
+
+        SignedVote {
+            sign_bytes,
+            validator_address: vote.validator_address,
+            signature: vote.signature.clone(),
+        }
+    }
+
     /// Return the id of the validator that signed this vote.
     pub fn validator_id(&self) -> account::Id {
         self.validator_address
@@ -104,7 +158,7 @@ impl SignedVote {
 
     /// Return the bytes (of the canonicalized vote) that were signed.
     pub fn sign_bytes(&self) -> Vec<u8> {
-        self.vote.bytes_vec_length_delimited()
+        self.sign_bytes.clone()
     }
 
     /// Return the actual signature on the canonicalized vote.
@@ -157,3 +211,41 @@ impl<'de> Deserialize<'de> for Type {
         Type::from_u8(byte).ok_or_else(|| D::Error::custom(format!("invalid vote type: {}", byte)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PrivValidatorKey;
+    use crate::validator;
+
+    fn make_vote() -> Vote {
+        Vote {
+            vote_type: Type::Precommit,
+            height: block::Height::from(10_u64),
+            round: 1,
+            block_id: None,
+            timestamp: Time::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap(),
+            validator_address: account::Id::new([0; 20]),
+            validator_index: 0,
+            signature: Signature::Ed25519(signatory::ed25519::Signature::new([0; 64])),
+        }
+    }
+
+    #[test]
+    fn sign_vote() {
+        let key = PrivValidatorKey::parse_json(include_str!(
+            "../tests/support/config/priv_validator_key.json"
+        ))
+        .unwrap();
+        let chain_id = "test-chain".parse().unwrap();
+
+        for encoding in &[Encoding::Amino, Encoding::Protobuf] {
+            let vote = make_vote().sign(&key.priv_key, &chain_id, *encoding);
+            assert_eq!(vote.validator_address, key.address);
+
+            let validator = validator::Info::new(key.pub_key, Power::new(1));
+            let sign_bytes = vote.sign_bytes(&chain_id, *encoding);
+            assert!(validator.verify_signature(&sign_bytes, vote.signature.as_bytes()));
+        }
+    }
+}
@@ -222,4 +222,17 @@ mod files {
             "1624DE6420F26BF4B2A2E84CEB7A53C3F1AE77408779B20064782FBADBDF0E365959EE4534"
         );
     }
+
+    /// Parse an example `priv_validator_state.json` to a `PrivValidatorState` struct
+    #[test]
+    fn priv_validator_state_parser() {
+        let raw_priv_validator_state = read_fixture("priv_validator_state.json");
+        let priv_validator_state =
+            PrivValidatorState::parse_json(&raw_priv_validator_state).unwrap();
+        assert_eq!(priv_validator_state.height.value(), 1268);
+        assert_eq!(priv_validator_state.round, 0);
+        assert_eq!(priv_validator_state.step, 3);
+        assert!(priv_validator_state.signature.is_some());
+        assert_eq!(priv_validator_state.sign_bytes.len(), 52);
+    }
 }
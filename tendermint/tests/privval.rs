@@ -0,0 +1,209 @@
+//! End-to-end tests of the remote signer protocol, over a local secret connection
+
+use signatory::ed25519;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+use tendermint::{
+    account, block, chain,
+    config::PrivValidatorState,
+    consensus::state::STEP_PRECOMMIT,
+    encoding::Encoding,
+    net, node,
+    private_key::Ed25519Keypair,
+    privval::{Connection, Signer, SignerClient},
+    validator, vote, Kind, PrivateKey, Proposal, Signature, Time, Vote,
+};
+
+fn make_key() -> PrivateKey {
+    PrivateKey::from(Ed25519Keypair::from(&ed25519::Seed::new([7; 32])))
+}
+
+fn make_identity(seed: u8) -> Ed25519Keypair {
+    Ed25519Keypair::from(&ed25519::Seed::new([seed; 32]))
+}
+
+fn make_vote(height: u64, vote_type: vote::Type) -> Vote {
+    Vote {
+        vote_type,
+        height: block::Height::from(height),
+        round: 0,
+        block_id: None,
+        timestamp: Time::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap(),
+        validator_address: account::Id::from(make_key().public_key()),
+        validator_index: 0,
+        signature: Signature::Ed25519(ed25519::Signature::new([0; 64])),
+    }
+}
+
+/// Spawn a signer for the given chain, listening for a single connection on
+/// the returned address
+fn spawn_signer_listener(chain_id: chain::Id, state: PrivValidatorState) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut conn = Connection::secret(stream, &make_identity(1)).unwrap();
+        let mut signer = Signer::new(chain_id, make_key(), state);
+        signer.serve(&mut conn).unwrap();
+    });
+
+    address
+}
+
+/// Spawn a signer for the given chain, and return a client connected to it
+fn spawn_signer(chain_id: chain::Id) -> SignerClient<Connection> {
+    let address = spawn_signer_listener(chain_id, PrivValidatorState::default());
+    let stream = TcpStream::connect(address).unwrap();
+    SignerClient::new(Connection::secret(stream, &make_identity(2)).unwrap())
+}
+
+#[test]
+fn ping_and_get_public_key() {
+    let mut client = spawn_signer("test-chain".parse().unwrap());
+
+    client.ping().unwrap();
+    assert_eq!(client.public_key().unwrap(), make_key().public_key());
+}
+
+#[test]
+fn connect_to_address_with_and_without_peer_id() {
+    let peer_id = node::Id::from(make_identity(1).public_key().ed25519().unwrap());
+
+    for with_peer_id in &[false, true] {
+        let address =
+            spawn_signer_listener("test-chain".parse().unwrap(), PrivValidatorState::default());
+        let address: net::Address = if *with_peer_id {
+            format!("tcp://{}@{}", peer_id, address)
+        } else {
+            format!("tcp://{}", address)
+        }
+        .parse()
+        .unwrap();
+
+        let conn = Connection::connect(&address, &make_identity(2)).unwrap();
+        let mut client = SignerClient::new(conn);
+        client.ping().unwrap();
+        assert_eq!(client.public_key().unwrap(), make_key().public_key());
+    }
+}
+
+#[test]
+fn reject_unexpected_peer_id() {
+    let address =
+        spawn_signer_listener("test-chain".parse().unwrap(), PrivValidatorState::default());
+    let other_id = node::Id::from(make_identity(3).public_key().ed25519().unwrap());
+    let address: net::Address = format!("tcp://{}@{}", other_id, address).parse().unwrap();
+
+    let err = Connection::connect(&address, &make_identity(2)).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<anomaly::Context<Kind>>().unwrap().kind(),
+        &Kind::InvalidKey
+    );
+}
+
+#[test]
+fn reject_non_tcp_scheme() {
+    let address: net::Address = "http://127.0.0.1:26659".parse().unwrap();
+
+    let err = Connection::connect(&address, &make_identity(2)).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<anomaly::Context<Kind>>().unwrap().kind(),
+        &Kind::Io
+    );
+}
+
+#[test]
+fn sign_votes_and_proposals() {
+    let chain_id: chain::Id = "test-chain".parse().unwrap();
+    let validator = validator::Info::new(make_key().public_key(), vote::Power::new(1));
+    let mut client = spawn_signer(chain_id);
+
+    let proposal = Proposal {
+        height: block::Height::from(1u64),
+        round: 0,
+        pol_round: None,
+        block_id: None,
+        timestamp: Time::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap(),
+        signature: Signature::Ed25519(ed25519::Signature::new([0; 64])),
+    };
+    let signed = client.sign_proposal(&proposal).unwrap();
+    assert!(validator.verify_signature(
+        &signed.sign_bytes(&chain_id, Encoding::Amino),
+        signed.signature.as_bytes()
+    ));
+
+    for vote in vec![
+        make_vote(1, vote::Type::Prevote),
+        make_vote(1, vote::Type::Precommit),
+    ] {
+        let signed = client.sign_vote(&vote).unwrap();
+        assert!(validator.verify_signature(
+            &signed.sign_bytes(&chain_id, Encoding::Amino),
+            signed.signature.as_bytes()
+        ));
+    }
+}
+
+#[test]
+fn refuse_to_double_sign() {
+    let mut client = spawn_signer("test-chain".parse().unwrap());
+
+    client
+        .sign_vote(&make_vote(2, vote::Type::Precommit))
+        .unwrap();
+
+    // Signing the same vote again yields the same signature
+    let vote = make_vote(2, vote::Type::Precommit);
+    assert_eq!(
+        client.sign_vote(&vote).unwrap(),
+        client.sign_vote(&vote).unwrap()
+    );
+
+    // Signing it again with another timestamp yields the same signature and timestamp
+    let mut later_vote = make_vote(2, vote::Type::Precommit);
+    later_vote.timestamp = Time::parse_from_rfc3339("2020-01-01T00:00:05Z").unwrap();
+    assert_eq!(
+        client.sign_vote(&later_vote).unwrap(),
+        client.sign_vote(&vote).unwrap()
+    );
+
+    // Going back in height or step is a double sign
+    for vote in vec![
+        make_vote(1, vote::Type::Precommit),
+        make_vote(2, vote::Type::Prevote),
+    ] {
+        let err = client.sign_vote(&vote).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<anomaly::Context<Kind>>().unwrap().kind(),
+            &Kind::DoubleSign
+        );
+    }
+
+    // The connection remains usable after a refusal
+    client.ping().unwrap();
+}
+
+#[test]
+fn report_unreadable_state_as_signer_error() {
+    // The last signed bytes cannot be decoded, eg. if they were encoded with
+    // another encoding than the requested ones
+    let state = PrivValidatorState {
+        height: block::Height::from(2u64),
+        round: 0,
+        step: STEP_PRECOMMIT,
+        signature: Some(Signature::Ed25519(ed25519::Signature::new([0; 64]))),
+        sign_bytes: vec![],
+    };
+    let address = spawn_signer_listener("test-chain".parse().unwrap(), state);
+    let stream = TcpStream::connect(address).unwrap();
+    let mut client = SignerClient::new(Connection::secret(stream, &make_identity(2)).unwrap());
+
+    let err = client
+        .sign_vote(&make_vote(2, vote::Type::Precommit))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<anomaly::Context<Kind>>().unwrap().kind(),
+        &Kind::Protocol
+    );
+}
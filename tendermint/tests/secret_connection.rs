@@ -0,0 +1,64 @@
+//! End-to-end tests of secret connections, over a local TCP connection
+
+use signatory::ed25519;
+use std::net::SocketAddr;
+use tendermint::{node, private_key::Ed25519Keypair, secret_connection::SecretConnection};
+use tokio::net::{TcpListener, TcpStream};
+
+fn make_key(seed: u8) -> Ed25519Keypair {
+    Ed25519Keypair::from(&ed25519::Seed::new([seed; 32]))
+}
+
+fn node_id(key: &Ed25519Keypair) -> node::Id {
+    node::Id::from(key.public_key().ed25519().unwrap())
+}
+
+/// Establish a secret connection between two local peers
+async fn connect_peers() -> (SecretConnection<TcpStream>, SecretConnection<TcpStream>) {
+    let address: SocketAddr = "127.0.0.1:0".parse().unwrap();
+    let mut listener = TcpListener::bind(address).await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let server = async {
+        let (stream, _) = listener.accept().await.unwrap();
+        SecretConnection::new(stream, &make_key(1)).await.unwrap()
+    };
+    let client = async {
+        let stream = TcpStream::connect(address).await.unwrap();
+        SecretConnection::new(stream, &make_key(2)).await.unwrap()
+    };
+
+    tokio::join!(server, client)
+}
+
+#[tokio::test]
+async fn authenticate_peers() {
+    let (server, client) = connect_peers().await;
+
+    assert_eq!(server.remote_node_id(), node_id(&make_key(2)));
+    assert_eq!(client.remote_node_id(), node_id(&make_key(1)));
+
+    client.verify_remote_node_id(node_id(&make_key(1))).unwrap();
+    assert!(client.verify_remote_node_id(node_id(&make_key(3))).is_err());
+}
+
+#[tokio::test]
+async fn exchange_data_over_several_frames() {
+    let (mut server, mut client) = connect_peers().await;
+    let message: Vec<u8> = (0..2500u32).map(|i| i as u8).collect();
+
+    client.write_all(&message).await.unwrap();
+
+    let mut received = vec![];
+    let mut buf = [0u8; 700];
+    while received.len() < message.len() {
+        let n = server.read(&mut buf).await.unwrap();
+        assert!(n > 0);
+        received.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(received, message);
+
+    // Reading after the peer hung up yields no data
+    drop(client);
+    assert_eq!(server.read(&mut buf).await.unwrap(), 0);
+}
@@ -0,0 +1,98 @@
+//! Commits signed by generated validators
+
+use crate::validator::{validator_set, Validator};
+use sha2::{Digest, Sha256};
+use signatory::ed25519;
+use tendermint::{
+    block::{self, parts, Commit},
+    encoding::Encoding,
+    lite::Header as _,
+    vote, Error, Hash, Signature, Vote,
+};
+
+/// Misbehaviour of a validator when signing a block
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// The validator does not sign the block
+    Absent(Validator),
+    /// The validator signs a precommit for nil instead of the block
+    Nil(Validator),
+    /// The signature of the validator does not match its precommit
+    InvalidSignature(Validator),
+}
+
+impl Fault {
+    /// The faulty validator
+    pub fn validator(&self) -> &Validator {
+        match self {
+            Fault::Absent(validator)
+            | Fault::Nil(validator)
+            | Fault::InvalidSignature(validator) => validator,
+        }
+    }
+}
+
+/// Compute the identifier of the given block header.
+///
+/// The block is deemed to be made of a single part, whose hash is derived from the header hash.
+pub fn block_id(header: &block::Header) -> block::Id {
+    let hash = header.hash();
+
+    let mut parts_hash = [0u8; 32];
+    parts_hash.copy_from_slice(&Sha256::digest(hash.as_bytes()));
+
+    block::Id::new(hash, Some(parts::Header::new(1, Hash::Sha256(parts_hash))))
+}
+
+/// Generate the commit for the given header at round 0, in which every validator
+/// of the given set precommits the block, unless it is faulty.
+///
+/// Precommits are signed with the encoding of the block version of the header.
+pub fn generate_commit(
+    header: &block::Header,
+    validators: &[Validator],
+    faults: &[Fault],
+) -> Result<Commit, Error> {
+    let block_id = block_id(header);
+    let encoding = Encoding::from(&header.version);
+    let set = validator_set(validators);
+
+    let votes = set
+        .validators()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, info)| {
+            let validator = validators.iter().find(|v| v.address() == info.address)?;
+            let fault = faults.iter().find(|f| f.validator().id() == validator.id());
+
+            if let Some(Fault::Absent(_)) = fault {
+                return None;
+            }
+
+            let vote = Vote {
+                vote_type: vote::Type::Precommit,
+                height: header.height,
+                round: 0,
+                block_id: match fault {
+                    Some(Fault::Nil(_)) => None,
+                    _ => Some(block_id.clone()),
+                },
+                timestamp: header.time,
+                validator_address: validator.address(),
+                validator_index: index as u64,
+                signature: Signature::Ed25519(ed25519::Signature::new([0; 64])),
+            };
+
+            let private_key = validator.private_key();
+            let mut vote = vote.sign(&private_key, &header.chain_id, encoding);
+
+            if let Some(Fault::InvalidSignature(_)) = fault {
+                vote.signature = private_key.sign(b"not the sign bytes of the vote");
+            }
+
+            Some(vote)
+        })
+        .collect::<Vec<_>>();
+
+    Commit::from_votes(header.height, 0, block_id, &votes, &set)
+}
@@ -0,0 +1,46 @@
+//! Generators of deterministic test chains for the Tendermint light client.
+//!
+//! Validators are derived from string identifiers, so that the same identifier
+//! always yields the same key pair. A [`LightChain`] then produces, block after
+//! block, correctly hashed headers along with validly signed commits, and lets
+//! tests script validator set changes, forks and faulty validators:
+//!
+//! ```
+//! use tendermint_testgen::{Fault, LightChain, Validator};
+//!
+//! let validators = Validator::from_ids(&["a", "b", "c", "d"]);
+//! let mut chain = LightChain::new("test-chain".parse().unwrap(), validators.clone());
+//!
+//! chain.advance_to(3).unwrap();
+//!
+//! // Validator `d` leaves the set at height 5
+//! chain.set_next_validators(validators[..3].to_vec());
+//! chain.advance_to(5).unwrap();
+//!
+//! // A conflicting chain, signed by the same validators from height 4 onwards
+//! let mut fork = chain.fork_at(4, "fork");
+//! fork.advance_to(5).unwrap();
+//!
+//! // A block which validator `a` did not sign
+//! chain.advance_with(&[Fault::Absent(validators[0].clone())]).unwrap();
+//! ```
+
+#![forbid(unsafe_code)]
+#![deny(
+    warnings,
+    missing_docs,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_import_braces,
+    unused_qualifications
+)]
+
+pub mod commit;
+pub mod light_chain;
+pub mod validator;
+
+pub use crate::{
+    commit::Fault,
+    light_chain::{LightChain, TestBlock},
+    validator::Validator,
+};
@@ -0,0 +1,375 @@
+//! Scripted generation of test chains
+
+use crate::commit::{block_id, generate_commit, Fault};
+use crate::validator::{validator_set, Validator};
+use anomaly::fail;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tendermint::{
+    block::{self, header::Version, signed_header::SignedHeader},
+    chain,
+    encoding::Encoding,
+    lite::ValidatorSet as _,
+    validator, Error, Hash, Kind, Time,
+};
+
+/// Time of the first block of every generated chain
+pub const GENESIS_TIME: &str = "2020-01-01T00:00:00Z";
+
+/// Time elapsed between two consecutive blocks
+pub const BLOCK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Block version of the generated headers
+pub const BLOCK_VERSION: u64 = 10;
+
+/// Signed header of a generated block, along with the validator sets at its height
+/// and at the next height.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestBlock {
+    /// Header and commit of the block
+    pub signed_header: SignedHeader,
+    /// Validator set at the height of the block
+    pub validators: validator::Set,
+    /// Validator set at the next height
+    pub next_validators: validator::Set,
+}
+
+impl TestBlock {
+    /// Height of the block
+    pub fn height(&self) -> u64 {
+        self.signed_header.header.height.value()
+    }
+}
+
+/// A chain of blocks, generated one at a time.
+///
+/// Every block is signed by all the validators of the set at its height,
+/// unless some of them are declared faulty when generating it.
+/// Validator set changes are announced one block in advance, through
+/// the next validators hash, as in Tendermint.
+#[derive(Clone, Debug)]
+pub struct LightChain {
+    chain_id: chain::Id,
+    validators: Vec<Validator>,
+    next_validators: Vec<Validator>,
+    app_hash: Vec<u8>,
+    data_hash: Option<Hash>,
+    blocks: Vec<TestBlock>,
+    /// The validators announced by each block, used to resume the chain at a fork
+    announced_validators: Vec<Vec<Validator>>,
+}
+
+impl LightChain {
+    /// Create an empty chain with the given identifier and initial validators
+    pub fn new(chain_id: chain::Id, validators: Vec<Validator>) -> Self {
+        Self {
+            chain_id,
+            next_validators: validators.clone(),
+            validators,
+            app_hash: vec![],
+            data_hash: None,
+            blocks: vec![],
+            announced_validators: vec![],
+        }
+    }
+
+    /// Identifier of this chain
+    pub fn chain_id(&self) -> chain::Id {
+        self.chain_id
+    }
+
+    /// Height of the latest block of this chain, or 0 if the chain is empty
+    pub fn height(&self) -> u64 {
+        self.blocks.len() as u64
+    }
+
+    /// All the blocks of this chain, by increasing height
+    pub fn blocks(&self) -> &[TestBlock] {
+        &self.blocks
+    }
+
+    /// The block at the given height, if it has been generated
+    pub fn block(&self, height: u64) -> Option<&TestBlock> {
+        if height == 0 {
+            return None;
+        }
+
+        self.blocks.get(height as usize - 1)
+    }
+
+    /// The latest block of this chain, if any
+    pub fn latest(&self) -> Option<&TestBlock> {
+        self.blocks.last()
+    }
+
+    /// Time of the block at the given height
+    pub fn time_at(height: u64) -> Time {
+        let genesis = Time::parse_from_rfc3339(GENESIS_TIME).unwrap();
+        genesis + BLOCK_INTERVAL * (height.saturating_sub(1) as u32)
+    }
+
+    /// Announce the given validators in the next block, so that they sign the blocks
+    /// from the height after it onwards.
+    pub fn set_next_validators(&mut self, validators: Vec<Validator>) -> &mut Self {
+        self.next_validators = validators;
+        self
+    }
+
+    /// Have the given validators sign the blocks from the next one onwards,
+    /// regardless of the validators announced by the latest block.
+    ///
+    /// Unless they were announced, this yields blocks which do not follow from
+    /// the previous ones, as in a lunatic attack.
+    pub fn set_validators(&mut self, validators: Vec<Validator>) -> &mut Self {
+        self.next_validators = validators.clone();
+        self.validators = validators;
+        self
+    }
+
+    /// Set the application hash of the blocks from the next one onwards
+    pub fn set_app_hash(&mut self, app_hash: Vec<u8>) -> &mut Self {
+        self.app_hash = app_hash;
+        self
+    }
+
+    /// Generate the next block, signed by all its validators
+    pub fn advance(&mut self) -> Result<&TestBlock, Error> {
+        self.advance_with(&[])
+    }
+
+    /// Generate the next block, signed by all its validators except for the faulty ones,
+    /// which misbehave as specified.
+    pub fn advance_with(&mut self, faults: &[Fault]) -> Result<&TestBlock, Error> {
+        let height = self.height() + 1;
+
+        let validators = validator_set(&self.validators);
+        let next_validators = validator_set(&self.next_validators);
+
+        let version = Version {
+            block: BLOCK_VERSION,
+            app: 0,
+        };
+        let encoding = Encoding::from(&version);
+
+        let header = block::Header {
+            version,
+            chain_id: self.chain_id,
+            height: block::Height::from(height),
+            time: Self::time_at(height),
+            last_block_id: self
+                .latest()
+                .map(|block| block_id(&block.signed_header.header)),
+            last_commit_hash: None,
+            data_hash: self.data_hash,
+            validators_hash: validators.hash_with_encoding(encoding),
+            next_validators_hash: next_validators.hash_with_encoding(encoding),
+            consensus_hash: Hash::Sha256(sha256(b"consensus params")),
+            app_hash: self.app_hash.clone(),
+            last_results_hash: None,
+            evidence_hash: None,
+            proposer_address: validators.validators()[0].address,
+        };
+
+        let commit = generate_commit(&header, &self.validators, faults)?;
+
+        self.blocks.push(TestBlock {
+            signed_header: SignedHeader { header, commit },
+            validators,
+            next_validators,
+        });
+        self.announced_validators.push(self.next_validators.clone());
+        self.validators = self.next_validators.clone();
+
+        Ok(self.blocks.last().unwrap())
+    }
+
+    /// Generate blocks, signed by all their validators, up to the given height
+    pub fn advance_to(&mut self, height: u64) -> Result<&TestBlock, Error> {
+        if height <= self.height() {
+            fail!(
+                Kind::OutOfRange,
+                "height {} is not above the latest height {}",
+                height,
+                self.height()
+            );
+        }
+
+        while self.height() < height {
+            self.advance()?;
+        }
+
+        Ok(self.blocks.last().unwrap())
+    }
+
+    /// Fork this chain at the given height.
+    ///
+    /// The fork shares the blocks of this chain below the given height. Its blocks from
+    /// that height onwards are signed by the validators announced by the last common block,
+    /// but carry a different data hash, derived from the given label. As such, validators
+    /// signing the blocks of both chains at the same height equivocate.
+    ///
+    /// ## Panics
+    /// - If the height is 1, or is above the height of the next block of this chain
+    pub fn fork_at(&self, height: u64, label: &str) -> Self {
+        assert!(
+            height > 1 && height <= self.height() + 1,
+            "cannot fork at height {}",
+            height
+        );
+
+        let common_height = (height - 1) as usize;
+
+        let mut fork = self.clone();
+        fork.blocks.truncate(common_height);
+        fork.announced_validators.truncate(common_height);
+
+        if let Some(validators) = fork.announced_validators.last() {
+            fork.validators = validators.clone();
+            fork.next_validators = validators.clone();
+        }
+
+        fork.data_hash = Some(Hash::Sha256(sha256(label.as_bytes())));
+        fork
+    }
+}
+
+fn sha256(bytes: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::digest(bytes));
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint::evidence::AttackType;
+    use tendermint::lite::{Commit as _, Header as _};
+
+    fn make_chain(ids: &[&str]) -> LightChain {
+        LightChain::new("test-chain".parse().unwrap(), Validator::from_ids(ids))
+    }
+
+    #[test]
+    fn generated_blocks_are_linked() {
+        let mut chain = make_chain(&["a", "b", "c"]);
+        chain.advance_to(3).unwrap();
+
+        let blocks = chain.blocks();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].signed_header.header.last_block_id, None);
+
+        for pair in blocks.windows(2) {
+            let (previous, block) = (&pair[0].signed_header, &pair[1].signed_header);
+
+            assert_eq!(
+                block.header.last_block_id.as_ref().unwrap().hash,
+                previous.header.hash()
+            );
+            assert_eq!(
+                block.header.validators_hash,
+                previous.header.next_validators_hash
+            );
+            assert!(block.header.time > previous.header.time);
+        }
+    }
+
+    #[test]
+    fn generated_commits_are_valid() {
+        let mut chain = make_chain(&["a", "b", "c"]);
+        let block = chain.advance().unwrap();
+
+        let signed_header = &block.signed_header;
+        assert_eq!(signed_header.header_hash(), signed_header.header.hash());
+        assert!(signed_header.validate(&block.validators).is_ok());
+        assert_eq!(
+            signed_header.voting_power_in(&block.validators).unwrap(),
+            30
+        );
+    }
+
+    #[test]
+    fn faulty_validators_do_not_contribute_voting_power() {
+        let validators = Validator::from_ids(&["a", "b", "c", "d"]);
+        let mut chain = LightChain::new("test-chain".parse().unwrap(), validators.clone());
+
+        let block = chain
+            .advance_with(&[
+                Fault::Absent(validators[0].clone()),
+                Fault::Nil(validators[1].clone()),
+                Fault::InvalidSignature(validators[2].clone()),
+            ])
+            .unwrap();
+
+        let commit = &block.signed_header.commit;
+        assert_eq!(
+            commit.signatures.iter().filter(|s| s.is_absent()).count(),
+            1
+        );
+        assert_eq!(commit.signatures.iter().filter(|s| s.is_nil()).count(), 1);
+        assert!(block
+            .signed_header
+            .voting_power_in(&block.validators)
+            .is_err());
+    }
+
+    #[test]
+    fn validator_set_changes_are_announced() {
+        let validators = Validator::from_ids(&["a", "b", "c"]);
+        let mut chain = LightChain::new("test-chain".parse().unwrap(), validators.clone());
+
+        chain.advance().unwrap();
+        chain.set_next_validators(validators[1..].to_vec());
+        chain.advance_to(3).unwrap();
+
+        let (second, third) = (chain.block(2).unwrap(), chain.block(3).unwrap());
+        assert_eq!(second.validators.validators().len(), 3);
+        assert_eq!(second.next_validators.validators().len(), 2);
+        assert_eq!(third.validators, second.next_validators);
+        assert_eq!(third.signed_header.commit.signatures.len(), 2);
+    }
+
+    #[test]
+    fn forks_are_equivocations() {
+        let mut chain = make_chain(&["a", "b", "c"]);
+        chain.advance_to(4).unwrap();
+
+        let mut fork = chain.fork_at(3, "fork");
+        fork.advance_to(4).unwrap();
+
+        assert_eq!(fork.block(2), chain.block(2));
+
+        let (trusted, conflicting) = (chain.block(3).unwrap(), fork.block(3).unwrap());
+        assert_ne!(
+            trusted.signed_header.header.hash(),
+            conflicting.signed_header.header.hash()
+        );
+        assert_eq!(
+            AttackType::classify(&conflicting.signed_header, &trusted.signed_header),
+            AttackType::Equivocation
+        );
+    }
+
+    #[test]
+    fn replacing_the_validators_is_a_lunatic_attack() {
+        let mut chain = make_chain(&["a", "b", "c"]);
+        chain.advance_to(3).unwrap();
+
+        let mut fork = chain.fork_at(3, "lunatic");
+        fork.set_validators(Validator::from_ids(&["a", "x", "y"]));
+        fork.advance().unwrap();
+
+        let (trusted, conflicting) = (chain.block(3).unwrap(), fork.block(3).unwrap());
+        assert_eq!(
+            AttackType::classify(&conflicting.signed_header, &trusted.signed_header),
+            AttackType::Lunatic
+        );
+    }
+
+    #[test]
+    fn cannot_advance_to_a_lower_height() {
+        let mut chain = make_chain(&["a"]);
+        chain.advance_to(2).unwrap();
+
+        assert!(chain.advance_to(2).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Validators with deterministic keys
+
+use sha2::{Digest, Sha256};
+use signatory::ed25519;
+use tendermint::{account, private_key::Ed25519Keypair, validator, vote, PrivateKey, PublicKey};
+
+/// Voting power of a validator, unless specified otherwise
+pub const DEFAULT_VOTING_POWER: u64 = 10;
+
+/// A validator whose key pair is derived from its identifier.
+///
+/// The seed of the key pair is the SHA-256 hash of the identifier,
+/// so that two validators with the same identifier share the same keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Validator {
+    id: String,
+    voting_power: u64,
+}
+
+impl Validator {
+    /// Create a validator with the given identifier and the default voting power
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            voting_power: DEFAULT_VOTING_POWER,
+        }
+    }
+
+    /// Create one validator with the default voting power per identifier
+    pub fn from_ids(ids: &[&str]) -> Vec<Self> {
+        ids.iter().map(|id| Self::new(id)).collect()
+    }
+
+    /// Set the voting power of this validator
+    pub fn voting_power(mut self, voting_power: u64) -> Self {
+        self.voting_power = voting_power;
+        self
+    }
+
+    /// Identifier of this validator
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Private key of this validator
+    pub fn private_key(&self) -> PrivateKey {
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&Sha256::digest(self.id.as_bytes()));
+
+        PrivateKey::from(Ed25519Keypair::from(&ed25519::Seed::new(seed)))
+    }
+
+    /// Public key of this validator
+    pub fn public_key(&self) -> PublicKey {
+        self.private_key().public_key()
+    }
+
+    /// Address of this validator
+    pub fn address(&self) -> account::Id {
+        account::Id::from(self.public_key())
+    }
+
+    /// Description of this validator, as found in a validator set
+    pub fn info(&self) -> validator::Info {
+        validator::Info::new(self.public_key(), vote::Power::new(self.voting_power))
+    }
+}
+
+/// Build the validator set made of the given validators
+pub fn validator_set(validators: &[Validator]) -> validator::Set {
+    validator::Set::new(validators.iter().map(Validator::info).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint::lite::ValidatorSet as _;
+
+    #[test]
+    fn keys_are_derived_from_the_identifier() {
+        assert_eq!(Validator::new("a").address(), Validator::new("a").address());
+        assert_ne!(Validator::new("a").address(), Validator::new("b").address());
+    }
+
+    #[test]
+    fn validator_set_of_validators() {
+        let validators = vec![Validator::new("a").voting_power(20), Validator::new("b")];
+        let set = validator_set(&validators);
+
+        assert_eq!(set.total_power(), 30);
+        assert_eq!(set.validator(validators[0].address()).unwrap().power(), 20);
+    }
+}